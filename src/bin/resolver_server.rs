@@ -0,0 +1,6 @@
+use factom_did::resolver::server;
+// Serves a DIF Universal Resolver driver for the Factom DID method, configured from the environment as described in
+// server::serve_from_environment.
+fn main() {
+    server::serve_from_environment();
+}
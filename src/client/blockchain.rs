@@ -57,6 +57,34 @@ fn calculate_chain_id<T0, RT>(ext_ids: T0) -> RT {
     }
     return hashlib.sha256(ext_ids_hash_bytes).hexdigest();
 }
+
+//
+// Calculates the hash of an entry, as computed by factomd: SHA256(SHA512(data) + data), where data is the marshalled
+// entry (version byte, chain ID, total ExtIDs size, length-prefixed ExtIDs and content).
+// Parameters
+// ----------
+// chain_id: str
+//     The chain in which the entry is (or will be) recorded.
+// ext_ids: bytes[]
+// content: bytes
+// Returns
+// -------
+// str
+//     The entry hash, hex encoded.
+//
+fn calculate_entry_hash<T0, T1, T2, RT>(chain_id: T0, ext_ids: T1, content: T2) -> RT {
+    let ext_ids_data = bytearray();
+    for ext_id in ext_ids {
+        ext_ids_data.extend(ext_id.len().to_bytes(2, "big"));
+        ext_ids_data.extend(ext_id);
+    }
+    let data = bytearray(b"\x00");
+    data.extend(bytes.fromhex(chain_id));
+    data.extend(ext_ids_data.len().to_bytes(2, "big"));
+    data.extend(ext_ids_data);
+    data.extend(content);
+    return hashlib.sha256((hashlib.sha512(data).digest() + data)).hexdigest();
+}
 fn create_chain<T0, T1, T2, T3, T4>(
     entry_data: T0,
     factomd: T1,
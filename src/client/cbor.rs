@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+use std::*;
+
+let all = vec!["dumps_cbor", "loads_cbor"];
+// CBOR major types (RFC 8949, section 3.1)
+const MAJOR_UNSIGNED: _ = 0;
+const MAJOR_NEGATIVE: _ = 1;
+const MAJOR_BYTES: _ = 2;
+const MAJOR_TEXT: _ = 3;
+const MAJOR_ARRAY: _ = 4;
+const MAJOR_MAP: _ = 5;
+const MAJOR_SIMPLE: _ = 7;
+const SIMPLE_FALSE: _ = 0xf4;
+const SIMPLE_TRUE: _ = 0xf5;
+const SIMPLE_NULL: _ = 0xf6;
+const FLOAT16: _ = 0xf9;
+const FLOAT32: _ = 0xfa;
+const FLOAT64: _ = 0xfb;
+fn dumps_cbor<T0, T1, RT>(value: T0, dag: T1) -> RT {
+    "
+    Serializes a JSON value (or bytes) to deterministically encoded CBOR.
+
+    The encoding follows the core deterministic encoding requirements of RFC 8949 (section 4.2.1):
+        * integers, lengths and tags use the shortest possible encoding
+        * indefinite-length items are never used
+        * map keys must be text strings, and are sorted by length first and then bytewise, which is the order used by
+          DAG-CBOR and the canonical CBOR of RFC 7049
+        * floating point numbers use the shortest of the half, single and double precision encodings which preserves
+          their value, or always the double precision encoding if dag is true, as required by DAG-CBOR
+
+    Parameters
+    ----------
+    value: dict or list or str or bytes or int or float or bool or None
+    dag: bool, optional
+        If true, the value is encoded as DAG-CBOR
+
+    Returns
+    -------
+    bytes
+
+    Raises
+    ------
+    ValueError
+        If the value contains a number which is not finite or does not fit in 64 bits, a map with non-string keys, or
+        a value which cannot be represented in CBOR
+    ";
+    let output = bytearray();
+    _encode(value, dag, output);
+    return bytes(output);
+}
+fn loads_cbor<T0, T1, RT>(data: T0, dag: T1) -> RT {
+    "
+    Deserializes CBOR produced by dumps_cbor.
+
+    Only deterministically encoded CBOR is accepted, so that loads_cbor(data) is serialized back to exactly the same
+    bytes by dumps_cbor.
+
+    Parameters
+    ----------
+    data: bytes
+    dag: bool, optional
+        If true, the data must be encoded as DAG-CBOR
+
+    Returns
+    -------
+    dict or list or str or bytes or int or float or bool or None
+
+    Raises
+    ------
+    ValueError
+        If the data is not valid, deterministically encoded CBOR, or contains items other than the ones produced by
+        dumps_cbor (e.g. tags or undefined)
+    ";
+    let (value, offset) = _decode(bytes(data), 0, dag);
+    if offset != data.len() {
+        raise!(ValueError("Unexpected data after the CBOR item")); //unsupported
+    }
+    return value;
+}
+fn _encode<T0, T1, T2>(value: T0, dag: T1, output: T2) {
+    if value == None {
+        output.append(SIMPLE_NULL);
+    } else {
+        if isinstance(value, bool) {
+            output.append(if value { SIMPLE_TRUE } else { SIMPLE_FALSE });
+        } else {
+            if isinstance(value, int) {
+                if value >= 0 {
+                    _encode_head(MAJOR_UNSIGNED, value, output);
+                } else {
+                    _encode_head(MAJOR_NEGATIVE, -1 - value, output);
+                }
+            } else {
+                if isinstance(value, float) {
+                    _encode_float(value, dag, output);
+                } else {
+                    if isinstance(value, (bytes, bytearray)) {
+                        _encode_head(MAJOR_BYTES, value.len(), output);
+                        output.extend(value);
+                    } else {
+                        if isinstance(value, str) {
+                            let encoded = value.encode("utf-8");
+                            _encode_head(MAJOR_TEXT, encoded.len(), output);
+                            output.extend(encoded);
+                        } else {
+                            if isinstance(value, (list, tuple)) {
+                                _encode_head(MAJOR_ARRAY, value.len(), output);
+                                for item in value {
+                                    _encode(item, dag, output);
+                                }
+                            } else {
+                                if isinstance(value, dict) {
+                                    if !value.iter().all(|k| isinstance(k, str)) {
+                                        raise!(ValueError("Map keys must be strings")); //unsupported
+                                    }
+                                    _encode_head(MAJOR_MAP, value.len(), output);
+                                    for key in sorted(value, _map_key_order) {
+                                        _encode(key, dag, output);
+                                        _encode(value[key], dag, output);
+                                    }
+                                } else {
+                                    raise!(ValueError("Value cannot be serialized: {}".format(value))); //unsupported
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+fn _encode_head<T0, T1, T2>(major_type: T0, argument: T1, output: T2) {
+    if argument < 0 || argument >= 2_u64.pow(64) {
+        raise!(ValueError("Integers must fit in 64 bits")); //unsupported
+    }
+    let initial_byte = major_type << 5;
+    if argument < 24 {
+        output.append(initial_byte | argument);
+    } else {
+        if argument < 0x100 {
+            output.append(initial_byte | 24);
+            output.extend(struct_.pack(">B", argument));
+        } else {
+            if argument < 0x10000 {
+                output.append(initial_byte | 25);
+                output.extend(struct_.pack(">H", argument));
+            } else {
+                if argument < 0x100000000 {
+                    output.append(initial_byte | 26);
+                    output.extend(struct_.pack(">I", argument));
+                } else {
+                    output.append(initial_byte | 27);
+                    output.extend(struct_.pack(">Q", argument));
+                }
+            }
+        }
+    }
+}
+fn _encode_float<T0, T1, T2>(value: T0, dag: T1, output: T2) {
+    if !math.isfinite(value) {
+        raise!(ValueError("Numbers must be finite")); //unsupported
+    }
+    if !dag {
+        for (head, format) in ((FLOAT16, ">e"), (FLOAT32, ">f")) {
+            let try_dummy = {
+                //unsupported
+                let encoded = struct_.pack(format, value);
+            };
+            let except!(OverflowError) = {
+                //unsupported
+                continue;
+            };
+            if struct_.unpack(format, encoded)[0] == value {
+                output.append(head);
+                output.extend(encoded);
+                return;
+            }
+        }
+    }
+    output.append(FLOAT64);
+    output.extend(struct_.pack(">d", value));
+}
+fn _map_key_order<T0, RT>(key: T0) -> RT {
+    let encoded = key.encode("utf-8");
+    return (encoded.len(), encoded);
+}
+fn _decode<T0, T1, T2, RT>(data: T0, offset: T1, dag: T2) -> RT {
+    if offset >= data.len() {
+        raise!(ValueError("Unexpected end of CBOR data")); //unsupported
+    }
+    let initial_byte = data[offset];
+    let major_type = initial_byte >> 5;
+    if major_type == MAJOR_SIMPLE {
+        return _decode_simple(data, offset, dag);
+    }
+    let (argument, offset) = _decode_head(data, offset);
+    if major_type == MAJOR_UNSIGNED {
+        return (argument, offset);
+    }
+    if major_type == MAJOR_NEGATIVE {
+        return (-1 - argument, offset);
+    }
+    if major_type == MAJOR_BYTES || major_type == MAJOR_TEXT {
+        let end = offset + argument;
+        if end > data.len() {
+            raise!(ValueError("Unexpected end of CBOR data")); //unsupported
+        }
+        let value = data[offset..end];
+        return (
+            if major_type == MAJOR_TEXT {
+                value.decode("utf-8")
+            } else {
+                value
+            },
+            end,
+        );
+    }
+    if major_type == MAJOR_ARRAY {
+        let items = vec![];
+        for _ in (0..argument) {
+            let (item, next_offset) = _decode(data, offset, dag);
+            items.append(item);
+            offset = next_offset;
+        }
+        return (items, offset);
+    }
+    if major_type == MAJOR_MAP {
+        let value = HashMap::new();
+        let previous_key = None;
+        for _ in (0..argument) {
+            let (key, next_offset) = _decode(data, offset, dag);
+            if !isinstance(key, str) {
+                raise!(ValueError("Map keys must be strings")); //unsupported
+            }
+            if previous_key != None && _map_key_order(key) <= _map_key_order(previous_key) {
+                raise!(ValueError("Map keys must be unique and sorted")); //unsupported
+            }
+            let (item, next_offset) = _decode(data, next_offset, dag);
+            value[key] = item;
+            previous_key = key;
+            offset = next_offset;
+        }
+        return (value, offset);
+    }
+    raise!(ValueError("Unsupported CBOR major type: {}".format(major_type))); //unsupported
+}
+fn _decode_head<T0, T1, RT>(data: T0, offset: T1) -> RT {
+    let additional_info = data[offset] & 0x1f;
+    if additional_info < 24 {
+        return (additional_info, offset + 1);
+    }
+    if additional_info > 27 {
+        raise!(ValueError("Indefinite-length and reserved CBOR items are not supported")); //unsupported
+    }
+    let size = 1 << (additional_info - 24);
+    if offset + 1 + size > data.len() {
+        raise!(ValueError("Unexpected end of CBOR data")); //unsupported
+    }
+    let argument = int.from_bytes(data[(offset + 1)..(offset + 1 + size)], "big");
+    let minimum = if size == 1 { 24 } else { 1 << (4 * size) };
+    if argument < minimum {
+        raise!(ValueError("CBOR integers must use the shortest encoding")); //unsupported
+    }
+    return (argument, offset + 1 + size);
+}
+fn _decode_simple<T0, T1, T2, RT>(data: T0, offset: T1, dag: T2) -> RT {
+    let initial_byte = data[offset];
+    if initial_byte == SIMPLE_FALSE {
+        return (false, offset + 1);
+    }
+    if initial_byte == SIMPLE_TRUE {
+        return (true, offset + 1);
+    }
+    if initial_byte == SIMPLE_NULL {
+        return (None, offset + 1);
+    }
+    let format = [(FLOAT16, ">e"), (FLOAT32, ">f"), (FLOAT64, ">d")]
+        .iter()
+        .cloned()
+        .collect::<HashMap<_, _>>()
+        .get(initial_byte);
+    if format == None {
+        raise!(ValueError("Unsupported CBOR simple value: {}".format(initial_byte))); //unsupported
+    }
+    let end = offset + 1 + struct_.calcsize(format);
+    if end > data.len() {
+        raise!(ValueError("Unexpected end of CBOR data")); //unsupported
+    }
+    let value = struct_.unpack(format, data[(offset + 1)..end])[0];
+    let output = bytearray();
+    _encode_float(value, dag, output);
+    if bytes(output) != data[offset..end] {
+        raise!(ValueError("CBOR floating point numbers must use the deterministic encoding")); //unsupported
+    }
+    return (value, end);
+}
@@ -7,12 +7,18 @@ use factom_did::client::constants::{*};
 use factom_did::client::deactivator::{DIDDeactivator};
 use factom_did::client::encryptor::{encrypt_keys};
 use factom_did::client::enums::{DIDKeyPurpose, EntryType, KeyType, Network};
+use factom_did::client::keys::abstract::{AbstractDIDKey};
 use factom_did::client::keys::did::{DIDKey};
 use factom_did::client::keys::management::{ManagementKey};
+use factom_did::client::mnemonic::{derive_private_key, generate_mnemonic, mnemonic_to_seed, KEY_DERIVATION_GAP_LIMIT};
+use factom_did::client::serialization::{dumps_entry_content};
 use factom_did::client::service::{Service};
 use factom_did::client::updater::{DIDUpdater};
 use factom_did::client::validators::{validate_did};
 use factom_did::client::version_upgrader::{DIDVersionUpgrader};
+use factom_did::resolver::schema::{get_schema_validator};
+use factom_did::resolver::validators::{validate_id_against_network, validate_management_key_id_against_chain_id};
+use jsonschema::exceptions::{ValidationError};
 let all = vec!["DID", "KeyType", "DIDKeyPurpose"];
 struct DID {
 _id: ST0,
@@ -24,6 +30,9 @@ spec_version: ST5,
 used_key_aliases: ST6,
 used_service_aliases: ST7,
 nonce: ST8,
+mnemonic: ST9,
+seed: ST10,
+key_derivation_indices: HashMap<_,_>,
 }
 
 impl DID {
@@ -44,8 +53,11 @@ impl DID {
         A list of DID keys
     services: Service[], optional
         A list of services
+    mnemonic_passphrase: str, optional
+        A passphrase protecting the BIP-39 recovery phrase from which new keys are derived. The same passphrase must
+        be passed to restore_from_mnemonic.
     ";
-fn init<T0, T1, T2, T3, T4>(&self, did: T0, management_keys: T1, did_keys: T2, services: T3, spec_version: T4)  {
+fn init<T0, T1, T2, T3, T4, T5>(&self, did: T0, management_keys: T1, did_keys: T2, services: T3, spec_version: T4, mnemonic_passphrase: T5)  {
 self._id = if did == None||!self.is_valid_did(did) { self._generate_did() } else { did };
 self.management_keys = if management_keys == None { vec![] } else { management_keys };
 self.did_keys = if did_keys == None { vec![] } else { did_keys };
@@ -54,6 +66,9 @@ self.network = DID::_get_network_from_id(self._id);
 self.spec_version = spec_version;
 self.used_key_aliases = set();
 self.used_service_aliases = set();
+self.mnemonic = generate_mnemonic();
+self.seed = mnemonic_to_seed(self.mnemonic, mnemonic_passphrase);
+self.key_derivation_indices = HashMap::new();
 for key in self.management_keys {
 self._check_alias_is_unique_and_add_to_used(self.used_key_aliases, key.alias);
 }
@@ -100,19 +115,21 @@ raise!(RuntimeError("Cannot update DID without management keys.")); //unsupporte
 }
 return DIDUpdater(self);
 }
-fn method_spec_version_upgrade<T0, RT>(&self, new_spec_version: T0) -> RT {
+fn method_spec_version_upgrade<T0, T1, RT>(&self, new_spec_version: T0, registry: T1) -> RT {
 "
         Parameters
         ----------
         new_spec_version: str
             The new DID Method version
+        registry: EntryProcessorRegistry, optional
+            The registry whose DID method version rules the new version must have. Defaults to the default registry.
 
         Raises
         ------
         RuntimeError
             If no management keys are available for the DID
         ValueError
-            If the new version is not an upgrade on the current version
+            If the new version is not an upgrade on the current version or is not supported by the registry
 
         Returns
         -------
@@ -121,7 +138,7 @@ fn method_spec_version_upgrade<T0, RT>(&self, new_spec_version: T0) -> RT {
 if !self.management_keys {
 raise!(RuntimeError("Cannot upgrade method spec version for DID without management keys.")); //unsupported
 }
-return DIDVersionUpgrader(self, new_spec_version);
+return DIDVersionUpgrader(self, new_spec_version, registry);
 }
 fn deactivate<RT>(&self) -> RT {
 "
@@ -175,7 +192,7 @@ fn management_key<T0, T1, T2, T3, T4, RT>(&self, alias: T0, priority: T1, key_ty
 if !controller {
 controller = self.id;
 }
-let key = ManagementKey(alias, priority, key_type, controller, priority_requirement);
+let key = ManagementKey(alias, priority, key_type, controller, priority_requirement, None, self._derive_private_key(alias, key_type, self._next_derivation_index(alias)));
 self._check_alias_is_unique_and_add_to_used(self.used_key_aliases, alias);
 self.management_keys.append(key);
 return self;
@@ -201,11 +218,43 @@ fn did_key<T0, T1, T2, T3, T4, RT>(&self, alias: T0, purpose: T1, key_type: T2,
 if !controller {
 controller = self.id;
 }
-let key = DIDKey(alias, purpose, key_type, controller, priority_requirement);
+let key = DIDKey(alias, purpose, key_type, controller, priority_requirement, None, self._derive_private_key(alias, key_type, self._next_derivation_index(alias)));
 self._check_alias_is_unique_and_add_to_used(self.used_key_aliases, alias);
 self.did_keys.append(key);
 return self;
 }
+fn add_management_key<T0, RT>(&self, key: T0) -> RT {
+"
+        Adds an existing management key to the DID, e.g. one imported with ManagementKey.from_pem.
+
+        The key may be public-only, in which case entries which must be signed with it have to be signed externally.
+
+        Parameters
+        ----------
+        key: ManagementKey
+        ";
+if !isinstance(key, ManagementKey) {
+raise!(ValueError("key must be a ManagementKey")); //unsupported
+}
+self._check_alias_is_unique_and_add_to_used(self.used_key_aliases, key.alias);
+self.management_keys.append(key);
+return self;
+}
+fn add_did_key<T0, RT>(&self, key: T0) -> RT {
+"
+        Adds an existing DID key to the DID, e.g. one imported with DIDKey.from_jwk.
+
+        Parameters
+        ----------
+        key: DIDKey
+        ";
+if !isinstance(key, DIDKey) {
+raise!(ValueError("key must be a DIDKey")); //unsupported
+}
+self._check_alias_is_unique_and_add_to_used(self.used_key_aliases, key.alias);
+self.did_keys.append(key);
+return self;
+}
 fn service<T0, T1, T2, T3, T4, RT>(&self, alias: T0, service_type: T1, endpoint: T2, priority_requirement: T3, custom_fields: T4) -> RT {
 "
         Adds a new service to the DID Document.
@@ -257,7 +306,7 @@ if !any(management_keys.iter().map(|key| key["priority"] == 0)) {
 raise!(ValueError("At least one management key must have priority 0.")); //unsupported
 }
 let ext_ids = vec![EntryType::Create.value.encode("utf-8"), ENTRY_SCHEMA_V100.encode("utf-8"), self.nonce];
-let entry_content = json.dumps(self._get_did_document(), (",", ":")).encode("utf-8");
+let entry_content = dumps_entry_content(self._get_did_document(), false).encode("utf-8");
 let entry_size = calculate_entry_size(ext_ids, entry_content);
 if entry_size > ENTRY_SIZE_LIMIT {
 raise!(RuntimeError("You have exceeded the entry size limit! Please remove some of your keys or services.")); //unsupported
@@ -299,6 +348,144 @@ fn export_encrypted_keys_as_json<T0, RT>(&self, password: T0) -> RT {
 let encryption_result = encrypt_keys(self.management_keys, self.did_keys, password);
 return json.dumps([("data", String::from(urlsafe_b64encode(encryption_result["data"]), "utf8")), ("encryptionAlgo", [("name", "AES-GCM"), ("iv", String::from(urlsafe_b64encode(encryption_result["iv"]), "utf8")), ("salt", String::from(urlsafe_b64encode(encryption_result["salt"]), "utf8")), ("tagLength", 128)].iter().cloned().collect::<HashMap<_,_>>()), ("did", self.id)].iter().cloned().collect::<HashMap<_,_>>());
 }
+fn export_mnemonic<RT>(&self) -> RT {
+"
+        Exports the 24-word BIP-39 recovery phrase from which the private keys of the DID are derived.
+
+        Returns
+        -------
+        str
+            The recovery phrase
+
+        Raises
+        ------
+        RuntimeError
+            If some of the keys were not derived from the recovery phrase (e.g. keys supplied to the constructor)
+        ";
+for key in self.management_keys.iter().chain(self.did_keys.iter()) {
+if self.key_derivation_indices.iter().all(|&x| x != key.alias) {
+raise!(RuntimeError("Key "{}" was not derived from the recovery phrase of this DID.".format(key.alias))); //unsupported
+}
+}
+return self.mnemonic;
+}
+fn from_entry_data<T0, T1, T2, RT>(ext_ids: T0, content: T1, network: T2) -> RT {
+"
+        Rebuilds a DID from the ExtIDs and content of a DIDManagement entry, without accessing the blockchain.
+
+        The entry is validated as the resolver would: the ExtIDs must identify a DIDManagement entry with entry schema
+        version 1.0.0, the content must match the DIDManagement schema, and the chain ID in every key and service
+        identifier must be the one calculated from the ExtIDs (i.e. from the nonce). The content must be exactly the
+        bytes which the resulting DID exports with export_entry_data, so that the DID can be re-exported without
+        changing the entry hash. The resulting DID has no private keys.
+
+        Parameters
+        ----------
+        ext_ids: bytes[]
+            The ExtIDs of the entry: the entry type, the entry schema version and the nonce
+        content: bytes or str
+            The entry content
+        network: Network, optional
+            The network of the DID. If not provided, it is taken from the identifiers in the entry.
+
+        Returns
+        -------
+        DID
+
+        Raises
+        ------
+        ValueError
+            If the entry is not a valid DIDManagement entry, its identifiers do not match the chain ID calculated from
+            the nonce or the given network, or its content differs from the bytes export_entry_data produces
+        ";
+if ext_ids.len() != 3 || ext_ids[0] != EntryType::Create.value.encode("utf-8") || ext_ids[1] != ENTRY_SCHEMA_V100.encode("utf-8") {
+raise!(ValueError("Invalid or missing {} entry ExtIDs".format(EntryType::Create.value))); //unsupported
+}
+let content = if type_(content) == str { content.encode("utf-8") } else { content };
+let try_dummy = { //unsupported
+let parsed_content = json.loads(content.decode("utf-8"));
+get_schema_validator("did_management_entry.json").validate(parsed_content);
+};
+let except!((UnicodeDecodeError, json.JSONDecodeError, ValidationError) as e) = { //unsupported
+raise!(ValueError("Invalid {} entry content: {}".format(EntryType::Create.value, e))); //unsupported
+};
+let chain_id = calculate_chain_id(ext_ids);
+let entry_ids = parsed_content["managementKey"].iter().chain(parsed_content.get("didKey", vec![]).iter()).chain(parsed_content.get("service", vec![]).iter()).map(|d| d["id"]).collect::<Vec<_>>();
+for id_value in entry_ids {
+if !validate_management_key_id_against_chain_id(id_value, chain_id) {
+raise!(ValueError("Identifier {} does not match the chain ID {} calculated from the nonce".format(id_value, chain_id))); //unsupported
+}
+}
+let mut did_id = entry_ids[0].split("#")[0];
+if !did_id.startswith("{}:".format(DID_METHOD_NAME)) {
+did_id = "{}:{}".format(DID_METHOD_NAME, chain_id);
+}
+let entry_network = DID::_get_network_from_id(did_id);
+if entry_ids.iter().any(|id_value| !validate_id_against_network(id_value, entry_network)) {
+raise!(ValueError("The identifiers in the entry refer to different networks")); //unsupported
+}
+if network != None && network != entry_network {
+raise!(ValueError("The entry is for network {}, not {}".format(entry_network.value, network.value))); //unsupported
+}
+let did = DID(did_id, parsed_content["managementKey"].iter().map(|d| ManagementKey::from_entry_dict(d, ENTRY_SCHEMA_V100)).collect::<Vec<_>>(), parsed_content.get("didKey", vec![]).iter().map(|d| DIDKey::from_entry_dict(d, ENTRY_SCHEMA_V100)).collect::<Vec<_>>(), parsed_content.get("service", vec![]).iter().map(|d| Service::from_entry_dict(d, ENTRY_SCHEMA_V100)).collect::<Vec<_>>(), parsed_content["didMethodVersion"], None);
+did.nonce = ext_ids[2];
+if did.export_entry_data()["content"] != content {
+raise!(ValueError("The entry content is not the one the DID object would record, e.g. because of unsupported fields or a different serialization")); //unsupported
+}
+return did;
+}
+fn restore_from_mnemonic<T0, T1, T2, T3, RT>(words: T0, did_id: T1, resolver: T2, passphrase: T3) -> RT {
+"
+        Restores a DID and its private keys from a recovery phrase.
+
+        The currently active management and DID keys are resolved from the blockchain and their private keys are
+        re-derived from the recovery phrase. Each re-derived key is checked against the public key recorded on-chain.
+        The DID method version is the one recorded on-chain, and the derivation indices of revoked keys are recovered
+        from the key history, so that keys added later under the same aliases are not derived again.
+
+        Parameters
+        ----------
+        words: str
+            The recovery phrase, as returned by export_mnemonic
+        did_id: str
+            The DID to restore
+        resolver: DIDResolver or CachingResolver
+            The resolver used to obtain the chain state of the DID
+        passphrase: str, optional
+            The passphrase protecting the recovery phrase, if one was passed to the DID constructor
+
+        Returns
+        -------
+        DID
+            The restored DID, with private keys for all active management and DID keys
+
+        Raises
+        ------
+        ValueError
+            If the recovery phrase is invalid or if a key cannot be re-derived from it (e.g. because the passphrase
+            is wrong)
+        ";
+let seed = mnemonic_to_seed(words, passphrase);
+let state = if hasattr(resolver, "resolve_chain_state") { resolver.resolve_chain_state(did_id) } else { resolver.resolve_incremental(did_id, None) };
+let key_derivation_indices = DID::_find_revoked_derivation_indices(seed, did_id, state);
+let management_keys = vec![];
+for key in state.management_keys.values() {
+let index = DID::_find_derivation_index(seed, key);
+management_keys.push(ManagementKey(key.alias, key.priority, key.key_type, key.controller, key.priority_requirement, key.public_key, derive_private_key(seed, key.key_type, key.alias, index)));
+key_derivation_indices[key.alias] = index;
+}
+let did_keys = vec![];
+for key in state.did_keys.values() {
+let index = DID::_find_derivation_index(seed, key);
+did_keys.push(DIDKey(key.alias, key.purpose, key.key_type, key.controller, key.priority_requirement, key.public_key, derive_private_key(seed, key.key_type, key.alias, index)));
+key_derivation_indices[key.alias] = index;
+}
+let did = DID(did_id, management_keys, did_keys, state.services.values().collect::<Vec<_>>(), state.method_version, None);
+did.mnemonic = words;
+did.seed = seed;
+did.key_derivation_indices = key_derivation_indices;
+return did;
+}
 fn record_on_chain<T0, T1, T2, T3>(&self, factomd: T0, walletd: T1, ec_address: T2, verbose: T3)  {
 "
         Attempts to create the DIDManagement chain.
@@ -343,6 +530,66 @@ did_document["service"] = services;
 }
 return did_document;
 }
+fn _derive_private_key<T0, T1, T2, RT>(&self, alias: T0, key_type: T1, index: T2) -> RT {
+"
+        Derives the private key for the key with the given alias from the seed of the DID and records the derivation
+        index used.
+        ";
+self.key_derivation_indices[alias] = index;
+return derive_private_key(self.seed, key_type, alias, index);
+}
+fn _next_derivation_index<T0, RT>(&self, alias: T0) -> RT {
+"
+        Returns the first derivation index not used yet for the given alias, so that a key added again under the alias
+        of a revoked key does not reproduce the revoked key.
+        ";
+return (self.key_derivation_indices.get(alias, -1) + 1);
+}
+fn _derive_rotated_private_key<T0, RT>(&self, key: T0) -> RT {
+"
+        Derives the next private key for a key which is being rotated. Keys which were not derived from the seed of
+        the DID are rotated to a new random key pair.
+        ";
+if self.key_derivation_indices.iter().all(|&x| x != key.alias) {
+return None;
+}
+return self._derive_private_key(key.alias, key.key_type, (self.key_derivation_indices[key.alias] + 1));
+}
+fn _find_revoked_derivation_indices<T0, T1, T2, RT>(seed: T0, did_id: T1, state: T2) -> RT {
+"
+        Finds the highest derivation index used for each alias in the key history of the chain state, among the keys
+        derived from the seed. Keys which were not derived from the seed are ignored.
+        ";
+let key_derivation_indices = HashMap::new();
+for record in state.key_history {
+let key_type = KeyType::from_str(record["type"]);
+for index in (0..KEY_DERIVATION_GAP_LIMIT) {
+let candidate = AbstractDIDKey(record["alias"], key_type, did_id, None, None, derive_private_key(seed, key_type, record["alias"], index));
+if candidate.to_entry_dict(did_id, ENTRY_SCHEMA_V100)[record["publicKeyField"]] == record["publicKey"] {
+key_derivation_indices[record["alias"]] = max(key_derivation_indices.get(record["alias"], index), index);
+break;
+}
+}
+}
+return key_derivation_indices;
+}
+fn _find_derivation_index<T0, T1, RT>(seed: T0, key: T1) -> RT {
+"
+        Finds the derivation index at which the private key for the given on-chain key can be re-derived from the seed.
+
+        Raises
+        ------
+        ValueError
+            If the key cannot be re-derived within KEY_DERIVATION_GAP_LIMIT rotations
+        ";
+for index in (0..KEY_DERIVATION_GAP_LIMIT) {
+let candidate = AbstractDIDKey(key.alias, key.key_type, key.controller, key.priority_requirement, None, derive_private_key(seed, key.key_type, key.alias, index));
+if candidate.public_key == key.public_key {
+return index;
+}
+}
+raise!(ValueError("Key "{}" does not match the on-chain public key for the given recovery phrase.".format(key.alias))); //unsupported
+}
 fn _generate_did<RT>(&self) -> RT {
 "
         Generates a new DID Id.
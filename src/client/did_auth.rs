@@ -0,0 +1,341 @@
+use std::collections::HashMap;
+use std::*;
+
+use factom::exceptions::FactomAPIError;
+use factom_did::client::enums::DIDKeyPurpose;
+use factom_did::client::jws::{sign_jwt, verify_jwt, InvalidJWS};
+use factom_did::client::keys::did::DIDKey;
+use factom_did::client::keys::formats::{b64url_decode, b64url_encode};
+use factom_did::client::serialization::dumps_jcs;
+use factom_did::resolver::exceptions::InvalidDIDChain;
+let all = vec![
+    "DIDAuthError",
+    "NonceStore",
+    "InMemoryNonceStore",
+    "DIDAuthVerifier",
+    "respond_to_challenge",
+    "create_id_token",
+];
+// Number of seconds during which a challenge can be answered
+const DEFAULT_CHALLENGE_TTL: _ = 300;
+// Number of seconds during which an ID token created in response to a challenge is valid
+const DEFAULT_ID_TOKEN_TTL: _ = 300;
+struct DIDAuthError {}
+
+impl DIDAuthError {
+    /*pass*/
+}
+// Storage for the nonces of outstanding challenges, which ensures that each challenge is answered at most once.
+// Implementations shared between several verifier instances (e.g. backed by a database) must make consume atomic.
+trait NonceStore {
+    // Records a nonce, which can be consumed until the given UNIX timestamp.
+    fn add<T0, T1>(&self, nonce: T0, expires_at: T1);
+    // Removes the nonce and returns True if it was recorded and has not expired, or False otherwise.
+    fn consume<T0, T1, RT>(&self, nonce: T0, now: T1) -> RT;
+}
+struct InMemoryNonceStore {
+    nonces: ST0,
+}
+
+impl NonceStore for InMemoryNonceStore {
+    "
+    In-memory nonce store, suitable for a single verifier process. Expired nonces are purged whenever a nonce is added.
+    ";
+    fn init(&self) {
+        self.nonces = HashMap::new();
+    }
+    fn add<T0, T1>(&self, nonce: T0, expires_at: T1) {
+        let now = time.time();
+        self.nonces = self
+            .nonces
+            .items()
+            .iter()
+            .filter(|(_, e)| e >= now)
+            .map(|(n, e)| (n, e))
+            .collect::<HashMap<_, _>>();
+        self.nonces[nonce] = expires_at;
+    }
+    fn consume<T0, T1, RT>(&self, nonce: T0, now: T1) -> RT {
+        let expires_at = self.nonces.pop(nonce, None);
+        return expires_at != None && now <= expires_at;
+    }
+}
+struct DIDAuthVerifier {
+    resolver: ST0,
+    domain: ST1,
+    nonce_store: ST2,
+    challenge_ttl: ST3,
+}
+
+impl DIDAuthVerifier {
+    "
+    Authenticates the holders of did:factom DIDs through a challenge-response exchange.
+
+    The verifier creates a challenge with a random nonce, its domain and an expiry time. The holder answers it with a
+    signature by one of its DID keys with the authentication purpose, either as a SIOPv2 self-issued ID token or in a
+    minimal JSON format. The verifier resolves the DID, checks the key and the signature, and consumes the nonce, so
+    that a response cannot be replayed.
+
+    Attributes
+    ----------
+    resolver: DIDResolver or CachingResolver
+        The resolver used to resolve the DIDs of the holders
+    domain: str
+        The domain of the verifier (the client_id of SIOPv2). Responses for other domains are rejected.
+    nonce_store: NonceStore, optional
+        The store of outstanding nonces. Defaults to an InMemoryNonceStore.
+    challenge_ttl: int, optional
+        Number of seconds during which a challenge can be answered. Defaults to 300.
+    ";
+    fn init<T0, T1, T2, T3>(&self, resolver: T0, domain: T1, nonce_store: T2, challenge_ttl: T3) {
+        if !domain {
+            raise!(ValueError("The domain must not be empty.")); //unsupported
+        }
+        self.resolver = resolver;
+        self.domain = domain;
+        self.nonce_store = if nonce_store == None {
+            InMemoryNonceStore()
+        } else {
+            nonce_store
+        };
+        self.challenge_ttl = if challenge_ttl == None {
+            DEFAULT_CHALLENGE_TTL
+        } else {
+            challenge_ttl
+        };
+    }
+    fn create_challenge<RT>(&self) -> RT {
+        "
+        Creates a challenge and records its nonce.
+
+        Returns
+        -------
+        dict
+            The challenge, with the keys 'nonce', 'domain' and 'expiresAt' (a UNIX timestamp)
+        ";
+        let challenge = [
+            ("nonce", secrets.token_urlsafe(32)),
+            ("domain", self.domain),
+            ("expiresAt", int(time.time()) + self.challenge_ttl),
+        ]
+        .iter()
+        .cloned()
+        .collect::<HashMap<_, _>>();
+        self.nonce_store.add(challenge["nonce"], challenge["expiresAt"]);
+        return challenge;
+    }
+    fn verify_response<T0, RT>(&self, response: T0) -> RT {
+        "
+        Verifies a response in the minimal JSON format, as produced by respond_to_challenge.
+
+        Parameters
+        ----------
+        response: dict
+
+        Returns
+        -------
+        str
+            The authenticated DID
+
+        Raises
+        ------
+        DIDAuthError
+            If the response is malformed, is not for this domain, was signed with a key which is not an active
+            authentication key of the DID, or its nonce is unknown, has expired or has already been used
+        ";
+        if !isinstance(response, dict)
+            || ["did", "nonce", "domain", "verificationMethod", "signature"]
+                .iter()
+                .any(|f| !isinstance(response.get(f), str))
+        {
+            raise!(DIDAuthError("Malformed DID Auth response")); //unsupported
+        }
+        if response["domain"] != self.domain {
+            raise!(DIDAuthError("The response is not for this domain")); //unsupported
+        }
+        let key = _resolve_authentication_key(self.resolver, response["did"], response["verificationMethod"]);
+        let try_dummy = {
+            //unsupported
+            let valid = key.verify(
+                _signing_input(response).encode("utf-8"),
+                b64url_decode(response["signature"]),
+                None,
+            );
+        };
+        let except!((AssertionError, ValueError, binascii.Error)) = {
+            //unsupported
+            let valid = false;
+        };
+        if !valid {
+            raise!(DIDAuthError("Invalid signature")); //unsupported
+        }
+        self._consume_nonce(response["nonce"]);
+        return response["did"];
+    }
+    fn verify_id_token<T0, RT>(&self, token: T0) -> RT {
+        "
+        Verifies a SIOPv2 self-issued ID token, as produced by create_id_token.
+
+        Parameters
+        ----------
+        token: str
+
+        Returns
+        -------
+        str
+            The authenticated DID
+
+        Raises
+        ------
+        DIDAuthError
+            If the token is not valid or has expired, is not self-issued by the DID of its signing key, is not for this
+            domain, was signed with a key which is not an active authentication key, or its nonce is unknown, has
+            expired or has already been used
+        ";
+        let try_dummy = {
+            //unsupported
+            let claims = verify_jwt(token, self.resolver, DIDKeyPurpose::AuthenticationKey, None);
+        };
+        let except!(InvalidJWS as e) = {
+            //unsupported
+            raise!(DIDAuthError("Invalid ID token: {}".format(e))); //unsupported
+        };
+        if claims.get("iss") == None || claims.get("sub") != claims["iss"] {
+            raise!(DIDAuthError("The ID token must be self-issued, with the DID as 'iss' and 'sub'")); //unsupported
+        }
+        if claims.get("aud") != self.domain {
+            raise!(DIDAuthError("The ID token is not for this domain")); //unsupported
+        }
+        if claims.iter().all(|&x| x != "exp") {
+            raise!(DIDAuthError("The ID token must have an expiry")); //unsupported
+        }
+        self._consume_nonce(claims.get("nonce"));
+        return claims["sub"];
+    }
+    fn _consume_nonce<T0>(&self, nonce: T0) {
+        if !isinstance(nonce, str) || !self.nonce_store.consume(nonce, time.time()) {
+            raise!(DIDAuthError("Unknown, expired or already used challenge")); //unsupported
+        }
+    }
+}
+fn respond_to_challenge<T0, T1, T2, RT>(challenge: T0, key: T1, did: T2) -> RT {
+    "
+    Answers a challenge in the minimal JSON format.
+
+    The signature covers the JCS serialization (RFC 8785) of the DID, the nonce and domain of the challenge and the
+    full DID URL of the key. Callers should check that the domain of the challenge is the one they intend to
+    authenticate to.
+
+    Parameters
+    ----------
+    challenge: dict
+        The challenge, as created by DIDAuthVerifier.create_challenge
+    key: DIDKey
+        The signing key. It must have the authentication purpose and a private key.
+    did: str
+        The DID to which the key belongs
+
+    Returns
+    -------
+    dict
+        The response, with the keys 'did', 'nonce', 'domain', 'verificationMethod' and 'signature' (base64url)
+
+    Raises
+    ------
+    ValueError
+        If the key is not an authentication key with a private key
+    ";
+    _check_authentication_key(key);
+    let response = [
+        ("did", did),
+        ("nonce", challenge["nonce"]),
+        ("domain", challenge["domain"]),
+        ("verificationMethod", key.full_id(did)),
+    ]
+    .iter()
+    .cloned()
+    .collect::<HashMap<_, _>>();
+    response["signature"] = b64url_encode(key.sign(_signing_input(response).encode("utf-8"), None));
+    return response;
+}
+fn create_id_token<T0, T1, T2, T3, RT>(challenge: T0, key: T1, did: T2, expires_in: T3) -> RT {
+    "
+    Answers a challenge with a SIOPv2 self-issued ID token, using the DID as subject identifier.
+
+    Parameters
+    ----------
+    challenge: dict
+        The challenge, as created by DIDAuthVerifier.create_challenge. Its domain is used as 'aud'.
+    key: DIDKey
+        The signing key. It must have the authentication purpose and a private key.
+    did: str
+        The DID to which the key belongs. It is used as 'iss' and 'sub'.
+    expires_in: int, optional
+        Number of seconds during which the token is valid. Defaults to 300.
+
+    Returns
+    -------
+    str
+
+    Raises
+    ------
+    ValueError
+        If the key is not an authentication key with a private key
+    ";
+    _check_authentication_key(key);
+    let claims = [
+        ("iss", did),
+        ("sub", did),
+        ("aud", challenge["domain"]),
+        ("nonce", challenge["nonce"]),
+    ]
+    .iter()
+    .cloned()
+    .collect::<HashMap<_, _>>();
+    return sign_jwt(
+        claims,
+        key,
+        did,
+        if expires_in == None {
+            DEFAULT_ID_TOKEN_TTL
+        } else {
+            expires_in
+        },
+    );
+}
+fn _check_authentication_key<T0>(key: T0) {
+    if !isinstance(key, DIDKey) || key.purpose.iter().all(|&x| x != DIDKeyPurpose::AuthenticationKey) {
+        raise!(ValueError("DID Auth responses must be signed with a DID key with the authentication purpose")); //unsupported
+    }
+    if key.signing_key == None {
+        raise!(ValueError("The key has no private key and cannot be used for signing")); //unsupported
+    }
+}
+fn _signing_input<T0, RT>(response: T0) -> RT {
+    return dumps_jcs(
+        ["did", "nonce", "domain", "verificationMethod"]
+            .iter()
+            .map(|f| (f, response[f]))
+            .collect::<HashMap<_, _>>(),
+    );
+}
+fn _resolve_authentication_key<T0, T1, T2, RT>(resolver: T0, did: T1, verification_method: T2) -> RT {
+    if !verification_method.startswith("{}#".format(did)) {
+        raise!(DIDAuthError("The verification method must be a key of the DID")); //unsupported
+    }
+    let try_dummy = {
+        //unsupported
+        let (_, did_keys, _, _) = resolver.resolve(did);
+    };
+    let except!((ValueError, InvalidDIDChain, FactomAPIError)) = {
+        //unsupported
+        raise!(DIDAuthError("DID {} cannot be resolved".format(did))); //unsupported
+    };
+    let key = did_keys.get(verification_method.split("#", 1)[1]);
+    if key == None || key.purpose.iter().all(|&x| x != DIDKeyPurpose::AuthenticationKey) {
+        raise!(DIDAuthError(
+            "{} is not an active authentication key".format(verification_method)
+        )); //unsupported
+    }
+    return key;
+}
@@ -1,11 +1,11 @@
 use std::*;
 use std::collections::HashMap;
 
-use base64::{urlsafe_b64decode};
+use base64::{urlsafe_b64decode, urlsafe_b64encode};
 use Crypto::Cipher::{AES};
 use Crypto::Hash::{SHA256, HMAC};
 use Crypto::Protocol::KDF::{PBKDF2};
-let all = vec!["encrypt_keys", "decrypt_keys_from_str", "decrypt_keys_from_json_str", "decrypt_keys_from_json_file"];
+let all = vec!["encrypt_keys", "encrypt_keys_as_json", "decrypt_keys_from_str", "decrypt_keys_from_json_str", "decrypt_keys_from_json_file"];
 fn encrypt_keys<T0, T1, T2, RT>(management_keys: T0, did_keys: T1, password: T2) -> RT {
 "
     Encrypts keys with a password.
@@ -27,6 +27,31 @@ fn encrypt_keys<T0, T1, T2, RT>(management_keys: T0, did_keys: T1, password: T2)
 let management_keys_dict = DictComp /*unimplemented()*/;
 let did_keys_dict = DictComp /*unimplemented()*/;
 let keys_data = [("managementKeys", management_keys_dict), ("didKeys", did_keys_dict)].iter().cloned().collect::<HashMap<_,_>>();
+return _encrypt(keys_data, password);
+}
+fn encrypt_keys_as_json<T0, T1, T2, RT>(keys_data: T0, did: T1, password: T2) -> RT {
+"
+    Encrypts arbitrary key data with a password and exports it in the same JSON format as the one produced by
+    DID.export_encrypted_keys_as_json().
+
+    Parameters
+    ----------
+    keys_data: dict
+        The key data to be encrypted. Must be JSON serializable.
+    did: str
+        The DID with which the keys are associated.
+    password: str
+        A password to use for the encryption of the keys.
+
+    Returns
+    -------
+    str
+        Encrypted keys JSON.
+    ";
+let encryption_result = _encrypt(keys_data, password);
+return json.dumps([("data", String::from(urlsafe_b64encode(encryption_result["data"]), "utf8")), ("encryptionAlgo", [("name", "AES-GCM"), ("iv", String::from(urlsafe_b64encode(encryption_result["iv"]), "utf8")), ("salt", String::from(urlsafe_b64encode(encryption_result["salt"]), "utf8")), ("tagLength", 128)].iter().cloned().collect::<HashMap<_,_>>()), ("did", did)].iter().cloned().collect::<HashMap<_,_>>());
+}
+fn _encrypt<T0, T1, RT>(keys_data: T0, password: T1) -> RT {
 let data = bytes(json.dumps(keys_data), "utf8");
 let salt = os.urandom(32);
 let iv = os.urandom(16);
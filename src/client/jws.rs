@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+use std::*;
+
+use factom::exceptions::FactomAPIError;
+use factom_did::client::enums::{DIDKeyPurpose, KeyType};
+use factom_did::client::keys::formats::{b64url_decode, b64url_encode};
+use factom_did::resolver::exceptions::InvalidDIDChain;
+let all = vec![
+    "InvalidJWS",
+    "sign_jws",
+    "decode_jws",
+    "verify_jws",
+    "verify_jws_signature",
+    "sign_jwt",
+    "verify_jwt",
+];
+// The JWS algorithm used for each key type (RFC 8037, RFC 8812 and RFC 7518)
+const JWS_ALGORITHMS: _ = [
+    (KeyType::EdDSA, "EdDSA"),
+    (KeyType::ECDSA, "ES256K"),
+    (KeyType::RSA, "RS256"),
+]
+.iter()
+.cloned()
+.collect::<HashMap<_, _>>();
+// Allowed clock skew, in seconds, when checking the 'exp' and 'nbf' claims of a JWT
+const DEFAULT_LEEWAY: _ = 60;
+struct InvalidJWS {}
+
+impl InvalidJWS {
+    /*pass*/
+}
+struct _Unhashed {
+    message: ST0,
+}
+
+impl _Unhashed {
+    "
+    Stands in for a hash object, so that EdDSA signatures are computed over the JWS signing input itself, as required
+    by RFC 8037, instead of over its SHA-256 digest.
+    ";
+    fn init<T0>(&self, message: T0) {
+        self.message = message;
+    }
+    fn digest<RT>(&self) -> RT {
+        return self.message;
+    }
+}
+fn sign_jws<T0, T1, T2, T3, RT>(payload: T0, key: T1, did: T2, headers: T3) -> RT {
+    "
+    Signs a payload with a DID key, producing a JWS in compact serialization.
+
+    The 'alg' header is determined by the key type ('EdDSA', 'ES256K' or 'RS256') and the 'kid' header is the full
+    DID URL of the key, so that verifiers can resolve it.
+
+    Parameters
+    ----------
+    payload: bytes
+    key: AbstractDIDKey
+        The signing key. It must have a private key.
+    did: str
+        The DID to which the key belongs
+    headers: dict, optional
+        Additional protected headers. 'alg' and 'kid' cannot be overridden.
+
+    Returns
+    -------
+    str
+
+    Raises
+    ------
+    ValueError
+        If the key has no private key
+    ";
+    if key.signing_key == None {
+        raise!(ValueError("The key has no private key and cannot be used for signing")); //unsupported
+    }
+    let header = if headers == None { dict() } else { dict(headers) };
+    header["alg"] = JWS_ALGORITHMS[key.key_type];
+    header["kid"] = key.full_id(did);
+    let signing_input = "{}.{}".format(
+        b64url_encode(json.dumps(header, (",", ":")).encode("utf-8")),
+        b64url_encode(payload),
+    );
+    let signature = key.sign(signing_input.encode("ascii"), _hash_function(key.key_type));
+    return "{}.{}".format(signing_input, b64url_encode(signature));
+}
+fn verify_jws<T0, T1, T2, RT>(token: T0, resolver: T1, purpose: T2) -> RT {
+    "
+    Verifies a compact JWS signed with a DID key.
+
+    The DID in the 'kid' header is resolved, and the token is only accepted if the key is an active DID key with the
+    required purpose, of the type matching the 'alg' header, and the signature is valid. Management keys are not
+    accepted, as they are only meant to sign DID updates.
+
+    Parameters
+    ----------
+    token: str
+    resolver: DIDResolver or CachingResolver
+        The resolver used to resolve the DID in the 'kid' header
+    purpose: DIDKeyPurpose, optional
+        The purpose which the key must have. Defaults to DIDKeyPurpose.AuthenticationKey.
+
+    Returns
+    -------
+    tuple
+        A 3-tuple containing the protected header (dict), the payload (bytes) and the DIDKey which signed the token
+
+    Raises
+    ------
+    InvalidJWS
+        If the token is malformed, the key cannot be found or is not suitable, or the signature is not valid
+    ";
+    let purpose = if purpose == None {
+        DIDKeyPurpose::AuthenticationKey
+    } else {
+        purpose
+    };
+    let (header, _) = decode_jws(token);
+    let (did, alias) = header["kid"].split("#", 1);
+    let try_dummy = {
+        //unsupported
+        let (_, did_keys, _, _) = resolver.resolve(did);
+    };
+    let except!((ValueError, InvalidDIDChain, FactomAPIError)) = {
+        //unsupported
+        raise!(InvalidJWS("DID {} cannot be resolved".format(did))); //unsupported
+    };
+    let key = did_keys.get(alias);
+    if key == None {
+        raise!(InvalidJWS("{} is not an active DID key".format(header["kid"]))); //unsupported
+    }
+    if key.purpose.iter().all(|&x| x != purpose) {
+        raise!(InvalidJWS("{} does not have the purpose {}".format(header["kid"], purpose.value))); //unsupported
+    }
+    let (header, payload) = verify_jws_signature(token, key);
+    return (header, payload, key);
+}
+fn decode_jws<T0, RT>(token: T0) -> RT {
+    "
+    Decodes a compact JWS signed with a DID key, without verifying its signature.
+
+    Parameters
+    ----------
+    token: str
+
+    Returns
+    -------
+    tuple
+        A 2-tuple containing the protected header (dict) and the payload (bytes)
+
+    Raises
+    ------
+    InvalidJWS
+        If the token is malformed, or its 'kid' header is not the full DID URL of a key
+    ";
+    let try_dummy = {
+        //unsupported
+        let (encoded_header, encoded_payload, _) = token.split(".");
+        let header = json.loads(b64url_decode(encoded_header));
+        let payload = b64url_decode(encoded_payload);
+    };
+    let except!((ValueError, binascii.Error)) = {
+        //unsupported
+        raise!(InvalidJWS("Malformed JWS")); //unsupported
+    };
+    if !isinstance(header, dict) || !isinstance(header.get("kid"), str) || !header["kid"].contains("#") {
+        raise!(InvalidJWS("The JWS header must contain the full DID URL of the key as 'kid'")); //unsupported
+    }
+    return (header, payload);
+}
+fn verify_jws_signature<T0, T1, RT>(token: T0, key: T1) -> RT {
+    "
+    Verifies the signature of a compact JWS with the given key, without resolving the 'kid' header. Callers are
+    responsible for checking that the key is the one identified by 'kid' and that it is suitable.
+
+    Parameters
+    ----------
+    token: str
+    key: AbstractDIDKey
+
+    Returns
+    -------
+    tuple
+        A 2-tuple containing the protected header (dict) and the payload (bytes)
+
+    Raises
+    ------
+    InvalidJWS
+        If the token is malformed, the 'alg' header does not match the key type, or the signature is not valid
+    ";
+    let (header, payload) = decode_jws(token);
+    if header.get("alg") != JWS_ALGORITHMS[key.key_type] {
+        raise!(InvalidJWS("Algorithm {} does not match the key type".format(header.get("alg")))); //unsupported
+    }
+    let try_dummy = {
+        //unsupported
+        let (encoded_header, encoded_payload, encoded_signature) = token.split(".");
+        let valid = key.verify(
+            "{}.{}".format(encoded_header, encoded_payload).encode("ascii"),
+            b64url_decode(encoded_signature),
+            _hash_function(key.key_type),
+        );
+    };
+    let except!((AssertionError, ValueError, binascii.Error)) = {
+        //unsupported
+        let valid = false;
+    };
+    if !valid {
+        raise!(InvalidJWS("Invalid signature")); //unsupported
+    }
+    return (header, payload);
+}
+fn sign_jwt<T0, T1, T2, T3, RT>(claims: T0, key: T1, did: T2, expires_in: T3) -> RT {
+    "
+    Signs a JWT with a DID key.
+
+    Parameters
+    ----------
+    claims: dict
+        The claims. 'iss' defaults to the DID.
+    key: AbstractDIDKey
+    did: str
+    expires_in: int, optional
+        If provided, the 'iat' claim is set to the current time and the 'exp' claim to expires_in seconds later
+
+    Returns
+    -------
+    str
+    ";
+    let claims = dict(claims);
+    claims.setdefault("iss", did);
+    if expires_in != None {
+        let now = int(time.time());
+        claims["iat"] = now;
+        claims["exp"] = now + expires_in;
+    }
+    return sign_jws(
+        json.dumps(claims, (",", ":")).encode("utf-8"),
+        key,
+        did,
+        [("typ", "JWT")].iter().cloned().collect::<HashMap<_, _>>(),
+    );
+}
+fn verify_jwt<T0, T1, T2, T3, RT>(token: T0, resolver: T1, purpose: T2, leeway: T3) -> RT {
+    "
+    Verifies a JWT signed with a DID key, as verify_jws does, and checks its claims.
+
+    Parameters
+    ----------
+    token: str
+    resolver: DIDResolver or CachingResolver
+    purpose: DIDKeyPurpose, optional
+        The purpose which the key must have. Defaults to DIDKeyPurpose.AuthenticationKey.
+    leeway: int, optional
+        The allowed clock skew in seconds when checking the 'exp' and 'nbf' claims. Defaults to 60.
+
+    Returns
+    -------
+    dict
+        The claims
+
+    Raises
+    ------
+    InvalidJWS
+        If the token is not valid, has expired or is not valid yet, or its issuer is not the DID of the signing key
+    ";
+    let leeway = if leeway == None { DEFAULT_LEEWAY } else { leeway };
+    let (header, payload, _) = verify_jws(token, resolver, purpose);
+    let try_dummy = {
+        //unsupported
+        let claims = json.loads(payload);
+    };
+    let except!(ValueError) = {
+        //unsupported
+        raise!(InvalidJWS("The JWT payload must be a JSON object")); //unsupported
+    };
+    if !isinstance(claims, dict) {
+        raise!(InvalidJWS("The JWT payload must be a JSON object")); //unsupported
+    }
+    let now = time.time();
+    if claims.iter().any(|&x| x == "exp") && now > claims["exp"] + leeway {
+        raise!(InvalidJWS("The JWT has expired")); //unsupported
+    }
+    if claims.iter().any(|&x| x == "nbf") && now < claims["nbf"] - leeway {
+        raise!(InvalidJWS("The JWT is not valid yet")); //unsupported
+    }
+    if claims.iter().any(|&x| x == "iss") && claims["iss"] != header["kid"].split("#")[0] {
+        raise!(InvalidJWS("The JWT issuer does not match the DID of the signing key")); //unsupported
+    }
+    return claims;
+}
+fn _hash_function<T0, RT>(key_type: T0) -> RT {
+    "
+    Returns the hash function to pass to AbstractDIDKey.sign and verify: EdDSA signs the signing input itself, while
+    ES256K and RS256 use the default SHA-256.
+    ";
+    return if key_type == KeyType::EdDSA { _Unhashed } else { None };
+}
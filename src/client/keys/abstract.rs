@@ -5,10 +5,12 @@ use factom_did::client::constants::ENTRY_SCHEMA_V100;
 use factom_did::client::enums::KeyType;
 use factom_did::client::keys::ecdsa::ECDSASecp256k1Key;
 use factom_did::client::keys::eddsa::Ed25519Key;
+use factom_did::client::keys::formats::{jwk_thumbprint, public_key_to_jwk, public_key_to_multibase};
 use factom_did::client::keys::rsa::RSAKey;
 use factom_did::client::validators::{
     validate_alias, validate_did, validate_key_type, validate_priority_requirement,
 };
+use factom_did::resolver::method_versions::DID_METHOD_SPEC_V020_RULES;
 struct AbstractDIDKey {
     alias: ST0,
     key_type: ST1,
@@ -183,12 +185,65 @@ impl AbstractDIDKey {
             )); //unsupported
         }
     }
-    fn rotate(&self) {
+    fn rotate<T0>(&self, private_key: T0) {
         "
         Generates new key pair for the key.
+
+        Parameters
+        ----------
+        private_key: bytes or str, optional
+            The private key of the new key pair. If not provided, a new key pair is generated at random.
         ";
         assert!(self.signing_key != None);
-        self.underlying = self.underlying.class();
+        self.underlying = self.underlying.class(None, private_key);
+    }
+    fn to_jwk<RT>(&self) -> RT {
+        "
+        Returns
+        -------
+        dict
+            The public key as a JSON Web Key (RFC 7517, RFC 8037)
+        ";
+        return public_key_to_jwk(self.key_type, self.public_key);
+    }
+    fn to_multibase<RT>(&self) -> RT {
+        "
+        Returns
+        -------
+        str
+            The public key as a multicodec-prefixed, base58btc multibase value
+        ";
+        return public_key_to_multibase(self.key_type, self.public_key);
+    }
+    fn jwk_thumbprint<RT>(&self) -> RT {
+        "
+        Returns
+        -------
+        str
+            The JWK thumbprint of the public key (RFC 7638)
+        ";
+        return jwk_thumbprint(self.to_jwk());
+    }
+    fn get_public_key_repr<T0, RT>(&self, public_key_format: T0) -> RT {
+        "
+        Parameters
+        ----------
+        public_key_format: PublicKeyFormat, optional
+            The representation of the public key. Defaults to the one used on-chain.
+
+        Returns
+        -------
+        tuple
+            A 2-tuple containing the name of the verification method property holding the public key
+            ('publicKeyBase58', 'publicKeyPem', 'publicKeyJwk' or 'publicKeyMultibase') and its value
+        ";
+        if public_key_format == "jwk" {
+            return ("publicKeyJwk", self.to_jwk());
+        }
+        if public_key_format == "multibase" {
+            return ("publicKeyMultibase", self.to_multibase());
+        }
+        return self.underlying.get_public_key_on_chain_repr();
     }
     fn full_id<T0, RT>(&self, did: T0) -> RT {
         "
@@ -203,7 +258,7 @@ impl AbstractDIDKey {
         str
             The full id for the key, constituting of the DID_METHOD_NAME, the network, the chain ID and the key alias.
         ";
-        return "{}#{}".format(did, self.alias);
+        return DID_METHOD_SPEC_V020_RULES.format_id(did, self.alias);
     }
     fn validate_key_input_params<T0, T1, T2, T3>(
         alias: T0,
@@ -4,6 +4,7 @@ use std::collections::HashMap;
 use factom_did::client::constants::{ENTRY_SCHEMA_V100};
 use factom_did::client::enums::{DIDKeyPurpose, KeyType};
 use factom_did::client::keys::abstract::{AbstractDIDKey};
+use factom_did::client::keys::formats::{key_material_from_jwk, key_material_from_openssh, key_material_from_pem, key_material_from_raw};
 struct DIDKey {
 purpose: ST0,
 }
@@ -67,5 +68,51 @@ return DIDKey(entry_dict["id"].split("#")[-1], entry_dict.get("purpose").iter().
 } else {
 raise!(NotImplementedError("Unknown schema version: {}".format(version))); //unsupported
 }
+}
+fn from_pem<T0, T1, T2, T3, T4, T5, RT>(alias: T0, purpose: T1, data: T2, controller: T3, priority_requirement: T4, password: T5) -> RT {
+"
+        Creates a DIDKey from a PEM encoded Ed25519, secp256k1 or RSA key. If the PEM contains a public key, the
+        resulting key can only be used to verify signatures.
+
+        Parameters
+        ----------
+        alias: str
+        purpose: DIDKeyPurpose or DIDKeyPurpose[]
+        data: bytes or str
+            The PEM encoded private or public key
+        controller: str
+        priority_requirement: int, optional
+        password: bytes, optional
+            The password of an encrypted private key
+
+        Raises
+        ------
+        ValueError
+            If the key cannot be parsed, or its type, curve or size is not supported
+        ";
+let (key_type, public_key, private_key) = key_material_from_pem(data, password);
+return DIDKey(alias, purpose, key_type, controller, priority_requirement, public_key, private_key);
+}
+fn from_jwk<T0, T1, T2, T3, T4, RT>(alias: T0, purpose: T1, jwk: T2, controller: T3, priority_requirement: T4) -> RT {
+"
+        Creates a DIDKey from a public or private JSON Web Key (OKP/Ed25519, EC/secp256k1 or RSA).
+        ";
+let (key_type, public_key, private_key) = key_material_from_jwk(jwk);
+return DIDKey(alias, purpose, key_type, controller, priority_requirement, public_key, private_key);
+}
+fn from_openssh<T0, T1, T2, T3, T4, T5, RT>(alias: T0, purpose: T1, data: T2, controller: T3, priority_requirement: T4, password: T5) -> RT {
+"
+        Creates a DIDKey from an OpenSSH public key line or private key file (ssh-ed25519 or ssh-rsa).
+        ";
+let (key_type, public_key, private_key) = key_material_from_openssh(data, password);
+return DIDKey(alias, purpose, key_type, controller, priority_requirement, public_key, private_key);
+}
+fn from_raw<T0, T1, T2, T3, T4, T5, T6, RT>(alias: T0, purpose: T1, key_type: T2, public_key: T3, private_key: T4, controller: T5, priority_requirement: T6) -> RT {
+"
+        Creates a DIDKey from raw key bytes. Either key may be omitted: the public key is derived from the private
+        key, and a public-only key can only be used to verify signatures.
+        ";
+let (key_type, public_key, private_key) = key_material_from_raw(key_type, public_key, private_key);
+return DIDKey(alias, purpose, key_type, controller, priority_requirement, public_key, private_key);
 } 
 }
\ No newline at end of file
@@ -7,7 +7,6 @@ use Crypto::Signature::{pkcs1_15};
 struct RSAKey {
 signing_key: ST0,
 verifying_key: ST1,
-verifying_key_key: ST2,
 }
 
 impl RSAKey {
@@ -136,7 +135,7 @@ return;
 }
 if public_key != None {
 self.signing_key = None;
-self.verifying_key_key = RSA::import_key(public_key);
+self.verifying_key = RSA::import_key(public_key);
 } else {
 self.signing_key = RSA::import_key(private_key);
 self.verifying_key = self.signing_key.publickey();
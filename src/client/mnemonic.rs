@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::*;
+
+use factom_did::client::enums::KeyType;
+use mnemonic::Mnemonic;
+use Crypto::Hash::{HMAC, SHA512};
+use Crypto::PublicKey::RSA;
+let all = vec![
+    "generate_mnemonic",
+    "validate_mnemonic",
+    "mnemonic_to_seed",
+    "derive_private_key",
+];
+const MNEMONIC_LANGUAGE: _ = "english";
+const MNEMONIC_STRENGTH: _ = 256;
+const MNEMONIC_WORD_COUNT: _ = 24;
+const KEY_DERIVATION_DOMAIN: _ = "factom-did";
+const KEY_DERIVATION_GAP_LIMIT: _ = 20;
+const SECP256K1_ORDER: _ = 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141;
+fn generate_mnemonic<RT>() -> RT {
+    "
+    Generates a new BIP-39 recovery phrase.
+
+    Returns
+    -------
+    str
+        A 24-word recovery phrase, encoding 256 bits of entropy
+    ";
+    return Mnemonic(MNEMONIC_LANGUAGE).generate(MNEMONIC_STRENGTH);
+}
+fn validate_mnemonic<T0>(words: T0) {
+    "
+    Validates a BIP-39 recovery phrase.
+
+    Parameters
+    ----------
+    words: str
+        The recovery phrase
+
+    Raises
+    ------
+    ValueError
+        If the phrase does not contain 24 words or if its checksum is invalid
+    ";
+    if words.split().len() != MNEMONIC_WORD_COUNT {
+        raise!(ValueError(
+            "Recovery phrase must contain {} words.".format(MNEMONIC_WORD_COUNT)
+        )); //unsupported
+    }
+    if !Mnemonic(MNEMONIC_LANGUAGE).check(words) {
+        raise!(ValueError("Invalid recovery phrase.")); //unsupported
+    }
+}
+fn mnemonic_to_seed<T0, T1, RT>(words: T0, passphrase: T1) -> RT {
+    "
+    Converts a BIP-39 recovery phrase to the 64-byte seed used for key derivation.
+
+    Parameters
+    ----------
+    words: str
+        The recovery phrase
+    passphrase: str, optional
+        An optional passphrase protecting the recovery phrase
+
+    Returns
+    -------
+    bytes
+        The seed
+
+    Raises
+    ------
+    ValueError
+        If the recovery phrase is invalid
+    ";
+    validate_mnemonic(words);
+    return Mnemonic::to_seed(words, if passphrase != None { passphrase } else { "" });
+}
+fn derive_private_key<T0, T1, T2, T3, RT>(seed: T0, key_type: T1, alias: T2, index: T3) -> RT {
+    "
+    Deterministically derives the private key for a management or DID key from a seed.
+
+    The key material is HMAC-SHA512(seed, 'factom-did/<key type>/<alias>/<index>'). The index is incremented each time
+    the key with the given alias is rotated.
+
+    Parameters
+    ----------
+    seed: bytes
+        The seed, as returned by mnemonic_to_seed
+    key_type: KeyType
+        The type of the key
+    alias: str
+        The alias of the key
+    index: int
+        The derivation index of the key
+
+    Returns
+    -------
+    bytes
+        The private key, in the format expected by the constructor of the underlying key class
+
+    Raises
+    ------
+    NotImplementedError
+        If the key type is not supported
+    ";
+    let path = "{}/{}/{}/{}".format(KEY_DERIVATION_DOMAIN, key_type.value, alias, index);
+    let key_material = HMAC::new(seed, path.encode("utf-8"), SHA512).digest();
+    if key_type == KeyType::EdDSA {
+        return key_material[..32];
+    } else {
+        if key_type == KeyType::ECDSA {
+            let secret_exponent =
+                (int.from_bytes(key_material[..32], "big") % (SECP256K1_ORDER - 1)) + 1;
+            return secret_exponent.to_bytes(32, "big");
+        } else {
+            if key_type == KeyType::RSA {
+                return RSA::generate(2048, _deterministic_randfunc(key_material))
+                    .export_key("PEM", None, 8);
+            } else {
+                raise!(NotImplementedError(
+                    "Unsupported signature type: {}".format(key_type.value)
+                )); //unsupported
+            }
+        }
+    }
+}
+fn _deterministic_randfunc<T0, RT>(key_material: T0) -> RT {
+    "
+    Returns a randfunc compatible with RSA.generate, which yields a deterministic byte stream seeded with the given
+    key material (HMAC-SHA512 in counter mode).
+    ";
+    return _DeterministicStream(key_material).read;
+}
+struct _DeterministicStream {
+    key_material: ST0,
+    counter: ST1,
+    buffer: ST2,
+}
+
+impl _DeterministicStream {
+    fn init<T0>(&self, key_material: T0) {
+        self.key_material = key_material;
+        self.counter = 0;
+        self.buffer = bytearray();
+    }
+    fn read<T0, RT>(&self, n: T0) -> RT {
+        while self.buffer.len() < n {
+            self.buffer
+                .extend(HMAC::new(self.key_material, self.counter.to_bytes(8, "big"), SHA512).digest());
+            self.counter += 1;
+        }
+        let result = bytes(self.buffer[..n]);
+        self.buffer[..n].drop();
+        return result;
+    }
+}
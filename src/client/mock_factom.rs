@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+use std::*;
+
+use factom::exceptions::FactomAPIError;
+use factom_did::client::blockchain::{calculate_chain_id, calculate_entry_hash};
+let all = vec!["MockFactom"];
+const CHAIN_CREATION_COST: _ = 10;
+const ENTRY_COST_UNIT: _ = 1024;
+const BLOCK_DURATION_MINUTES: _ = 10;
+struct MockFactom {
+    chains: HashMap<_, _>,
+    pending_entries: ST0,
+    balances: HashMap<_, _>,
+    block_height: ST1,
+    block_timestamp: ST2,
+    minute: ST3,
+    auto_seal: ST4,
+    injected_failures: HashMap<_, _>,
+}
+
+impl MockFactom {
+    "
+    An in-memory Factom network, which can be used in place of both the factomd and the walletd instances of the
+    Python factom-api package, e.g. to record and resolve DIDs in tests without a running factomd node.
+
+    Chain IDs and entry hashes are computed as by factomd. Entries are added to the current directory block and become
+    readable once the block is sealed. Each directory block spans 10 minutes and the timestamp of an entry is the
+    start of the minute in which it was submitted.
+
+    Attributes
+    ----------
+    genesis_timestamp: int, optional
+        The UNIX timestamp of the first directory block. Defaults to 1600000000.
+    auto_seal: bool, optional
+        If true (the default), the current directory block is sealed after each chain or entry submission
+    ";
+    fn init<T0, T1>(&self, genesis_timestamp: T0, auto_seal: T1) {
+        self.chains = HashMap::new();
+        self.pending_entries = vec![];
+        self.balances = HashMap::new();
+        self.block_height = 0;
+        self.block_timestamp = if genesis_timestamp == None {
+            1600000000
+        } else {
+            genesis_timestamp
+        };
+        self.minute = 0;
+        self.auto_seal = if auto_seal == None { true } else { auto_seal };
+        self.injected_failures = HashMap::new();
+    }
+    fn fund<T0, T1>(&self, ec_address: T0, amount: T1) {
+        "
+        Adds entry credits to an EC address.
+
+        Parameters
+        ----------
+        ec_address: str
+        amount: int
+        ";
+        self.balances[ec_address] = self.balances.get(ec_address, 0) + amount;
+    }
+    fn inject_failure<T0, T1, T2>(&self, method: T0, message: T1, count: T2) {
+        "
+        Makes the next calls to the given API method fail with a FactomAPIError.
+
+        Parameters
+        ----------
+        method: str
+            The name of the API method, e.g. 'new_entry' or 'read_chain'
+        message: str, optional
+            The error message
+        count: int, optional
+            The number of calls which fail. Defaults to 1.
+        ";
+        self.injected_failures[method] = (
+            if count == None { 1 } else { count },
+            if message == None {
+                "Injected failure"
+            } else {
+                message
+            },
+        );
+    }
+    fn advance_minute(&self) {
+        "
+        Advances the clock by one minute, sealing the current directory block when its last minute has passed.
+        ";
+        self.minute += 1;
+        if self.minute == BLOCK_DURATION_MINUTES {
+            self.seal_block();
+        }
+    }
+    fn seal_block(&self) {
+        "
+        Seals the current directory block, making its entries readable, and starts a new one.
+        ";
+        let entries_by_chain = HashMap::new();
+        for entry in self.pending_entries {
+            entries_by_chain.setdefault(entry["chainid"], vec![]).append(entry);
+        }
+        for (chain_id, entries) in entries_by_chain.items() {
+            let chain = self.chains[chain_id];
+            let data = bytearray(bytes.fromhex(chain_id));
+            data.extend(bytes.fromhex(chain["chainhead"]));
+            for entry in entries {
+                entry["dbheight"] = self.block_height;
+                data.extend(bytes.fromhex(entry["entryhash"]));
+            }
+            chain["chainhead"] = hashlib.sha256(data).hexdigest();
+            chain["entries"].extend(entries);
+        }
+        self.pending_entries = vec![];
+        self.block_height += 1;
+        self.block_timestamp += (BLOCK_DURATION_MINUTES * 60);
+        self.minute = 0;
+    }
+    fn new_chain<T0, T1, T2, T3, RT>(&self, _factomd: T0, ext_ids: T1, content: T2, ec_address: T3) -> RT {
+        "
+        Creates a new chain with the given first entry, as walletd does.
+
+        Returns
+        -------
+        dict
+            A dictionary with the keys 'chainid' and 'entryhash'
+        ";
+        self._check_injected_failure("new_chain");
+        let (ext_ids, content) = _to_bytes(ext_ids, content);
+        let chain_id = calculate_chain_id(ext_ids);
+        if self.chains.iter().any(|&x| x == chain_id) {
+            raise!(FactomAPIError(_error_data(-32010, "Chain already exists"))); //unsupported
+        }
+        self._charge(ec_address, CHAIN_CREATION_COST + _entry_cost(ext_ids, content));
+        self.chains[chain_id] = [("chainhead", "00".repeat(32)), ("entries", vec![])]
+            .iter()
+            .cloned()
+            .collect::<HashMap<_, _>>();
+        let entry_hash = self._submit(chain_id, ext_ids, content);
+        return [("chainid", chain_id), ("entryhash", entry_hash)]
+            .iter()
+            .cloned()
+            .collect::<HashMap<_, _>>();
+    }
+    fn new_entry<T0, T1, T2, T3, T4, RT>(
+        &self,
+        _factomd: T0,
+        chain_id: T1,
+        ext_ids: T2,
+        content: T3,
+        ec_address: T4,
+    ) -> RT {
+        "
+        Adds an entry to an existing chain, as walletd does.
+
+        Returns
+        -------
+        dict
+            A dictionary with the keys 'chainid' and 'entryhash'
+        ";
+        self._check_injected_failure("new_entry");
+        if self.chains.iter().all(|&x| x != chain_id) {
+            raise!(FactomAPIError(_error_data(-32009, "Missing Chain Head"))); //unsupported
+        }
+        let (ext_ids, content) = _to_bytes(ext_ids, content);
+        self._charge(ec_address, _entry_cost(ext_ids, content));
+        let entry_hash = self._submit(chain_id, ext_ids, content);
+        return [("chainid", chain_id), ("entryhash", entry_hash)]
+            .iter()
+            .cloned()
+            .collect::<HashMap<_, _>>();
+    }
+    fn entry_credit_balance<T0, RT>(&self, ec_address: T0) -> RT {
+        self._check_injected_failure("entry_credit_balance");
+        return [("balance", self.balances.get(ec_address, 0))]
+            .iter()
+            .cloned()
+            .collect::<HashMap<_, _>>();
+    }
+    fn chain_head<T0, RT>(&self, chain_id: T0) -> RT {
+        "
+        Returns
+        -------
+        dict
+            A dictionary with the keys 'chainhead' (the keyMR of the latest sealed entry block of the chain) and
+            'chaininprocesslist' (true if the chain has entries which have not been sealed yet)
+        ";
+        self._check_injected_failure("chain_head");
+        let chain = self._get_chain(chain_id);
+        return [
+            ("chainhead", chain["chainhead"]),
+            (
+                "chaininprocesslist",
+                self.pending_entries.iter().any(|e| e["chainid"] == chain_id),
+            ),
+        ]
+        .iter()
+        .cloned()
+        .collect::<HashMap<_, _>>();
+    }
+    fn read_chain<T0, T1, T2, RT>(&self, chain_id: T0, from_height: T1, include_entry_context: T2) -> RT {
+        "
+        Returns
+        -------
+        list of dict
+            The sealed entries of the chain, from the given directory block height onward, in the order in which they
+            were recorded. Each entry has the keys 'chainid', 'entryhash', 'extids', 'content' and 'timestamp', and
+            'dbheight' if include_entry_context is true.
+        ";
+        self._check_injected_failure("read_chain");
+        let chain = self._get_chain(chain_id);
+        let mut entries = vec![];
+        for entry in chain["entries"] {
+            if entry["dbheight"] < from_height {
+                continue;
+            }
+            let e = dict(entry);
+            if !include_entry_context {
+                e.drop("dbheight");
+            }
+            entries.append(e);
+        }
+        return entries;
+    }
+    fn _submit<T0, T1, T2, RT>(&self, chain_id: T0, ext_ids: T1, content: T2) -> RT {
+        let entry_hash = calculate_entry_hash(chain_id, ext_ids, content);
+        self.pending_entries.append(
+            [
+                ("chainid", chain_id),
+                ("entryhash", entry_hash),
+                ("extids", ext_ids),
+                ("content", content),
+                ("timestamp", self.block_timestamp + (self.minute * 60)),
+            ]
+            .iter()
+            .cloned()
+            .collect::<HashMap<_, _>>(),
+        );
+        if self.auto_seal {
+            self.seal_block();
+        }
+        return entry_hash;
+    }
+    fn _get_chain<T0, RT>(&self, chain_id: T0) -> RT {
+        if self.chains.iter().all(|&x| x != chain_id) {
+            raise!(FactomAPIError(_error_data(-32009, "Missing Chain Head"))); //unsupported
+        }
+        return self.chains[chain_id];
+    }
+    fn _charge<T0, T1>(&self, ec_address: T0, cost: T1) {
+        if self.balances.get(ec_address, 0) < cost {
+            raise!(FactomAPIError(_error_data(-32603, "Insufficient entry credit balance"))); //unsupported
+        }
+        self.balances[ec_address] -= cost;
+    }
+    fn _check_injected_failure<T0>(&self, method: T0) {
+        if self.injected_failures.iter().all(|&x| x != method) {
+            return;
+        }
+        let (count, message) = self.injected_failures[method];
+        if count <= 1 {
+            self.injected_failures.drop(method);
+        } else {
+            self.injected_failures[method] = (count - 1, message);
+        }
+        raise!(FactomAPIError(_error_data(-32603, message))); //unsupported
+    }
+}
+fn _to_bytes<T0, T1, RT>(ext_ids: T0, content: T1) -> RT {
+    let to_bytes = |v| if type_(v) == bytes { v } else { bytes.fromhex(v) };
+    return (
+        ext_ids.iter().map(to_bytes).collect::<Vec<_>>(),
+        to_bytes(content),
+    );
+}
+fn _entry_cost<T0, T1, RT>(ext_ids: T0, content: T1) -> RT {
+    let payload_size = (ext_ids.iter().map(|x| 2 + x.len()).sum::<i32>() + content.len());
+    return (payload_size + ENTRY_COST_UNIT - 1) / ENTRY_COST_UNIT;
+}
+fn _error_data<T0, T1, RT>(code: T0, message: T1) -> RT {
+    return [("code", code), ("message", message)]
+        .iter()
+        .cloned()
+        .collect::<HashMap<_, _>>();
+}
@@ -0,0 +1,356 @@
+use std::collections::HashMap;
+use std::*;
+
+use factom_did::client::enums::{DIDKeyPurpose, KeyType};
+use factom_did::resolver::diff::diff;
+use factom_did::resolver::state::DIDState;
+let all = vec!["DIDUpdatePlan", "load_desired_state", "plan"];
+const DESIRED_SERVICE_FIELDS: _ = ("alias", "type", "serviceEndpoint", "priorityRequirement");
+struct DIDUpdatePlan {
+    updater: ST0,
+    operations: ST1,
+    replacements: ST2,
+    required_priority: ST3,
+    signing_key: ST4,
+}
+
+impl DIDUpdatePlan {
+    "
+    A reviewable plan for bringing a DID to a desired state, consisting of the minimal set of DIDUpdater operations.
+
+    Attributes
+    ----------
+    updater: DIDUpdater
+        The updater with all operations of the plan applied
+    operations: list of tuple
+        The planned operations, as (operation, alias, argument) tuples, where operation is the name of the DIDUpdater
+        method and argument is None, or the revoked DIDKeyPurpose for revoke_did_key_purpose
+    replacements: str[]
+        Aliases of keys and services which must be revoked and then added again with new properties. The resolver does
+        not allow an alias to be revoked and added in the same entry, so only the revocation is part of this plan and
+        the addition will be planned once this plan has been recorded on-chain.
+    required_priority: int or float
+        The maximum priority the signing management key must have
+    signing_key: ManagementKey
+        The management key which will sign the update
+    ";
+    fn init<T0, T1, T2>(&self, updater: T0, operations: T1, replacements: T2) {
+        self.updater = updater;
+        self.operations = operations;
+        self.replacements = replacements;
+        self.required_priority = updater.required_signing_priority();
+        self.signing_key = sorted(updater.orig_management_keys, op.attrgetter("priority"))[0];
+    }
+    fn is_empty<RT>(&self) -> RT {
+        return !self.operations;
+    }
+    fn is_signable<RT>(&self) -> RT {
+        "
+        Returns
+        -------
+        bool
+            True if the available signing key has sufficient priority for the planned update
+        ";
+        return self.signing_key.priority <= self.required_priority;
+    }
+    fn to_text<RT>(&self) -> RT {
+        "
+        Renders the plan as human-readable text for review.
+
+        Returns
+        -------
+        str
+        ";
+        if self.is_empty() {
+            return "No changes. The DID matches the desired state.";
+        }
+        let lines = vec![];
+        for (operation, alias, argument) in self.operations {
+            if argument != None {
+                lines.push("{} {} ({})".format(operation, alias, argument.value));
+            } else {
+                lines.push("{} {}".format(operation, alias));
+            }
+        }
+        for alias in self.replacements {
+            lines.push("{} will be added again in a follow-up update".format(alias));
+        }
+        if self.required_priority != math.inf {
+            lines.push(
+                "Requires a signing key with priority <= {}; {} has priority {}{}".format(
+                    self.required_priority,
+                    self.signing_key.alias,
+                    self.signing_key.priority,
+                    if self.is_signable() { "" } else { " (insufficient)" },
+                ),
+            );
+        }
+        return "\n".join(lines);
+    }
+    fn export_entry_data<RT>(&self) -> RT {
+        "
+        Constructs the signed DIDUpdate entry for the plan.
+
+        Returns
+        -------
+        dict
+            A dictionary with ExtIDs and content for the entry, or None if the plan is empty
+
+        Raises
+        ------
+        RuntimeError
+            If the signing key does not have sufficient priority
+        ";
+        return self.updater.export_entry_data();
+    }
+    fn apply<T0, T1, T2, T3>(&self, factomd: T0, walletd: T1, ec_address: T2, verbose: T3) {
+        "
+        Records the DIDUpdate entry for the plan on-chain.
+
+        Parameters
+        ----------
+        factomd: obj
+            Factomd instance, instantiated from the Python factom-api package.
+        walletd: obj
+            Factom walletd instance, instantiated from the Python factom-api package.
+        ec_address: str
+            EC address used to pay for the entry.
+        verbose: bool, optional
+            If true, display the contents of the entry that will be recorded on-chain.
+
+        Raises
+        ------
+        RuntimeError
+            If the plan is empty, or if the entry cannot be recorded
+        ";
+        if self.is_empty() {
+            raise!(RuntimeError("Nothing to apply: the DID matches the desired state.")); //unsupported
+        }
+        self.updater.record_on_chain(factomd, walletd, ec_address, verbose);
+    }
+}
+fn load_desired_state<T0, RT>(document: T0) -> RT {
+    "
+    Loads a desired state document. The document can be either YAML or JSON (which is a subset of YAML) and has the
+    following structure:
+
+    managementKey:
+      - alias: root
+        priority: 0
+        type: Ed25519VerificationKey
+        controller: did:factom:...  (optional)
+        priorityRequirement: 0  (optional)
+    didKey:
+      - alias: signing-key
+        purpose: [publicKey, authentication]
+        type: ECDSASecp256k1VerificationKey
+    service:
+      - alias: inbox
+        type: SocialInbox
+        serviceEndpoint: https://example.com/inbox
+        description: My public social inbox  (custom field)
+
+    Parameters
+    ----------
+    document: str
+
+    Returns
+    -------
+    dict
+
+    Raises
+    ------
+    ValueError
+        If the document is not valid, e.g. if a DID key has no purpose
+    ";
+    let try_dummy = {
+        //unsupported
+        let desired_state = yaml.safe_load(document);
+    };
+    let except!(yaml.YAMLError) = {
+        //unsupported
+        raise!(ValueError("Desired state must be valid YAML or JSON.")); //unsupported
+    };
+    _validate_desired_state(desired_state);
+    return desired_state;
+}
+fn plan<T0, T1, T2, RT>(did: T0, desired_state: T1, resolver: T2) -> RT {
+    "
+    Computes the minimal update which brings the DID from its current on-chain state to the desired state.
+
+    Keys and services are matched by alias. A DID key whose desired purposes are a subset of its current purposes has
+    the extra purposes revoked; any other difference in the properties of a key or service results in a replacement.
+    Public keys are not part of the desired state: new keys are generated by the DID.
+
+    Parameters
+    ----------
+    did: client.did.DID
+        The DID, with the private keys of its management keys. Its keys and services must match the current on-chain
+        state of the DID.
+    desired_state: dict
+        The desired state, as returned by load_desired_state
+    resolver: DIDResolver
+        The resolver used to obtain the current on-chain state of the DID
+
+    Returns
+    -------
+    DIDUpdatePlan
+
+    Raises
+    ------
+    ValueError
+        If the desired state is not valid
+    RuntimeError
+        If the local DID does not match the current on-chain state
+    ";
+    _validate_desired_state(desired_state);
+    let current = resolver.resolve_state(did.id, None);
+    let local_diff = diff(current, DIDState::from_did(did));
+    if !local_diff.is_empty() {
+        raise!(RuntimeError(
+            "The local DID does not match its on-chain state:\n{}".format(local_diff.to_text())
+        )); //unsupported
+    }
+    let updater = did.update();
+    let operations = vec![];
+    let replacements = vec![];
+    let desired_management_keys = desired_state
+        .get("managementKey", vec![])
+        .iter()
+        .map(|k| (k["alias"], k))
+        .collect::<HashMap<_, _>>();
+    for (alias, key) in current.management_keys.items() {
+        if desired_management_keys.iter().all(|&x| x != alias) {
+            updater.revoke_management_key(alias);
+            operations.push(("revoke_management_key", alias, None));
+        } else {
+            if !_key_matches(key, desired_management_keys[alias], did.id) {
+                updater.revoke_management_key(alias);
+                operations.push(("revoke_management_key", alias, None));
+                replacements.push(alias);
+            }
+        }
+    }
+    for (alias, key_data) in desired_management_keys.items() {
+        if current.management_keys.iter().all(|&x| x != alias) {
+            updater.add_management_key(
+                alias,
+                key_data["priority"],
+                KeyType::from_str(key_data.get("type", KeyType::EdDSA.value)),
+                key_data.get("controller"),
+                key_data.get("priorityRequirement"),
+            );
+            operations.push(("add_management_key", alias, None));
+        }
+    }
+    let desired_did_keys = desired_state
+        .get("didKey", vec![])
+        .iter()
+        .map(|k| (k["alias"], k))
+        .collect::<HashMap<_, _>>();
+    for (alias, key) in current.did_keys.items() {
+        if desired_did_keys.iter().all(|&x| x != alias) {
+            updater.revoke_did_key(alias);
+            operations.push(("revoke_did_key", alias, None));
+            continue;
+        }
+        let key_data = desired_did_keys[alias];
+        let current_purposes = set(key.purpose.iter().map(|p| p.value));
+        let desired_purposes = set(key_data["purpose"]);
+        if !_key_matches(key, key_data, did.id) || !desired_purposes.issubset(current_purposes) {
+            updater.revoke_did_key(alias);
+            operations.push(("revoke_did_key", alias, None));
+            replacements.push(alias);
+        } else {
+            for purpose in current_purposes.difference(desired_purposes) {
+                updater.revoke_did_key_purpose(alias, DIDKeyPurpose::from_str(purpose));
+                operations.push(("revoke_did_key_purpose", alias, DIDKeyPurpose::from_str(purpose)));
+            }
+        }
+    }
+    for (alias, key_data) in desired_did_keys.items() {
+        if current.did_keys.iter().all(|&x| x != alias) {
+            updater.add_did_key(
+                alias,
+                key_data["purpose"].iter().map(DIDKeyPurpose::from_str).collect::<Vec<_>>(),
+                KeyType::from_str(key_data.get("type", KeyType::EdDSA.value)),
+                key_data.get("controller"),
+                key_data.get("priorityRequirement"),
+            );
+            operations.push(("add_did_key", alias, None));
+        }
+    }
+    let desired_services = desired_state
+        .get("service", vec![])
+        .iter()
+        .map(|s| (s["alias"], s))
+        .collect::<HashMap<_, _>>();
+    for (alias, service) in current.services.items() {
+        if desired_services.iter().all(|&x| x != alias) {
+            updater.revoke_service(alias);
+            operations.push(("revoke_service", alias, None));
+        } else {
+            if !_service_matches(service, desired_services[alias]) {
+                updater.revoke_service(alias);
+                operations.push(("revoke_service", alias, None));
+                replacements.push(alias);
+            }
+        }
+    }
+    for (alias, service_data) in desired_services.items() {
+        if current.services.iter().all(|&x| x != alias) {
+            updater.add_service(
+                alias,
+                service_data["type"],
+                service_data["serviceEndpoint"],
+                service_data.get("priorityRequirement"),
+                _get_custom_fields(service_data),
+            );
+            operations.push(("add_service", alias, None));
+        }
+    }
+    return DIDUpdatePlan(updater, operations, replacements);
+}
+fn _validate_desired_state<T0>(desired_state: T0) {
+    if !isinstance(desired_state, dict) || !desired_state.get("managementKey") {
+        raise!(ValueError("Desired state must contain at least one management key.")); //unsupported
+    }
+    for section in ("managementKey", "didKey", "service") {
+        let aliases = desired_state.get(section, vec![]).iter().map(|x| x.get("alias")).collect::<Vec<_>>();
+        if aliases.iter().any(|a| a == None) || set(aliases).len() != aliases.len() {
+            raise!(ValueError(
+                "Each entry in {} must have a unique alias.".format(section)
+            )); //unsupported
+        }
+    }
+    // An empty purpose would be a subset of every current purpose and would plan the revocation of all of them
+    let valid_purposes = (DIDKeyPurpose::PublicKey.value, DIDKeyPurpose::AuthenticationKey.value);
+    for key_data in desired_state.get("didKey", vec![]) {
+        let purpose = key_data.get("purpose");
+        if !isinstance(purpose, list) || !purpose || purpose.iter().any(|p| valid_purposes.iter().all(|&x| x != p)) {
+            raise!(ValueError(
+                "DID key {} must have a non-empty list of purposes among {}.".format(key_data["alias"], valid_purposes)
+            )); //unsupported
+        }
+    }
+}
+fn _key_matches<T0, T1, T2, RT>(key: T0, key_data: T1, did: T2) -> RT {
+    return key.key_type.value == key_data.get("type", KeyType::EdDSA.value)
+        && key.controller == key_data.get("controller", did)
+        && key.priority_requirement == key_data.get("priorityRequirement")
+        && (!hasattr(key, "priority") || key.priority == key_data.get("priority"));
+}
+fn _service_matches<T0, T1, RT>(service: T0, service_data: T1) -> RT {
+    return service.service_type == service_data.get("type")
+        && service.endpoint == service_data.get("serviceEndpoint")
+        && service.priority_requirement == service_data.get("priorityRequirement")
+        && service.custom_fields == _get_custom_fields(service_data);
+}
+fn _get_custom_fields<T0, RT>(service_data: T0) -> RT {
+    let custom_fields = service_data
+        .items()
+        .iter()
+        .filter(|(k, _)| DESIRED_SERVICE_FIELDS.iter().all(|&x| x != k))
+        .collect::<HashMap<_, _>>();
+    return if custom_fields { custom_fields } else { None };
+}
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::*;
+
+use collections::OrderedDict;
+let all = vec!["order_entry_content", "dumps_entry_content", "dumps_jcs"];
+// The order in which the Python factom-did library inserts the fields of entry content objects, at each level where
+// the entry schemas define them. Fields not listed for a level (e.g. custom service fields) follow the listed ones, in
+// the order in which they are given, and their values are not reordered.
+const ENTRY_CONTENT_FIELD_ORDER: _ = vec!["didMethodVersion", "revoke", "add", "managementKey", "didKey", "service"];
+const KEYS_AND_SERVICES_FIELD_ORDER: _ = vec!["managementKey", "didKey", "service"];
+const KEY_FIELD_ORDER: _ = vec![
+    "id",
+    "type",
+    "controller",
+    "publicKeyBase58",
+    "publicKeyPem",
+    "priorityRequirement",
+    "priority",
+    "purpose",
+];
+const SERVICE_FIELD_ORDER: _ = vec!["id", "type", "serviceEndpoint", "priorityRequirement"];
+fn order_entry_content<T0, RT>(content: T0) -> RT {
+    "
+    Orders the fields of entry content in the same way as the Python factom-did library.
+
+    Parameters
+    ----------
+    content: dict
+
+    Returns
+    -------
+    OrderedDict
+    ";
+    if !isinstance(content, dict) {
+        return content;
+    }
+    let ordered = _order_keys_and_services(_order_fields(content, ENTRY_CONTENT_FIELD_ORDER));
+    for action in vec!["revoke", "add"] {
+        if isinstance(ordered.get(action), dict) {
+            ordered[action] = _order_keys_and_services(_order_fields(ordered[action], KEYS_AND_SERVICES_FIELD_ORDER));
+        }
+    }
+    return ordered;
+}
+fn _order_keys_and_services<T0, RT>(content: T0) -> RT {
+    for (section, field_order) in vec![
+        ("managementKey", KEY_FIELD_ORDER),
+        ("didKey", KEY_FIELD_ORDER),
+        ("service", SERVICE_FIELD_ORDER),
+    ] {
+        if isinstance(content.get(section), list) {
+            content[section] = content[section]
+                .iter()
+                .map(|item| {
+                    if isinstance(item, dict) {
+                        _order_fields(item, field_order)
+                    } else {
+                        item
+                    }
+                })
+                .collect::<Vec<_>>();
+        }
+    }
+    return content;
+}
+fn _order_fields<T0, T1, RT>(object: T0, field_order: T1) -> RT {
+    let known = field_order
+        .iter()
+        .filter(|k| object.iter().any(|&x| x == k))
+        .collect::<Vec<_>>();
+    let unknown = object
+        .iter()
+        .filter(|k| field_order.iter().all(|&x| x != k))
+        .collect::<Vec<_>>();
+    return OrderedDict(
+        known
+            .iter()
+            .chain(unknown.iter())
+            .map(|k| (k, object[k]))
+            .collect::<Vec<_>>(),
+    );
+}
+fn dumps_entry_content<T0, T1, RT>(content: T0, jcs: T1) -> RT {
+    "
+    Serializes entry content deterministically.
+
+    By default, the output is byte-for-byte identical to the one of the Python factom-did library: fields are in the
+    order in which the library inserts them, there is no whitespace and non-ASCII characters are escaped.
+
+    Parameters
+    ----------
+    content: dict
+        The entry content
+    jcs: bool, optional
+        If true, the content is serialized according to the JSON Canonicalization Scheme (RFC 8785) instead
+
+    Returns
+    -------
+    str
+    ";
+    if jcs {
+        return dumps_jcs(content);
+    }
+    return json.dumps(order_entry_content(content), (",", ":"));
+}
+fn dumps_jcs<T0, RT>(value: T0) -> RT {
+    "
+    Serializes a JSON value according to the JSON Canonicalization Scheme (RFC 8785): object members are sorted by
+    the UTF-16 code units of their names, numbers are formatted as in ECMAScript and there is no whitespace.
+
+    Parameters
+    ----------
+    value: dict or list or str or int or float or bool or None
+
+    Returns
+    -------
+    str
+
+    Raises
+    ------
+    ValueError
+        If the value contains a number which is not finite, or a value which cannot be represented in JSON
+    ";
+    if value == None || isinstance(value, bool) {
+        return json.dumps(value);
+    }
+    if isinstance(value, int) {
+        return str(value);
+    }
+    if isinstance(value, float) {
+        return _jcs_number(value);
+    }
+    if isinstance(value, str) {
+        return json.dumps(value, false);
+    }
+    if isinstance(value, (list, tuple)) {
+        return "[{}]".format(",".join(value.iter().map(dumps_jcs)));
+    }
+    if isinstance(value, dict) {
+        let keys = sorted(value.keys(), |k| k.encode("utf-16-be"));
+        return "{{{}}}".format(",".join(
+            keys.iter()
+                .map(|k| "{}:{}".format(json.dumps(k, false), dumps_jcs(value[k]))),
+        ));
+    }
+    raise!(ValueError("Value cannot be serialized: {}".format(value))); //unsupported
+}
+fn _jcs_number<T0, RT>(value: T0) -> RT {
+    "
+    Serializes a float as specified by ECMAScript's Number.prototype.toString, which RFC 8785 requires. The shortest
+    digits which round-trip are taken from repr, and the decimal point is placed according to their exponent.
+    ";
+    if !math.isfinite(value) {
+        raise!(ValueError("Numbers must be finite")); //unsupported
+    }
+    if value == 0 {
+        return "0";
+    }
+    let sign = if value < 0 { "-" } else { "" };
+    let text = repr(value.abs());
+    let (mantissa, exponent) = if text.contains("e") {
+        text.split("e")
+    } else {
+        (text, "0")
+    };
+    let (integer_part, fraction_part) = if mantissa.contains(".") {
+        mantissa.split(".")
+    } else {
+        (mantissa, "")
+    };
+    let all_digits = integer_part + fraction_part;
+    let digits = all_digits.lstrip("0");
+    // The value is 0.<digits> * 10 ** n
+    let n = int(exponent) + integer_part.len() - (all_digits.len() - digits.len());
+    let digits = digits.rstrip("0");
+    let k = digits.len();
+    if k <= n && n <= 21 {
+        return sign + digits + "0".repeat(n - k);
+    }
+    if 0 < n && n <= 21 {
+        return sign + digits[..n] + "." + digits[n..];
+    }
+    if -6 < n && n <= 0 {
+        return sign + "0." + "0".repeat(-n) + digits;
+    }
+    let exponent = n - 1;
+    let exponent_text = "e{}{}".format(if exponent > 0 { "+" } else { "-" }, exponent.abs());
+    if k == 1 {
+        return sign + digits + exponent_text;
+    }
+    return sign + digits[..1] + "." + digits[1..] + exponent_text;
+}
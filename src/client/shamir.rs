@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+use std::*;
+
+use base64::{urlsafe_b64decode, urlsafe_b64encode};
+use factom_did::client::constants::ENTRY_SCHEMA_V100;
+use factom_did::client::encryptor::{decrypt_keys_from_json_str, encrypt_keys_as_json};
+use factom_did::client::enums::KeyType;
+use factom_did::client::keys::management::ManagementKey;
+let all = vec!["split_management_key", "combine_management_key_shares"];
+const GF256_REDUCING_POLYNOMIAL: _ = 0x11b;
+const MAX_SHARES: _ = 255;
+fn split_management_key<T0, T1, T2, T3, RT>(
+    management_key: T0,
+    did: T1,
+    threshold: T2,
+    passwords: T3,
+) -> RT {
+    "
+    Splits the private key of a management key into M-of-N Shamir shares, for social recovery of the key.
+
+    Each share is encrypted with its own password and exported in the same JSON format as the one produced by
+    DID.export_encrypted_keys_as_json(), so that it can be handed over to a different custodian.
+
+    Parameters
+    ----------
+    management_key: ManagementKey
+        The management key to split. Usually the management key with priority 0.
+    did: str
+        The DID with which the management key is associated
+    threshold: int
+        The number of shares (M) required to rebuild the key
+    passwords: str[]
+        The passwords used to encrypt the shares, one per share. The number of passwords determines the number of
+        shares (N).
+
+    Returns
+    -------
+    str[]
+        The encrypted shares, as JSON strings
+
+    Raises
+    ------
+    ValueError
+        If the key has no private key or if the threshold and number of shares are not valid
+    ";
+    if management_key.private_key == None {
+        raise!(ValueError("Cannot split a management key without a private key.")); //unsupported
+    }
+    let shares_count = passwords.len();
+    if threshold < 2 || threshold > shares_count || shares_count > MAX_SHARES {
+        raise!(ValueError(
+            "Threshold must be between 2 and the number of shares, which must not exceed {}.".format(MAX_SHARES)
+        )); //unsupported
+    }
+    let private_key = if type_(management_key.private_key) == bytes {
+        management_key.private_key
+    } else {
+        management_key.private_key.encode("utf-8")
+    };
+    let shares = _split_secret(private_key, threshold, shares_count);
+    let encrypted_shares = vec![];
+    for (i, (x, y)) in shares.iter().enumerate() {
+        let share_data = [
+            ("alias", management_key.alias),
+            ("priority", management_key.priority),
+            ("type", management_key.key_type.value),
+            ("controller", management_key.controller),
+            ("priorityRequirement", management_key.priority_requirement),
+            ("threshold", threshold),
+            ("index", x),
+            ("share", String::from(urlsafe_b64encode(y), "utf8")),
+            ("did", did),
+        ]
+        .iter()
+        .cloned()
+        .collect::<HashMap<_, _>>();
+        encrypted_shares.push(encrypt_keys_as_json(share_data, did, passwords[i]));
+    }
+    return encrypted_shares;
+}
+fn combine_management_key_shares<T0, T1, T2, T3, RT>(
+    encrypted_shares: T0,
+    passwords: T1,
+    did: T2,
+    resolver: T3,
+) -> RT {
+    "
+    Rebuilds a management key from M-of-N encrypted Shamir shares.
+
+    The rebuilt key is verified against the management key with the same alias, which is currently active for the DID:
+    its public key, priority, type, controller and priority requirement must all match.
+
+    Parameters
+    ----------
+    encrypted_shares: str[]
+        The encrypted shares, as returned by split_management_key
+    passwords: str[]
+        The passwords of the shares, in the same order as the shares
+    did: str
+        The DID with which the management key is associated
+    resolver: DIDResolver
+        The resolver used to obtain the currently active management keys of the DID
+
+    Returns
+    -------
+    ManagementKey
+        The rebuilt management key, including its private key
+
+    Raises
+    ------
+    ValueError
+        If no shares are given, if a share or password is invalid, if a share was issued for a different DID, if the
+        shares do not belong to the same key, if there are not enough shares or if the rebuilt key does not match the
+        key currently active on-chain
+    ";
+    if encrypted_shares.len() == 0 {
+        raise!(ValueError("At least one share is required to rebuild the key.")); //unsupported
+    }
+    if encrypted_shares.len() != passwords.len() {
+        raise!(ValueError("A password must be provided for each share.")); //unsupported
+    }
+    let shares_data = encrypted_shares
+        .iter()
+        .zip(passwords.iter())
+        .map(|(share, password)| _decrypt_share(share, password, did))
+        .collect::<Vec<_>>();
+    let first_share = shares_data[0];
+    for share_data in shares_data {
+        for field in ("alias", "priority", "type", "controller", "priorityRequirement", "threshold") {
+            if share_data.get(field) != first_share.get(field) {
+                raise!(ValueError("The shares do not belong to the same management key.")); //unsupported
+            }
+        }
+    }
+    let indices = set(shares_data.iter().map(|s| s["index"]));
+    if indices.len() != shares_data.len() {
+        raise!(ValueError("Duplicate share detected.")); //unsupported
+    }
+    if shares_data.len() < first_share["threshold"] {
+        raise!(ValueError(
+            "At least {} shares are required to rebuild the key.".format(first_share["threshold"])
+        )); //unsupported
+    }
+    let private_key = _combine_shares(
+        shares_data
+            .iter()
+            .map(|s| (s["index"], urlsafe_b64decode(s["share"])))
+            .collect::<Vec<_>>(),
+    );
+    let key_type = KeyType::from_str(first_share["type"]);
+    let (active_management_keys, _, _, _) = resolver.resolve(did);
+    let active_key = active_management_keys.get(first_share["alias"]);
+    if active_key == None {
+        raise!(ValueError(
+            "Management key '{}' is not active for {}.".format(first_share["alias"], did)
+        )); //unsupported
+    }
+    let management_key = ManagementKey(
+        first_share["alias"],
+        first_share["priority"],
+        key_type,
+        first_share["controller"],
+        first_share["priorityRequirement"],
+        None,
+        if key_type == KeyType::RSA { private_key.decode("utf-8") } else { private_key },
+    );
+    if management_key.to_entry_dict(did, ENTRY_SCHEMA_V100) != active_key.to_entry_dict(did, ENTRY_SCHEMA_V100) {
+        raise!(ValueError(
+            "The rebuilt key does not match the key '{}' currently active on-chain.".format(first_share["alias"])
+        )); //unsupported
+    }
+    return management_key;
+}
+fn _decrypt_share<T0, T1, T2, RT>(encrypted_share: T0, password: T1, did: T2) -> RT {
+    "
+    Decrypts a share and checks that it was issued for the given DID, both in the JSON envelope and in the encrypted
+    (and therefore authenticated) share data.
+    ";
+    let share_data = decrypt_keys_from_json_str(encrypted_share, password);
+    if json.loads(encrypted_share).get("did") != did || share_data.get("did") != did {
+        raise!(ValueError("The share was not issued for {}.".format(did))); //unsupported
+    }
+    return share_data;
+}
+fn _split_secret<T0, T1, T2, RT>(secret: T0, threshold: T1, shares_count: T2) -> RT {
+    "
+    Splits the secret byte-wise over GF(256), using a random polynomial of degree threshold - 1 for each byte.
+
+    Returns
+    -------
+    list of tuple
+        A list of (x, y) pairs, where x is the share index (1..shares_count) and y is the share, as bytes
+    ";
+    let shares = (1..(shares_count + 1)).map(|x| (x, bytearray())).collect::<Vec<_>>();
+    for secret_byte in secret {
+        let coefficients = vec![secret_byte] + list(os.urandom((threshold - 1)));
+        for (x, y) in shares {
+            y.push(_evaluate_polynomial(coefficients, x));
+        }
+    }
+    return shares.iter().map(|(x, y)| (x, bytes(y))).collect::<Vec<_>>();
+}
+fn _combine_shares<T0, RT>(shares: T0) -> RT {
+    "
+    Recovers the secret from the given (x, y) shares by Lagrange interpolation at x = 0 over GF(256).
+    ";
+    let secret_length = shares[0][1].len();
+    if shares.iter().any(|(_, y)| y.len() != secret_length) {
+        raise!(ValueError("The shares do not belong to the same management key.")); //unsupported
+    }
+    let secret = bytearray();
+    for i in (0..secret_length) {
+        let mut value = 0;
+        for (j, (x_j, y_j)) in shares.iter().enumerate() {
+            let mut basis = 1;
+            for (m, (x_m, _)) in shares.iter().enumerate() {
+                if m != j {
+                    basis = _gf256_mul(basis, _gf256_div(x_m, x_m ^ x_j));
+                }
+            }
+            value ^= _gf256_mul(y_j[i], basis);
+        }
+        secret.push(value);
+    }
+    return bytes(secret);
+}
+fn _evaluate_polynomial<T0, T1, RT>(coefficients: T0, x: T1) -> RT {
+    let mut result = 0;
+    for coefficient in coefficients.iter().rev() {
+        result = _gf256_mul(result, x) ^ coefficient;
+    }
+    return result;
+}
+fn _gf256_mul<T0, T1, RT>(a: T0, b: T1) -> RT {
+    let mut result = 0;
+    while b {
+        if b & 1 {
+            result ^= a;
+        }
+        a <<= 1;
+        if a & 0x100 {
+            a ^= GF256_REDUCING_POLYNOMIAL;
+        }
+        b >>= 1;
+    }
+    return result;
+}
+fn _gf256_div<T0, T1, RT>(a: T0, b: T1) -> RT {
+    if b == 0 {
+        raise!(ZeroDivisionError("Division by zero in GF(256)")); //unsupported
+    }
+    let mut inverse = 1;
+    for _ in (0..254) {
+        inverse = _gf256_mul(inverse, b);
+    }
+    return _gf256_mul(a, inverse);
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::*;
+
+use factom_did::client::blockchain::calculate_entry_hash;
+use factom_did::client::did::DID;
+use factom_did::resolver::diff::diff;
+use factom_did::resolver::parser::parse_did_chain_entries;
+use factom_did::resolver::state::DIDState;
+let all = vec!["SimulationResult", "simulate_entry"];
+struct SimulationResult {
+    accepted: ST0,
+    entry_hash: ST1,
+    before: ST2,
+    after: ST3,
+}
+
+impl SimulationResult {
+    "
+    The outcome of feeding a prospective entry through the resolver, without recording it on-chain.
+
+    Attributes
+    ----------
+    accepted: bool
+        True if the resolver would apply the entry, False if it would skip it
+    entry_hash: str
+        The hash the entry would have once recorded
+    before: DIDState
+        The state of the DID resolved from the current chain entries
+    after: DIDState
+        The state of the DID resolved from the current chain entries followed by the prospective entry
+    ";
+    fn init<T0, T1, T2, T3>(&self, accepted: T0, entry_hash: T1, before: T2, after: T3) {
+        self.accepted = accepted;
+        self.entry_hash = entry_hash;
+        self.before = before;
+        self.after = after;
+    }
+    fn represent<RT>(&self) -> RT {
+        return "<{}.{}(accepted={}, entry_hash={})>".format(
+            self.module,
+            type_(self).__name__,
+            self.accepted,
+            self.entry_hash,
+        );
+    }
+    fn diff<RT>(&self) -> RT {
+        "
+        Returns
+        -------
+        DIDDiff
+            The changes the entry would make to the DID Document
+        ";
+        return diff(self.before, self.after);
+    }
+}
+fn simulate_entry<T0, T1, T2, RT>(did: T0, entry_data: T1, current_chain_entries: T2) -> RT {
+    "
+    Simulates recording an entry in a DIDManagement chain, by running the resolver on the current chain entries
+    followed by the prospective entry.
+
+    Parameters
+    ----------
+    did: str
+        The DID whose chain the entry would be recorded in
+    entry_data: dict
+        A dictionary with ExtIDs and content for the entry, as returned by export_entry_data()
+    current_chain_entries: list of dict
+        The entries currently in the DIDManagement chain, in the same format as the one accepted by
+        parse_did_chain_entries
+
+    Returns
+    -------
+    SimulationResult
+
+    Raises
+    ------
+    ValueError
+        If there is no entry to simulate
+    InvalidDIDChain
+        If the first entry in the chain is not a valid DIDManagement entry
+    ";
+    if entry_data == None {
+        raise!(ValueError("There is no entry to simulate.")); //unsupported
+    }
+    let chain_id = did.split(":")[-1];
+    let network = DID::_get_network_from_id(did);
+    let entry_hash = calculate_entry_hash(chain_id, entry_data["ext_ids"], entry_data["content"]);
+    let prospective_entry = [
+        ("extids", entry_data["ext_ids"]),
+        ("content", entry_data["content"]),
+        ("entryhash", entry_hash),
+    ]
+    .iter()
+    .cloned()
+    .collect::<HashMap<_, _>>();
+    let before = DIDState::from_parse_result(
+        did,
+        parse_did_chain_entries(current_chain_entries, chain_id, network),
+    );
+    let after = DIDState::from_parse_result(
+        did,
+        parse_did_chain_entries(
+            (current_chain_entries + vec![prospective_entry]),
+            chain_id,
+            network,
+        ),
+    );
+    return SimulationResult(
+        after.skipped_entries == before.skipped_entries,
+        entry_hash,
+        before,
+        after,
+    );
+}
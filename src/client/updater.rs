@@ -7,6 +7,8 @@ use factom_did::client::constants::{ENTRY_SCHEMA_V100, ENTRY_SIZE_LIMIT};
 use factom_did::client::did::{KeyType};
 use factom_did::client::enums::{DIDKeyPurpose, EntryType};
 use factom_did::client::keys::did::{DIDKey};
+use factom_did::client::serialization::{dumps_entry_content};
+use factom_did::client::simulator::{simulate_entry};
 struct DIDUpdater {
 did: ST0,
 orig_management_keys: ST1,
@@ -49,6 +51,9 @@ new_did_keys.push(key);
 }
 }
 self.did.did_keys = new_did_keys;
+// The aliases of revoked keys and services may be used again in a later update
+self.did.used_key_aliases = set(self.did.management_keys.iter().chain(self.did.did_keys.iter()).map(|k| k.alias).collect::<Vec<_>>());
+self.did.used_service_aliases = set(self.did.services.iter().map(|s| s.alias).collect::<Vec<_>>());
 return self.did;
 }
 fn add_management_key<T0, T1, T2, T3, T4, RT>(&self, alias: T0, priority: T1, key_type: T2, controller: T3, priority_requirement: T4) -> RT {
@@ -174,7 +179,7 @@ fn rotate_management_key<T0, RT>(&self, alias: T0) -> RT {
         ";
 for mgt_key in self.did.management_keys {
 if mgt_key.alias == alias {
-mgt_key.rotate();
+mgt_key.rotate(self.did._derive_rotated_private_key(mgt_key));
 return self;
 }
 }
@@ -191,7 +196,7 @@ fn rotate_did_key<T0, RT>(&self, alias: T0) -> RT {
         ";
 for did_key in self.did.did_keys {
 if did_key.alias == alias {
-did_key.rotate();
+did_key.rotate(self.did._derive_rotated_private_key(did_key));
 return self;
 }
 }
@@ -217,29 +222,21 @@ if !self.exists_management_key_with_priority_zero(self.orig_management_keys, new
 raise!(ValueError("DIDUpdate entry would leave no management keys of priority zero")); //unsupported
 }
 let revoke_dict = defaultdict(list);
-let mut update_key_required_priority = math.inf;
 for key in revoked_management_keys {
 revoke_dict["managementKey"].append([("id", key.alias)].iter().cloned().collect::<HashMap<_,_>>());
-update_key_required_priority = self._get_required_key_priority_for_update(key, update_key_required_priority, |k| k.priority_requirement);
-if key.priority_requirement == None {
-update_key_required_priority = self._get_required_key_priority_for_update(key, update_key_required_priority, |k| k.priority);
-}
 }
 for key in revoked_did_keys {
 revoke_dict["didKey"].append([("id", key.alias)].iter().cloned().collect::<HashMap<_,_>>());
-update_key_required_priority = self._get_required_key_priority_for_update(key, update_key_required_priority, |k| k.priority_requirement);
 }
 for (alias, purpose) in self.did_key_purposes_to_revoke.items() {
 revoke_dict["didKey"].append([("id", alias), ("purpose", vec![purpose.value])].iter().cloned().collect::<HashMap<_,_>>());
 }
 for service in revoked_services {
 revoke_dict["service"].append([("id", service.alias)].iter().cloned().collect::<HashMap<_,_>>());
-update_key_required_priority = self._get_required_key_priority_for_update(service, update_key_required_priority, |s| s.priority_requirement);
 }
 let add_dict = defaultdict(list);
 for key in new_management_keys {
 add_dict["managementKey"].append(key.to_entry_dict(self.did.id));
-update_key_required_priority = self._get_required_key_priority_for_update(key, update_key_required_priority, |k| k.priority);
 }
 for key in new_did_keys {
 add_dict["didKey"].append(key.to_entry_dict(self.did.id));
@@ -250,6 +247,7 @@ add_dict["service"].append(service.to_entry_dict(self.did.id));
 if !revoke_dict&&!add_dict {
 return None;
 }
+let update_key_required_priority = self.required_signing_priority();
 let signing_key = sorted(self.orig_management_keys, op.attrgetter("priority"))[0];
 if signing_key.priority > update_key_required_priority {
 raise!(RuntimeError("The update requires a key with priority <= {}, but the highest priority key available is with priority {}".format(update_key_required_priority, signing_key.priority))); //unsupported
@@ -261,7 +259,7 @@ entry_content_dict["revoke"] = revoke_dict;
 if add_dict {
 entry_content_dict["add"] = add_dict;
 }
-let entry_content = json.dumps(entry_content_dict, (",", ":"));
+let entry_content = dumps_entry_content(entry_content_dict, false);
 let data_to_sign = "".join(vec![EntryType::Update.value, ENTRY_SCHEMA_V100, signing_key.full_id(self.did.id), entry_content]);
 let signature = signing_key.sign(hashlib.sha256(data_to_sign.encode("utf-8")).digest());
 let ext_ids = vec![EntryType::Update.value.encode("utf-8"), ENTRY_SCHEMA_V100::encode("utf-8"), signing_key.full_id(self.did.id).encode("utf-8"), signature];
@@ -271,6 +269,61 @@ raise!(RuntimeError("You have exceeded the entry size limit! Please remove some
 }
 return [("ext_ids", ext_ids), ("content", entry_content.encode("utf-8"))].iter().cloned().collect::<HashMap<_,_>>();
 }
+fn required_signing_priority<RT>(&self) -> RT {
+"
+        Computes the priority which the management key signing the update must have, based on the priority
+        requirements of the revoked keys and services and on the priorities of the added management keys.
+
+        Returns
+        -------
+        int or float
+            The maximum (i.e. least privileged) priority allowed for the signing key, or math.inf if any management
+            key can sign the update
+        ";
+let (revoked_management_keys, revoked_did_keys, revoked_services) = self._get_revoked();
+let (new_management_keys, _, _) = self._get_new();
+let mut update_key_required_priority = math.inf;
+for key in revoked_management_keys {
+update_key_required_priority = self._get_required_key_priority_for_update(key, update_key_required_priority, |k| k.priority_requirement);
+if key.priority_requirement == None {
+update_key_required_priority = self._get_required_key_priority_for_update(key, update_key_required_priority, |k| k.priority);
+}
+}
+for key in revoked_did_keys {
+update_key_required_priority = self._get_required_key_priority_for_update(key, update_key_required_priority, |k| k.priority_requirement);
+}
+for service in revoked_services {
+update_key_required_priority = self._get_required_key_priority_for_update(service, update_key_required_priority, |s| s.priority_requirement);
+}
+for key in new_management_keys {
+update_key_required_priority = self._get_required_key_priority_for_update(key, update_key_required_priority, |k| k.priority);
+}
+return update_key_required_priority;
+}
+fn simulate<T0, RT>(&self, current_chain_entries: T0) -> RT {
+"
+        Feeds the DIDUpdate entry through the resolver's processing rules, without recording it on-chain.
+
+        Parameters
+        ----------
+        current_chain_entries: list of dict
+            The entries currently in the DIDManagement chain, in the same format as the one accepted by
+            parse_did_chain_entries
+
+        Returns
+        -------
+        SimulationResult
+            Whether the resolver would accept or skip the entry, and the resulting DID Document
+
+        Raises
+        ------
+        ValueError
+            If there are no changes to simulate
+        RuntimeError
+            If a management key of sufficient priority is not available to sign the update.
+        ";
+return simulate_entry(self.did.id, self.export_entry_data(), current_chain_entries);
+}
 fn record_on_chain<T0, T1, T2, T3>(&self, factomd: T0, walletd: T1, ec_address: T2, verbose: T3)  {
 "
         Attempts to record the DIDUpdate entry on-chain.
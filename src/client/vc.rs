@@ -0,0 +1,590 @@
+use std::collections::HashMap;
+use std::*;
+
+use datetime::{datetime, timezone};
+use factom::exceptions::FactomAPIError;
+use factom_did::client::enums::{DIDKeyPurpose, KeyType};
+use factom_did::client::jws::{_Unhashed, decode_jws, sign_jws, verify_jws_signature, InvalidJWS};
+use factom_did::client::keys::did::DIDKey;
+use factom_did::client::serialization::dumps_jcs;
+use factom_did::resolver::exceptions::InvalidDIDChain;
+use factom_did::resolver::jsonld::canonicalize_urdna2015;
+use pyld::jsonld;
+let all = vec![
+    "ProofFormat",
+    "InvalidCredential",
+    "issue_credential",
+    "issue_presentation",
+    "verify_credential",
+    "verify_presentation",
+];
+const CREDENTIALS_V2_CONTEXT: _ = "https://www.w3.org/ns/credentials/v2";
+const ED25519_2020_CONTEXT: _ = "https://w3id.org/security/suites/ed25519-2020/v1";
+const VC_JWT_TYPE: _ = "vc+jwt";
+const VP_JWT_TYPE: _ = "vp+jwt";
+struct ProofFormat {}
+
+impl ProofFormat {
+    const Jwt: _ = "jwt";
+    const EddsaJcs2022: _ = "eddsa-jcs-2022";
+    const Ed25519Signature2020: _ = "Ed25519Signature2020";
+}
+struct InvalidCredential {}
+
+impl InvalidCredential {
+    /*pass*/
+}
+fn issue_credential<T0, T1, T2, T3, RT>(credential: T0, key: T1, did: T2, proof_format: T3) -> RT {
+    "
+    Issues a Verifiable Credential (VC Data Model 2.0) signed with a DID key.
+
+    The issuer defaults to the DID and 'validFrom' to the current time. The key must be active at the issuance time
+    for the credential to be verified, so it should not be set in the past.
+
+    Parameters
+    ----------
+    credential: dict
+        The unsecured credential. Its first @context must be 'https://www.w3.org/ns/credentials/v2' and its type must
+        include 'VerifiableCredential'.
+    key: DIDKey
+        The signing key. It must have the publicKey purpose (assertionMethod) and a private key.
+    did: str
+        The DID to which the key belongs
+    proof_format: ProofFormat, optional
+        How the credential is secured: as a JWT (vc+jwt, the default), or with an 'eddsa-jcs-2022' or
+        'Ed25519Signature2020' Data Integrity proof. Data Integrity proofs require an Ed25519 key.
+
+    Returns
+    -------
+    str or dict
+        The compact JWT, or the credential with its 'proof'
+
+    Raises
+    ------
+    ValueError
+        If the credential is not valid, its issuer is not the DID, or the key is not suitable
+    ";
+    let proof_format = if proof_format == None {
+        ProofFormat::Jwt
+    } else {
+        proof_format
+    };
+    _check_signing_key(key, DIDKeyPurpose::PublicKey, proof_format);
+    let credential = dict(credential);
+    _check_document(credential, "VerifiableCredential", ValueError);
+    credential.setdefault("issuer", did);
+    if _get_id(credential["issuer"]) != did {
+        raise!(ValueError("The issuer of the credential must be the DID of the key")); //unsupported
+    }
+    credential.setdefault("validFrom", _format_datetime(time.time()));
+    return _secure(credential, key, did, proof_format, "assertionMethod", VC_JWT_TYPE, HashMap::new());
+}
+fn issue_presentation<T0, T1, T2, T3, T4, T5, RT>(
+    presentation: T0,
+    key: T1,
+    did: T2,
+    proof_format: T3,
+    challenge: T4,
+    domain: T5,
+) -> RT {
+    "
+    Issues a Verifiable Presentation (VC Data Model 2.0) signed with a DID key of the holder.
+
+    Credentials secured as JWTs are embedded as EnvelopedVerifiableCredential objects.
+
+    Parameters
+    ----------
+    presentation: dict
+        The unsecured presentation. Its first @context must be 'https://www.w3.org/ns/credentials/v2' and its type
+        must include 'VerifiablePresentation'.
+    key: DIDKey
+        The signing key. It must have the authentication purpose and a private key.
+    did: str
+        The DID of the holder. The holder defaults to it.
+    proof_format: ProofFormat, optional
+        How the presentation is secured. Defaults to a JWT (vp+jwt).
+    challenge: str, optional
+        The challenge provided by the verifier, included as 'nonce' in JWTs
+    domain: str, optional
+        The domain of the verifier, included as 'aud' in JWTs
+
+    Returns
+    -------
+    str or dict
+        The compact JWT, or the presentation with its 'proof'
+
+    Raises
+    ------
+    ValueError
+        If the presentation is not valid, its holder is not the DID, or the key is not suitable
+    ";
+    let proof_format = if proof_format == None {
+        ProofFormat::Jwt
+    } else {
+        proof_format
+    };
+    _check_signing_key(key, DIDKeyPurpose::AuthenticationKey, proof_format);
+    let presentation = dict(presentation);
+    _check_document(presentation, "VerifiablePresentation", ValueError);
+    presentation.setdefault("holder", did);
+    if _get_id(presentation["holder"]) != did {
+        raise!(ValueError("The holder of the presentation must be the DID of the key")); //unsupported
+    }
+    if presentation.iter().any(|&x| x == "verifiableCredential") {
+        presentation["verifiableCredential"] = _as_list(presentation["verifiableCredential"])
+            .iter()
+            .map(_envelop_credential)
+            .collect::<Vec<_>>();
+    }
+    let proof_options = HashMap::new();
+    if challenge != None {
+        proof_options["challenge"] = challenge;
+    }
+    if domain != None {
+        proof_options["domain"] = domain;
+    }
+    return _secure(presentation, key, did, proof_format, "authentication", VP_JWT_TYPE, proof_options);
+}
+fn verify_credential<T0, T1, T2, T3, RT>(credential: T0, resolver: T1, now: T2, trusted_timestamp: T3) -> RT {
+    "
+    Verifies a Verifiable Credential secured as a JWT or with a Data Integrity proof.
+
+    The signing key must be an active DID key of the issuer with the publicKey purpose, both at the issuance time of
+    the credential ('validFrom', or the creation time of the proof) and now. The issuance time is chosen by the signer,
+    so a credential signed with a key which has since been revoked or rotated is only accepted with a trusted_timestamp
+    at which the key was active, proving that the credential existed before the key was revoked.
+
+    Parameters
+    ----------
+    credential: str or dict
+        A compact JWT, or a credential with a 'proof'
+    resolver: DIDResolver or CachingResolver
+        The resolver used to resolve the issuer DID
+    now: int or float, optional
+        The UNIX timestamp at which validity is checked. Defaults to the current time.
+    trusted_timestamp: int or float, optional
+        A UNIX timestamp at which the credential is known to have existed, obtained independently of the signer, e.g.
+        the time at which its hash was recorded on the Factom blockchain
+
+    Returns
+    -------
+    dict
+        The credential
+
+    Raises
+    ------
+    InvalidCredential
+        If the credential is malformed, its signature is not valid, the signing key was not suitable at issuance time
+        or has been revoked since without a trusted timestamp at which it was active, its issuer is not the DID of the
+        signing key, or it has expired or is not valid yet
+    ";
+    let now = if now == None { time.time() } else { now };
+    let (credential, verification_method, _) = _verify_secured(
+        credential,
+        resolver,
+        DIDKeyPurpose::PublicKey,
+        "assertionMethod",
+        VC_JWT_TYPE,
+        now,
+        trusted_timestamp,
+    );
+    _check_document(credential, "VerifiableCredential", InvalidCredential);
+    if _get_id(credential.get("issuer")) != verification_method.split("#")[0] {
+        raise!(InvalidCredential("The issuer does not match the DID of the signing key")); //unsupported
+    }
+    if credential.iter().any(|&x| x == "validFrom") && now < _parse_datetime(credential["validFrom"]) {
+        raise!(InvalidCredential("The credential is not valid yet")); //unsupported
+    }
+    if credential.iter().any(|&x| x == "validUntil") && now > _parse_datetime(credential["validUntil"]) {
+        raise!(InvalidCredential("The credential has expired")); //unsupported
+    }
+    return credential;
+}
+fn verify_presentation<T0, T1, T2, T3, T4, RT>(
+    presentation: T0,
+    resolver: T1,
+    challenge: T2,
+    domain: T3,
+    now: T4,
+) -> RT {
+    "
+    Verifies a Verifiable Presentation secured as a JWT or with a Data Integrity proof, and all the credentials it
+    contains. The holder key and the keys which signed the credentials must still be active.
+
+    Parameters
+    ----------
+    presentation: str or dict
+        A compact JWT, or a presentation with a 'proof'
+    resolver: DIDResolver or CachingResolver
+        The resolver used to resolve the holder and issuer DIDs
+    challenge: str, optional
+        If provided, the presentation must have been signed for this challenge
+    domain: str, optional
+        If provided, the presentation must have been signed for this domain
+    now: int or float, optional
+        The UNIX timestamp at which validity is checked. Defaults to the current time.
+
+    Returns
+    -------
+    tuple
+        A 2-tuple containing the presentation (dict) and the list of verified credentials (list of dict)
+
+    Raises
+    ------
+    InvalidCredential
+        If the presentation or one of its credentials is not valid, the signing key is not an active authentication
+        key of the holder, or the challenge or domain do not match
+    ";
+    let now = if now == None { time.time() } else { now };
+    let (presentation, verification_method, proof_options) = _verify_secured(
+        presentation,
+        resolver,
+        DIDKeyPurpose::AuthenticationKey,
+        "authentication",
+        VP_JWT_TYPE,
+        now,
+        None,
+    );
+    _check_document(presentation, "VerifiablePresentation", InvalidCredential);
+    if presentation.iter().any(|&x| x == "holder")
+        && _get_id(presentation["holder"]) != verification_method.split("#")[0]
+    {
+        raise!(InvalidCredential("The holder does not match the DID of the signing key")); //unsupported
+    }
+    if challenge != None && proof_options.get("challenge") != challenge {
+        raise!(InvalidCredential("The presentation was not signed for this challenge")); //unsupported
+    }
+    if domain != None && proof_options.get("domain") != domain {
+        raise!(InvalidCredential("The presentation was not signed for this domain")); //unsupported
+    }
+    let credentials = _as_list(presentation.get("verifiableCredential", vec![]))
+        .iter()
+        .map(|c| verify_credential(_unenvelop_credential(c), resolver, now, None))
+        .collect::<Vec<_>>();
+    return (presentation, credentials);
+}
+fn _secure<T0, T1, T2, T3, T4, T5, T6, RT>(
+    document: T0,
+    key: T1,
+    did: T2,
+    proof_format: T3,
+    proof_purpose: T4,
+    jwt_type: T5,
+    proof_options: T6,
+) -> RT {
+    if proof_format == ProofFormat::Jwt {
+        let payload = dict(document);
+        if proof_options.iter().any(|&x| x == "challenge") {
+            payload["nonce"] = proof_options["challenge"];
+        }
+        if proof_options.iter().any(|&x| x == "domain") {
+            payload["aud"] = proof_options["domain"];
+        }
+        return sign_jws(
+            json.dumps(payload, (",", ":")).encode("utf-8"),
+            key,
+            did,
+            [("typ", jwt_type), ("cty", jwt_type.split("+")[0])]
+                .iter()
+                .cloned()
+                .collect::<HashMap<_, _>>(),
+        );
+    }
+    let proof = [
+        ("type", "DataIntegrityProof"),
+        ("cryptosuite", ProofFormat::EddsaJcs2022),
+        ("created", _format_datetime(time.time())),
+        ("verificationMethod", key.full_id(did)),
+        ("proofPurpose", proof_purpose),
+    ]
+    .iter()
+    .cloned()
+    .collect::<HashMap<_, _>>();
+    if proof_format == ProofFormat::Ed25519Signature2020 {
+        proof["type"] = ProofFormat::Ed25519Signature2020;
+        proof.pop("cryptosuite");
+        if document["@context"].iter().all(|&x| x != ED25519_2020_CONTEXT) {
+            document["@context"] = (document["@context"] + vec![ED25519_2020_CONTEXT]);
+        }
+    }
+    proof.update(proof_options);
+    let signature = key.sign(_hash_data(document, proof), _Unhashed);
+    proof["proofValue"] = "z{}".format(base58.b58encode(signature).decode());
+    document["proof"] = proof;
+    return document;
+}
+fn _verify_secured<T0, T1, T2, T3, T4, T5, T6, RT>(
+    document: T0,
+    resolver: T1,
+    purpose: T2,
+    proof_purpose: T3,
+    jwt_type: T4,
+    now: T5,
+    trusted_timestamp: T6,
+) -> RT {
+    "
+    Verifies the signature of a secured credential or presentation with the key which was active when it was signed,
+    and which is still active unless a trusted timestamp is provided.
+
+    Returns
+    -------
+    tuple
+        A 3-tuple containing the document (dict), the full DID URL of the signing key and the proof options (dict)
+        with the challenge and domain it was signed for
+    ";
+    if isinstance(document, str) {
+        let try_dummy = {
+            //unsupported
+            let (header, payload) = decode_jws(document);
+            let unsecured = json.loads(payload);
+        };
+        let except!((InvalidJWS, ValueError)) = {
+            //unsupported
+            raise!(InvalidCredential("Malformed JWT")); //unsupported
+        };
+        if !isinstance(unsecured, dict) || header.get("typ") != jwt_type {
+            raise!(InvalidCredential("The JWT must have the type {} and a JSON object payload".format(jwt_type))); //unsupported
+        }
+        let signed_at = if unsecured.iter().any(|&x| x == "validFrom") {
+            _parse_datetime(unsecured["validFrom"])
+        } else {
+            now
+        };
+        let key = _resolve_key(resolver, header["kid"], signed_at, purpose, trusted_timestamp);
+        let try_dummy = {
+            //unsupported
+            verify_jws_signature(document, key);
+        };
+        let except!(InvalidJWS as e) = {
+            //unsupported
+            raise!(InvalidCredential("Invalid signature: {}".format(e))); //unsupported
+        };
+        let proof_options = [
+            ("challenge", unsecured.pop("nonce", None)),
+            ("domain", unsecured.pop("aud", None)),
+        ]
+        .iter()
+        .cloned()
+        .collect::<HashMap<_, _>>();
+        return (unsecured, header["kid"], proof_options);
+    }
+    if !isinstance(document, dict) || !isinstance(document.get("proof"), dict) {
+        raise!(InvalidCredential("The document must be a JWT or have a single Data Integrity proof")); //unsupported
+    }
+    let proof = document["proof"];
+    let suite = if proof.get("type") == "DataIntegrityProof" {
+        proof.get("cryptosuite")
+    } else {
+        proof.get("type")
+    };
+    if suite != ProofFormat::EddsaJcs2022 && suite != ProofFormat::Ed25519Signature2020 {
+        raise!(InvalidCredential("Unsupported proof type: {}".format(suite))); //unsupported
+    }
+    if proof.get("proofPurpose") != proof_purpose {
+        raise!(InvalidCredential("The proof purpose must be {}".format(proof_purpose))); //unsupported
+    }
+    let verification_method = proof.get("verificationMethod");
+    if !isinstance(verification_method, str) || !verification_method.contains("#") {
+        raise!(InvalidCredential("The verification method must be the full DID URL of a key")); //unsupported
+    }
+    let proof_value = proof.get("proofValue");
+    if !isinstance(proof_value, str) || !proof_value.startswith("z") {
+        raise!(InvalidCredential("The proof value must be base58btc multibase encoded")); //unsupported
+    }
+    let key = _resolve_key(
+        resolver,
+        verification_method,
+        _parse_datetime(proof.get("created")),
+        purpose,
+        trusted_timestamp,
+    );
+    if key.key_type != KeyType::EdDSA {
+        raise!(InvalidCredential("{} proofs require an Ed25519 key".format(suite))); //unsupported
+    }
+    let try_dummy = {
+        //unsupported
+        let valid = key.verify(
+            _hash_data(document, proof),
+            base58.b58decode(proof_value[1..]),
+            _Unhashed,
+        );
+    };
+    let except!((AssertionError, ValueError, jsonld.JsonLdError)) = {
+        //unsupported
+        let valid = false;
+    };
+    if !valid {
+        raise!(InvalidCredential("Invalid signature")); //unsupported
+    }
+    let unsecured = document
+        .items()
+        .iter()
+        .filter(|(k, _)| k != "proof")
+        .map(|(k, v)| (k, v))
+        .collect::<HashMap<_, _>>();
+    return (unsecured, verification_method, proof);
+}
+fn _resolve_key<T0, T1, T2, T3, T4, RT>(
+    resolver: T0,
+    verification_method: T1,
+    signed_at: T2,
+    purpose: T3,
+    trusted_timestamp: T4,
+) -> RT {
+    "
+    Returns the key which was active at the given signing time. The signing time is chosen by the signer, so the key
+    must also be active now, with the same public key, unless it was active at the trusted timestamp.
+    ";
+    let (did, alias) = verification_method.split("#", 1);
+    let try_dummy = {
+        //unsupported
+        let state = resolver.resolve_state_at(did, signed_at);
+        let current_state = if trusted_timestamp == None {
+            resolver.resolve_state(did, None)
+        } else {
+            resolver.resolve_state_at(did, trusted_timestamp)
+        };
+    };
+    let except!((ValueError, InvalidDIDChain, FactomAPIError)) = {
+        //unsupported
+        raise!(InvalidCredential("DID {} cannot be resolved".format(did))); //unsupported
+    };
+    let key = state.did_keys.get(alias);
+    if key == None {
+        raise!(InvalidCredential(
+            "{} was not an active DID key at the time of signing".format(verification_method)
+        )); //unsupported
+    }
+    if key.purpose.iter().all(|&x| x != purpose) {
+        raise!(InvalidCredential(
+            "{} did not have the purpose {} at the time of signing".format(verification_method, purpose.value)
+        )); //unsupported
+    }
+    let current_key = current_state.did_keys.get(alias);
+    if current_key == None
+        || current_key.public_key != key.public_key
+        || current_key.purpose.iter().all(|&x| x != purpose)
+    {
+        if trusted_timestamp == None {
+            raise!(InvalidCredential(
+                "{} has been revoked or rotated since the time of signing".format(verification_method)
+            )); //unsupported
+        }
+        raise!(InvalidCredential(
+            "{} was not an active DID key at the trusted timestamp".format(verification_method)
+        )); //unsupported
+    }
+    return key;
+}
+fn _hash_data<T0, T1, RT>(document: T0, proof: T1) -> RT {
+    "
+    Computes the data signed by a Data Integrity proof: the SHA-256 digest of the canonical proof options (with the
+    @context of the document) followed by the SHA-256 digest of the canonical document without its proof.
+    eddsa-jcs-2022 canonicalizes with JCS (RFC 8785), and Ed25519Signature2020 with URDNA2015.
+    ";
+    let unsecured = document
+        .items()
+        .iter()
+        .filter(|(k, _)| k != "proof")
+        .map(|(k, v)| (k, v))
+        .collect::<HashMap<_, _>>();
+    let proof_options = proof
+        .items()
+        .iter()
+        .filter(|(k, _)| k != "proofValue")
+        .map(|(k, v)| (k, v))
+        .collect::<HashMap<_, _>>();
+    proof_options["@context"] = document["@context"];
+    let canonicalize = if proof.get("cryptosuite") == ProofFormat::EddsaJcs2022 {
+        dumps_jcs
+    } else {
+        canonicalize_urdna2015
+    };
+    return hashlib.sha256(canonicalize(proof_options).encode("utf-8")).digest()
+        + hashlib.sha256(canonicalize(unsecured).encode("utf-8")).digest();
+}
+fn _check_signing_key<T0, T1, T2>(key: T0, purpose: T1, proof_format: T2) {
+    if !isinstance(key, DIDKey) {
+        raise!(ValueError("Credentials and presentations must be signed with a DID key")); //unsupported
+    }
+    if key.purpose.iter().all(|&x| x != purpose) {
+        raise!(ValueError("The key must have the purpose {}".format(purpose.value))); //unsupported
+    }
+    if proof_format != ProofFormat::Jwt
+        && proof_format != ProofFormat::EddsaJcs2022
+        && proof_format != ProofFormat::Ed25519Signature2020
+    {
+        raise!(ValueError("Unknown proof format: {}".format(proof_format))); //unsupported
+    }
+    if proof_format != ProofFormat::Jwt && key.key_type != KeyType::EdDSA {
+        raise!(ValueError("{} proofs require an Ed25519 key".format(proof_format))); //unsupported
+    }
+}
+fn _check_document<T0, T1, T2>(document: T0, document_type: T1, error: T2) {
+    let contexts = document.get("@context");
+    if !isinstance(contexts, list) || !contexts || contexts[0] != CREDENTIALS_V2_CONTEXT {
+        raise!(error("The first @context must be {}".format(CREDENTIALS_V2_CONTEXT))); //unsupported
+    }
+    if _as_list(document.get("type")).iter().all(|&x| x != document_type) {
+        raise!(error("The type must include {}".format(document_type))); //unsupported
+    }
+    if document_type == "VerifiableCredential" && document.get("credentialSubject") == None {
+        raise!(error("The credential must have a credentialSubject")); //unsupported
+    }
+}
+fn _envelop_credential<T0, RT>(credential: T0) -> RT {
+    if !isinstance(credential, str) {
+        return credential;
+    }
+    return [
+        ("@context", vec![CREDENTIALS_V2_CONTEXT]),
+        ("id", "data:application/{},{}".format(VC_JWT_TYPE, credential)),
+        ("type", "EnvelopedVerifiableCredential"),
+    ]
+    .iter()
+    .cloned()
+    .collect::<HashMap<_, _>>();
+}
+fn _unenvelop_credential<T0, RT>(credential: T0) -> RT {
+    if !isinstance(credential, dict) || credential.get("type") != "EnvelopedVerifiableCredential" {
+        return credential;
+    }
+    let prefix = "data:application/{},".format(VC_JWT_TYPE);
+    if !isinstance(credential.get("id"), str) || !credential["id"].startswith(prefix) {
+        raise!(InvalidCredential("Only vc+jwt enveloped credentials are supported")); //unsupported
+    }
+    return credential["id"][prefix.len()..];
+}
+fn _get_id<T0, RT>(value: T0) -> RT {
+    return if isinstance(value, dict) {
+        value.get("id")
+    } else {
+        value
+    };
+}
+fn _as_list<T0, RT>(value: T0) -> RT {
+    return if isinstance(value, list) {
+        value
+    } else {
+        vec![value]
+    };
+}
+fn _format_datetime<T0, RT>(timestamp: T0) -> RT {
+    return datetime
+        .fromtimestamp(int(timestamp), timezone.utc)
+        .strftime("%Y-%m-%dT%H:%M:%SZ");
+}
+fn _parse_datetime<T0, RT>(value: T0) -> RT {
+    let try_dummy = {
+        //unsupported
+        let parsed = datetime.fromisoformat(value.replace("Z", "+00:00"));
+    };
+    let except!((TypeError, ValueError, AttributeError)) = {
+        //unsupported
+        raise!(InvalidCredential("Invalid date-time: {}".format(value))); //unsupported
+    };
+    if parsed.tzinfo == None {
+        raise!(InvalidCredential("Date-times must include a time zone: {}".format(value))); //unsupported
+    }
+    return parsed.timestamp();
+}
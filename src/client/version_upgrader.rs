@@ -1,10 +1,12 @@
 use std::*;
 use std::collections::HashMap;
 
-use packaging::{version};
 use factom_did::client::blockchain::{record_entry};
 use factom_did::client::constants::{ENTRY_SCHEMA_V100};
 use factom_did::client::enums::{EntryType};
+use factom_did::client::serialization::{dumps_entry_content};
+use factom_did::resolver::method_versions::{is_method_version_upgrade};
+use factom_did::resolver::registry::{EntryProcessorRegistry};
 struct DIDVersionUpgrader {
 did: ST0,
 new_spec_version: ST1,
@@ -20,16 +22,23 @@ impl DIDVersionUpgrader {
         The DID object to update
     new_spec_version: str
         The new version to upgrade to
+    registry: EntryProcessorRegistry, optional
+        The registry whose DID method version rules the new version must have. Defaults to the registry used by the
+        resolver, so that only versions which the resolver supports can be recorded.
 
     Raises
     ------
     ValueError
-        If the new version is not an upgrade on the current version
+        If the new version is not an upgrade on the current version, or if it has no rules in the registry
     ";
-fn init<T0, T1>(&self, did: T0, new_spec_version: T1)  {
-if version::parse(did.spec_version) >= version::parse(new_spec_version) {
+fn init<T0, T1, T2>(&self, did: T0, new_spec_version: T1, registry: T2)  {
+if !is_method_version_upgrade(did.spec_version, new_spec_version) {
 raise!(ValueError("New version must be an upgrade on old version")); //unsupported
 }
+let registry = if registry == None { EntryProcessorRegistry::default() } else { registry };
+if !registry.is_supported_method_version(new_spec_version) {
+raise!(ValueError("Unsupported DID method version: {}".format(new_spec_version))); //unsupported
+}
 self.did = did;
 self.new_spec_version = new_spec_version;
 }
@@ -43,7 +52,7 @@ fn export_entry_data<RT>(&self) -> RT {
             A dictionary with ExtIDs and content for the entry
         ";
 let signing_key = sorted(self.did.management_keys, op.attrgetter("priority"), true)[0];
-let entry_content = json.dumps([("didMethodVersion", self.new_spec_version)].iter().cloned().collect::<HashMap<_,_>>()).replace(" ", "");
+let entry_content = dumps_entry_content([("didMethodVersion", self.new_spec_version)].iter().cloned().collect::<HashMap<_,_>>(), false);
 let data_to_sign = "".join(vec![EntryType::VersionUpgrade.value, ENTRY_SCHEMA_V100, signing_key.full_id(self.did.id), entry_content]);
 let signature = signing_key.sign(hashlib.sha256(data_to_sign.encode("utf-8")).digest());
 let ext_ids = vec![EntryType::VersionUpgrade.value.encode("utf-8"), ENTRY_SCHEMA_V100::encode("utf-8"), signing_key.full_id(self.did.id).encode("utf-8"), signature];
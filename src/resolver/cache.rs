@@ -0,0 +1,363 @@
+use std::collections::HashMap;
+use std::*;
+
+use collections::OrderedDict;
+use factom::exceptions::FactomAPIError;
+use factom_did::client::validators::validate_did;
+use factom_did::resolver::chain_state::{DIDChainState, CHAIN_STATE_SNAPSHOT_VERSION};
+use factom_did::resolver::exceptions::is_missing_chain_error;
+let all = vec![
+    "CachePolicy",
+    "CacheStorage",
+    "InMemoryLRUStorage",
+    "SQLiteStorage",
+    "CachingResolver",
+];
+// Storage backend for CachingResolver. Records are JSON serializable dictionaries with the keys 'snapshot' (a
+// DIDChainState snapshot), 'chainHead' (the keyMR of the chain head when the snapshot was taken) and 'checkedAt' (the
+// UNIX timestamp at which the chain head was last checked). Records are keyed by the resolution mode and the DID, as
+// the same chain can resolve to different states in different modes.
+trait CacheStorage {
+    // Returns the record stored under the key, or None if there is none.
+    fn get<T0, RT>(&self, key: T0) -> RT;
+    // Stores the record under the key, replacing any existing record.
+    fn set<T0, T1>(&self, key: T0, record: T1);
+    // Removes the record stored under the key, if any.
+    fn delete<T0>(&self, key: T0);
+    // Removes all records.
+    fn clear(&self);
+}
+struct CachePolicy {
+    ttl: ST0,
+    max_stale: ST1,
+}
+
+impl CachePolicy {
+    "
+    Determines when cached resolution results are revalidated.
+
+    Attributes
+    ----------
+    ttl: int
+        Number of seconds during which a cached result is returned without checking the chain head. 0 means that the
+        chain head is checked on every resolution.
+    max_stale: int
+        Number of seconds since the last successful check during which a cached result may still be returned if the
+        chain head cannot be queried. 0 means that stale results are never returned.
+    ";
+    fn init<T0, T1>(&self, ttl: T0, max_stale: T1) {
+        if ttl < 0 || max_stale < 0 {
+            raise!(ValueError("ttl and max_stale must be non-negative.")); //unsupported
+        }
+        self.ttl = ttl;
+        self.max_stale = max_stale;
+    }
+    fn is_fresh<T0, T1, RT>(&self, record: T0, now: T1) -> RT {
+        return (now - record["checkedAt"]) < self.ttl;
+    }
+    fn is_usable_when_stale<T0, T1, RT>(&self, record: T0, now: T1) -> RT {
+        return (now - record["checkedAt"]) < self.max_stale;
+    }
+}
+struct InMemoryLRUStorage {
+    capacity: ST0,
+    records: ST1,
+}
+
+impl CacheStorage for InMemoryLRUStorage {
+    "
+    In-memory storage, which evicts the least recently used record once its capacity is reached.
+
+    Attributes
+    ----------
+    capacity: int
+        The maximum number of DIDs for which records are kept
+    ";
+    fn init<T0>(&self, capacity: T0) {
+        if capacity < 1 {
+            raise!(ValueError("Capacity must be a positive integer.")); //unsupported
+        }
+        self.capacity = capacity;
+        self.records = OrderedDict();
+    }
+    fn get<T0, RT>(&self, key: T0) -> RT {
+        if self.records.iter().all(|&x| x != key) {
+            return None;
+        }
+        self.records.move_to_end(key);
+        return self.records[key];
+    }
+    fn set<T0, T1>(&self, key: T0, record: T1) {
+        self.records[key] = record;
+        self.records.move_to_end(key);
+        while self.records.len() > self.capacity {
+            self.records.popitem(false);
+        }
+    }
+    fn delete<T0>(&self, key: T0) {
+        self.records.pop(key, None);
+    }
+    fn clear(&self) {
+        self.records.clear();
+    }
+}
+struct SQLiteStorage {
+    connection: ST0,
+}
+
+impl CacheStorage for SQLiteStorage {
+    "
+    On-disk storage, backed by an embedded SQLite database.
+
+    Attributes
+    ----------
+    path: str
+        The path of the database file. It is created if it does not exist.
+    ";
+    fn init<T0>(&self, path: T0) {
+        self.connection = sqlite3.connect(path);
+        self.connection.execute(
+            "CREATE TABLE IF NOT EXISTS did_resolution_cache (did TEXT PRIMARY KEY, record TEXT NOT NULL)",
+        );
+        self.connection.commit();
+    }
+    fn get<T0, RT>(&self, key: T0) -> RT {
+        let row = self
+            .connection
+            .execute("SELECT record FROM did_resolution_cache WHERE did = ?", (key,))
+            .fetchone();
+        return if row == None { None } else { json.loads(row[0]) };
+    }
+    fn set<T0, T1>(&self, key: T0, record: T1) {
+        self.connection.execute(
+            "INSERT OR REPLACE INTO did_resolution_cache (did, record) VALUES (?, ?)",
+            (key, json.dumps(record, (",", ":"))),
+        );
+        self.connection.commit();
+    }
+    fn delete<T0>(&self, key: T0) {
+        self.connection.execute("DELETE FROM did_resolution_cache WHERE did = ?", (key,));
+        self.connection.commit();
+    }
+    fn clear(&self) {
+        self.connection.execute("DELETE FROM did_resolution_cache");
+        self.connection.commit();
+    }
+    fn close(&self) {
+        self.connection.close();
+    }
+}
+struct CachingResolver {
+    resolver: ST0,
+    storage: ST1,
+    policy: ST2,
+}
+
+impl CachingResolver {
+    "
+    Wraps a DIDResolver and caches the parsed state of each DIDManagement chain, together with the keyMR of the chain
+    head at the time it was parsed.
+
+    A cached result is revalidated by querying only the chain head. If the chain head has not changed, the cached
+    result is returned; otherwise only the entries recorded since the cached result was parsed are processed.
+
+    Results are cached separately for each resolution mode, so that resolvers with different modes can share a
+    storage without being served each other's results.
+
+    Attributes
+    ----------
+    resolver: DIDResolver
+        The resolver used to read the chains
+    storage: CacheStorage, optional
+        The storage backend. Defaults to an in-memory LRU storage with a capacity of 10000 DIDs.
+    policy: CachePolicy, optional
+        The revalidation policy. Defaults to checking the chain head on every resolution.
+    ";
+    fn init<T0, T1, T2>(&self, resolver: T0, storage: T1, policy: T2) {
+        self.resolver = resolver;
+        self.storage = if storage == None {
+            InMemoryLRUStorage(10000)
+        } else {
+            storage
+        };
+        self.policy = if policy == None { CachePolicy(0, 0) } else { policy };
+    }
+    fn options<RT>(&self) -> RT {
+        return self.resolver.options;
+    }
+    fn resolve<T0, RT>(&self, did: T0) -> RT {
+        "
+        Resolves the given DID, using the cache where possible.
+
+        Parameters
+        ----------
+        did: str
+
+        Returns
+        -------
+        tuple
+            A 4-tuple containing the active management keys, the active DID key, the active services and the number of
+            entries skipped while parsing the chain, as returned by DIDResolver.resolve. A deactivated DID cannot be
+            told apart from a DID without keys and services; use resolve_state instead.
+        ";
+        return self.resolve_chain_state(did).to_tuple();
+    }
+    fn resolve_state<T0, T1, RT>(&self, did: T0, until_entry_hash: T1) -> RT {
+        "
+        Resolves the given DID to a DIDState, using the cache where possible. Historical versions are not cached.
+
+        Parameters
+        ----------
+        did: str
+        until_entry_hash: bytes, optional
+            If provided, the state of the DID right after the entry with this hash was recorded is returned
+
+        Returns
+        -------
+        DIDState
+            The resolved state, including the deactivation metadata if the DID has been deactivated
+        ";
+        if until_entry_hash != None {
+            return self.resolver.resolve_state(did, until_entry_hash);
+        }
+        return self.resolve_chain_state(did).to_did_state(did);
+    }
+    fn resolve_chain_state<T0, RT>(&self, did: T0) -> RT {
+        "
+        Resolves the given DID to its chain state, using the cache where possible.
+
+        Parameters
+        ----------
+        did: str
+
+        Returns
+        -------
+        DIDChainState
+
+        Raises
+        ------
+        ValueError
+            If the DID is not valid
+        RuntimeError
+            If the chain head cannot be queried and there is no cached result within the staleness limit
+        FactomAPIError
+            If the DIDManagement chain does not exist
+        InvalidDIDChain
+            If the first entry in the chain is not a valid DIDManagement entry
+        ";
+        validate_did(did);
+        let now = time.time();
+        let key = self._cache_key(did);
+        let mut record = self.storage.get(key);
+        if record != None && record["snapshot"].get("version") != CHAIN_STATE_SNAPSHOT_VERSION {
+            record = None;
+        }
+        if record != None && self.policy.is_fresh(record, now) {
+            return DIDChainState::from_dict(record["snapshot"]);
+        }
+        let try_dummy = {
+            //unsupported
+            let chain_head = self.resolver.factomd.chain_head(did.split(":")[-1])["chainhead"];
+        };
+        let except!(FactomAPIError as e) = {
+            //unsupported
+            if is_missing_chain_error(e) {
+                self.storage.delete(key);
+                raise!(e); //unsupported
+            }
+            if record != None && self.policy.is_usable_when_stale(record, now) {
+                return DIDChainState::from_dict(record["snapshot"]);
+            }
+            raise!(RuntimeError(
+                "Failed while trying to read the chain head: {}".format(e.data)
+            )); //unsupported
+        };
+        let state = if record == None {
+            self.resolver.resolve_incremental(did, None)
+        } else {
+            if record["chainHead"] == chain_head {
+                DIDChainState::from_dict(record["snapshot"])
+            } else {
+                self.resolver.resolve_incremental(did, record["snapshot"])
+            }
+        };
+        self.storage.set(
+            key,
+            [
+                ("snapshot", state.to_dict()),
+                ("chainHead", chain_head),
+                ("checkedAt", now),
+            ]
+            .iter()
+            .cloned()
+            .collect::<HashMap<_, _>>(),
+        );
+        return state;
+    }
+    fn key_history<T0, RT>(&self, did: T0) -> RT {
+        "
+        Returns the history of all management and DID keys of the given DID, using the cache where possible.
+
+        Parameters
+        ----------
+        did: str
+
+        Returns
+        -------
+        list of dict
+            The key history, as described in DIDChainState
+        ";
+        return self.resolve_chain_state(did).key_history;
+    }
+    fn is_key_ever_used<T0, T1, RT>(&self, did: T0, public_key: T1) -> RT {
+        "
+        Checks whether the given public key has been active at some point for the given DID, using the cache where
+        possible.
+
+        Parameters
+        ----------
+        did: str
+        public_key: bytes or str
+            The public key, either as raw bytes or in its on-chain representation (base58 or PEM)
+
+        Returns
+        -------
+        bool
+        ";
+        return self.resolve_chain_state(did).is_key_ever_used(public_key);
+    }
+    fn resolve_state_at<T0, T1, RT>(&self, did: T0, timestamp: T1) -> RT {
+        "
+        Resolves the state of the given DID at a point in time. Historical states are not cached.
+
+        Parameters
+        ----------
+        did: str
+        timestamp: int or float
+
+        Returns
+        -------
+        DIDState
+        ";
+        return self.resolver.resolve_state_at(did, timestamp);
+    }
+    fn invalidate<T0>(&self, did: T0) {
+        "
+        Removes the cached result for the given DID, forcing a full resolution the next time it is resolved.
+
+        Parameters
+        ----------
+        did: str
+        ";
+        self.storage.delete(self._cache_key(did));
+    }
+    fn _cache_key<T0, RT>(&self, did: T0) -> RT {
+        return "{}:{}".format(self.resolver.options.mode, did);
+    }
+    fn invalidate_all(&self) {
+        "
+        Removes all cached results.
+        ";
+        self.storage.clear();
+    }
+}
@@ -0,0 +1,422 @@
+use std::collections::HashMap;
+use std::*;
+
+use factom_did::client::cbor::{dumps_cbor, loads_cbor};
+use factom_did::client::constants::DID_METHOD_NAME;
+use factom_did::client::enums::Network;
+use factom_did::client::keys::did::DIDKey;
+use factom_did::client::keys::management::ManagementKey;
+use factom_did::client::service::Service;
+use factom_did::resolver::deactivation::DeactivationMetadata;
+use factom_did::resolver::state::DIDState;
+let all = vec!["DIDChainState"];
+const CHAIN_STATE_SNAPSHOT_VERSION: _ = 4;
+struct DIDChainState {
+    chain_id: ST0,
+    network: ST1,
+    management_keys: HashMap<_, _>,
+    did_keys: HashMap<_, _>,
+    services: HashMap<_, _>,
+    all_keys: ST2,
+    method_version: ST3,
+    skipped_entries: ST4,
+    processed_entry_hashes: ST5,
+    processed_entries: ST12,
+    keep_parsing: ST6,
+    last_entry_hash: ST7,
+    key_history: ST8,
+    last_skip_reason: ST9,
+    last_entry_timestamp: ST10,
+    deactivation: ST11,
+}
+
+impl DIDChainState {
+    "
+    The state of the resolver while parsing a DIDManagement chain. Entry processors read and update this state.
+
+    Attributes
+    ----------
+    chain_id: str
+        The DIDManagement chain ID
+    network: Network
+        The Factom network on which the DID is recorded
+    management_keys: dict
+        The currently active management keys, keyed by alias
+    did_keys: dict
+        The currently active DID keys, keyed by alias
+    services: dict
+        The currently active services, keyed by alias
+    all_keys: set
+        The set of all management and DID keys that have been active at some point for the chain
+    method_version: str
+        The current DID method spec version. None until the DIDManagement entry has been processed.
+    skipped_entries: int
+        The number of entries skipped so far
+    processed_entry_hashes: set
+        The hashes of all entries seen so far
+    processed_entries: int
+        The number of entries of the chain seen so far, including skipped and duplicate entries. Parsing is resumed
+        from this position in the chain.
+    keep_parsing: bool
+        False once an entry has been processed after which no further entries must be applied (e.g. a deactivation)
+    last_entry_hash: str
+        The hash of the last entry of the chain seen by the parser, whether it was applied or skipped
+    key_history: list of dict
+        One record for each period during which a management or DID key was active, in the order in which the keys
+        were added. Each record has the keys 'alias', 'keyKind' ('managementKey' or 'didKey'), 'type',
+        'publicKeyField', 'publicKey', 'addedIn' and 'revokedIn', where the last two are the hashes of the entries
+        which added and revoked the key. 'revokedIn' is None while the key is active.
+    last_skip_reason: str
+        Why the last skipped entry was skipped. Not included in snapshots.
+    last_entry_timestamp: int
+        The timestamp of the last entry of the chain seen by the parser, if provided by the chain reader. Not included
+        in snapshots.
+    deactivation: DeactivationMetadata
+        The metadata of the DIDDeactivation entry, or None if the DID has not been deactivated
+    ";
+    fn init<T0, T1>(&self, chain_id: T0, network: T1) {
+        self.chain_id = chain_id;
+        self.network = network;
+        self.management_keys = HashMap::new();
+        self.did_keys = HashMap::new();
+        self.services = HashMap::new();
+        self.all_keys = set();
+        self.method_version = None;
+        self.skipped_entries = 0;
+        self.processed_entry_hashes = set();
+        self.processed_entries = 0;
+        self.keep_parsing = true;
+        self.last_entry_hash = None;
+        self.key_history = vec![];
+        self.last_skip_reason = None;
+        self.last_entry_timestamp = None;
+        self.deactivation = None;
+    }
+    fn represent<RT>(&self) -> RT {
+        return "<{}.{}(chain_id={}, management_keys={}, did_keys={}, services={}, skipped_entries={})>".format(
+            self.module,
+            type_(self).__name__,
+            self.chain_id,
+            self.management_keys.len(),
+            self.did_keys.len(),
+            self.services.len(),
+            self.skipped_entries,
+        );
+    }
+    fn is_initialized<RT>(&self) -> RT {
+        "
+        Returns
+        -------
+        bool
+            True if the DIDManagement entry of the chain has been processed
+        ";
+        return self.method_version != None;
+    }
+    fn skip_entry<T0>(&self, reason: T0) {
+        "
+        Records that the current entry has been skipped.
+
+        Parameters
+        ----------
+        reason: str, optional
+            Why the entry has been skipped
+        ";
+        self.skipped_entries += 1;
+        self.last_skip_reason = if reason == None { "Invalid entry" } else { reason };
+    }
+    fn record_active_keys(&self) {
+        "
+        Adds the currently active management and DID keys to the set of all keys ever active for the chain and updates
+        the key history with the keys added and revoked by the last entry.
+        ";
+        self.all_keys.update(self.management_keys.values(), self.did_keys.values());
+        let active_keys = self
+            .management_keys
+            .values()
+            .iter()
+            .map(|k| _key_record(k, "managementKey"))
+            .chain(self.did_keys.values().iter().map(|k| _key_record(k, "didKey")))
+            .collect::<Vec<_>>();
+        let active_identities = set(active_keys.iter().map(_key_identity));
+        for record in self.key_history {
+            if record["revokedIn"] == None && active_identities.iter().all(|&x| x != _key_identity(record)) {
+                record["revokedIn"] = self.last_entry_hash;
+            }
+        }
+        let open_identities = set(
+            self.key_history
+                .iter()
+                .filter(|r| r["revokedIn"] == None)
+                .map(_key_identity),
+        );
+        for record in active_keys {
+            if open_identities.iter().all(|&x| x != _key_identity(record)) {
+                record["addedIn"] = self.last_entry_hash;
+                record["revokedIn"] = None;
+                self.key_history.append(record);
+            }
+        }
+    }
+    fn is_key_ever_used<T0, RT>(&self, public_key: T0) -> RT {
+        "
+        Parameters
+        ----------
+        public_key: bytes or str
+            The public key, either as raw bytes or in its on-chain representation (base58 or PEM)
+
+        Returns
+        -------
+        bool
+            True if the public key has been active at some point for the chain, as a management or DID key
+        ";
+        let candidates = if isinstance(public_key, str) {
+            set(vec![public_key])
+        } else {
+            set(vec![
+                base58.b58encode(public_key).decode(),
+                public_key.decode("utf-8", "ignore"),
+            ])
+        };
+        return self
+            .key_history
+            .iter()
+            .any(|r| candidates.iter().any(|&x| x == r["publicKey"]));
+    }
+    fn to_tuple<RT>(&self) -> RT {
+        "
+        Returns
+        -------
+        tuple
+            A 4-tuple containing the active management keys, the active DID key, the active services and the number of
+            entries skipped while parsing the chain.
+        ";
+        return (
+            self.management_keys,
+            self.did_keys,
+            self.services,
+            self.skipped_entries,
+        );
+    }
+    fn did<RT>(&self) -> RT {
+        "
+        Returns
+        -------
+        str
+            The DID whose chain is being parsed
+        ";
+        if self.network == Network::Unspecified {
+            return "{}:{}".format(DID_METHOD_NAME, self.chain_id);
+        }
+        return ":".join(vec![DID_METHOD_NAME, self.network.value, self.chain_id]);
+    }
+    fn to_dict<RT>(&self) -> RT {
+        "
+        Converts the chain state to a JSON serializable snapshot, from which parsing can be resumed with
+        resolve_incremental.
+
+        Returns
+        -------
+        dict
+        ";
+        let did = self.did();
+        return [
+            ("version", CHAIN_STATE_SNAPSHOT_VERSION),
+            ("chainId", self.chain_id),
+            ("network", self.network.value),
+            ("managementKey", self.management_keys.values().iter().map(|k| k.to_entry_dict(did)).collect::<Vec<_>>()),
+            ("didKey", self.did_keys.values().iter().map(|k| k.to_entry_dict(did)).collect::<Vec<_>>()),
+            ("service", self.services.values().iter().map(|s| s.to_entry_dict(did)).collect::<Vec<_>>()),
+            (
+                "allKeys",
+                [
+                    (
+                        "managementKey",
+                        self.all_keys
+                            .iter()
+                            .filter(|k| isinstance(k, ManagementKey))
+                            .map(|k| k.to_entry_dict(did))
+                            .collect::<Vec<_>>(),
+                    ),
+                    (
+                        "didKey",
+                        self.all_keys
+                            .iter()
+                            .filter(|k| isinstance(k, DIDKey))
+                            .map(|k| k.to_entry_dict(did))
+                            .collect::<Vec<_>>(),
+                    ),
+                ]
+                .iter()
+                .cloned()
+                .collect::<HashMap<_, _>>(),
+            ),
+            ("didMethodVersion", self.method_version),
+            ("skippedEntries", self.skipped_entries),
+            ("processedEntryHashes", sorted(self.processed_entry_hashes)),
+            ("processedEntries", self.processed_entries),
+            ("keepParsing", self.keep_parsing),
+            ("lastEntryHash", self.last_entry_hash),
+            ("keyHistory", self.key_history),
+            (
+                "deactivation",
+                if self.deactivation == None {
+                    None
+                } else {
+                    self.deactivation.to_dict()
+                },
+            ),
+        ]
+        .iter()
+        .cloned()
+        .collect::<HashMap<_, _>>();
+    }
+    fn from_dict<T0, RT>(snapshot: T0) -> RT {
+        "
+        Restores a chain state from a snapshot produced by to_dict.
+
+        Parameters
+        ----------
+        snapshot: dict
+
+        Returns
+        -------
+        DIDChainState
+
+        Raises
+        ------
+        ValueError
+            If the snapshot version is not supported
+        ";
+        if snapshot.get("version") != CHAIN_STATE_SNAPSHOT_VERSION {
+            raise!(ValueError(
+                "Unsupported chain state snapshot version: {}".format(snapshot.get("version"))
+            )); //unsupported
+        }
+        let state = DIDChainState(snapshot["chainId"], Network::from_str(snapshot["network"]));
+        state.management_keys = snapshot["managementKey"]
+            .iter()
+            .map(|d| ManagementKey::from_entry_dict(d))
+            .map(|k| (k.alias, k))
+            .collect::<HashMap<_, _>>();
+        state.did_keys = snapshot["didKey"]
+            .iter()
+            .map(|d| DIDKey::from_entry_dict(d))
+            .map(|k| (k.alias, k))
+            .collect::<HashMap<_, _>>();
+        state.services = snapshot["service"]
+            .iter()
+            .map(|d| Service::from_entry_dict(d))
+            .map(|s| (s.alias, s))
+            .collect::<HashMap<_, _>>();
+        state.all_keys = set(
+            snapshot["allKeys"]["managementKey"]
+                .iter()
+                .map(|d| ManagementKey::from_entry_dict(d))
+                .chain(snapshot["allKeys"]["didKey"].iter().map(|d| DIDKey::from_entry_dict(d))),
+        );
+        state.method_version = snapshot["didMethodVersion"];
+        state.skipped_entries = snapshot["skippedEntries"];
+        state.processed_entry_hashes = set(snapshot["processedEntryHashes"]);
+        state.processed_entries = snapshot["processedEntries"];
+        state.keep_parsing = snapshot["keepParsing"];
+        state.last_entry_hash = snapshot["lastEntryHash"];
+        state.key_history = snapshot["keyHistory"].iter().map(dict).collect::<Vec<_>>();
+        state.deactivation = if snapshot["deactivation"] == None {
+            None
+        } else {
+            DeactivationMetadata::from_dict(snapshot["deactivation"])
+        };
+        return state;
+    }
+    fn to_json<RT>(&self) -> RT {
+        "
+        Returns
+        -------
+        str
+            The snapshot of the chain state, serialized as JSON
+        ";
+        return json.dumps(self.to_dict(), (",", ":"), true);
+    }
+    fn from_json<T0, RT>(snapshot_json: T0) -> RT {
+        "
+        Restores a chain state from a snapshot serialized with to_json.
+
+        Parameters
+        ----------
+        snapshot_json: str
+
+        Returns
+        -------
+        DIDChainState
+        ";
+        return DIDChainState::from_dict(json.loads(snapshot_json));
+    }
+    fn to_cbor<RT>(&self) -> RT {
+        "
+        Returns
+        -------
+        bytes
+            The snapshot of the chain state, serialized as DAG-CBOR. This is a more compact alternative to to_json
+            for bandwidth constrained clients.
+        ";
+        return dumps_cbor(self.to_dict(), true);
+    }
+    fn from_cbor<T0, RT>(snapshot_cbor: T0) -> RT {
+        "
+        Restores a chain state from a snapshot serialized with to_cbor.
+
+        Parameters
+        ----------
+        snapshot_cbor: bytes
+
+        Returns
+        -------
+        DIDChainState
+        ";
+        return DIDChainState::from_dict(loads_cbor(snapshot_cbor, true));
+    }
+    fn copy<RT>(&self) -> RT {
+        "
+        Returns
+        -------
+        DIDChainState
+            A deep copy of the chain state
+        ";
+        return DIDChainState::from_dict(self.to_dict());
+    }
+    fn to_did_state<T0, RT>(&self, did: T0) -> RT {
+        "
+        Parameters
+        ----------
+        did: str
+
+        Returns
+        -------
+        DIDState
+        ";
+        return DIDState(
+            did,
+            self.management_keys,
+            self.did_keys,
+            self.services,
+            self.skipped_entries,
+            self.deactivation,
+        );
+    }
+}
+fn _key_record<T0, T1, RT>(key: T0, key_kind: T1) -> RT {
+    let (public_key_field, public_key) = key.underlying.get_public_key_on_chain_repr();
+    return [
+        ("alias", key.alias),
+        ("keyKind", key_kind),
+        ("type", key.key_type.value),
+        ("publicKeyField", public_key_field),
+        ("publicKey", public_key),
+    ]
+    .iter()
+    .cloned()
+    .collect::<HashMap<_, _>>();
+}
+fn _key_identity<T0, RT>(record: T0) -> RT {
+    return (record["keyKind"], record["alias"], record["publicKey"]);
+}
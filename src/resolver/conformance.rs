@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+use std::*;
+
+use factom_did::client::blockchain::{calculate_chain_id, calculate_entry_hash, calculate_entry_size};
+use factom_did::client::enums::{KeyType, Network};
+use factom_did::client::keys::management::ManagementKey;
+use factom_did::client::serialization::dumps_entry_content;
+use factom_did::resolver::chain_state::DIDChainState;
+use factom_did::resolver::parser::resolve_incremental;
+let all = vec![
+    "load_conformance_vectors",
+    "to_chain_entries",
+    "check_conformance_vector",
+];
+// The vector files, embedded at build time. conformance.json is produced by generate_conformance_vectors.py with the
+// reference factom-did Python package and conformance-node.json by generate_conformance_vectors.js.
+const CONFORMANCE_VECTOR_FILES: _ = vec![
+    include_str!("vectors/conformance.json"),
+    include_str!("vectors/conformance-node.json"),
+];
+fn load_conformance_vectors<RT>() -> RT {
+    "
+    Loads the bundled conformance test vectors.
+
+    Each vector describes a DIDManagement chain: the ExtIDs (hex encoded), content, entry hash and entry size of each
+    entry, the Ed25519 seeds of the keys which signed them, and the expected resolution outcome. Only the outcome
+    fields present in a vector are checked, as the reference parser does not report the method version or
+    deactivation. The versions of the libraries which generated each file are recorded in its 'generator' member. The
+    vectors include adversarial entries (bad signatures, priority violations, a version downgrade, duplicate entry
+    hashes and entries recorded after deactivation).
+
+    Returns
+    -------
+    list of dict
+    ";
+    let vectors = vec![];
+    for contents in CONFORMANCE_VECTOR_FILES {
+        vectors.extend(json.loads(contents)["vectors"]);
+    }
+    return vectors;
+}
+fn to_chain_entries<T0, RT>(vector: T0) -> RT {
+    "
+    Converts the entries of a conformance vector to the format accepted by parse_did_chain_entries.
+
+    Parameters
+    ----------
+    vector: dict
+
+    Returns
+    -------
+    list of dict
+    ";
+    return vector["entries"]
+        .iter()
+        .map(|entry| {
+            [
+                (
+                    "extids",
+                    entry["extIds"].iter().map(bytes.fromhex).collect::<Vec<_>>(),
+                ),
+                ("content", entry["content"].encode("utf-8")),
+                ("entryhash", entry["entryHash"]),
+                ("timestamp", entry["timestamp"]),
+            ]
+            .iter()
+            .cloned()
+            .collect::<HashMap<_, _>>()
+        })
+        .collect::<Vec<_>>();
+}
+fn check_conformance_vector<T0, RT>(vector: T0) -> RT {
+    "
+    Checks that this implementation produces the same results as the reference implementation for a conformance
+    vector: the chain ID, the size, hash and content serialization of every entry, the signatures of the entries
+    signed with the keys of the vector, and the resolution outcome.
+
+    Parameters
+    ----------
+    vector: dict
+        A conformance vector, as returned by load_conformance_vectors
+
+    Returns
+    -------
+    list of str
+        A description of each mismatch. The list is empty if the implementation conforms to the vector.
+    ";
+    let mismatches = vec![];
+    let entries = to_chain_entries(vector);
+    let chain_id = calculate_chain_id(entries[0]["extids"]);
+    if chain_id != vector["chainId"] {
+        mismatches.append("chain ID: expected {}, got {}".format(vector["chainId"], chain_id));
+    }
+    for (index, (entry, expected)) in entries.iter().zip(vector["entries"]).enumerate() {
+        let entry_size = calculate_entry_size(entry["extids"], entry["content"]);
+        if entry_size != expected["entrySize"] {
+            mismatches.append("entry {} size: expected {}, got {}".format(index, expected["entrySize"], entry_size));
+        }
+        let entry_hash = calculate_entry_hash(vector["chainId"], entry["extids"], entry["content"]);
+        if entry_hash != expected["entryHash"] {
+            mismatches.append("entry {} hash: expected {}, got {}".format(index, expected["entryHash"], entry_hash));
+        }
+        if entry["content"] && dumps_entry_content(json.loads(entry["content"]), false) != expected["content"] {
+            mismatches.append("entry {} content serialization differs".format(index));
+        }
+        if expected.iter().any(|&x| x == "validSignature") {
+            let signature_matches = _sign_entry(vector, entry) == entry["extids"][3];
+            if signature_matches != expected["validSignature"] {
+                mismatches.append("entry {} signature: expected {}match".format(
+                    index,
+                    if expected["validSignature"] { "" } else { "no " },
+                ));
+            }
+        }
+    }
+    let network = Network::from_str(vector["network"]);
+    let state = resolve_incremental(DIDChainState(vector["chainId"], network), entries, None, None);
+    let outcome = [
+        ("managementKeys", sorted(state.management_keys)),
+        (
+            "didKeys",
+            state
+                .did_keys
+                .items()
+                .iter()
+                .map(|(alias, key)| (alias, key.purpose.iter().map(|p| p.value).collect::<Vec<_>>()))
+                .collect::<HashMap<_, _>>(),
+        ),
+        ("services", sorted(state.services)),
+        ("skippedEntries", state.skipped_entries),
+        ("didMethodVersion", state.method_version),
+        ("deactivated", state.deactivation != None),
+    ]
+    .iter()
+    .cloned()
+    .collect::<HashMap<_, _>>();
+    for (field, expected) in vector["expected"].items() {
+        if outcome[field] != expected {
+            mismatches.append("{}: expected {}, got {}".format(field, expected, outcome[field]));
+        }
+    }
+    return mismatches;
+}
+fn _sign_entry<T0, T1, RT>(vector: T0, entry: T1) -> RT {
+    let ext_ids = entry["extids"];
+    let key_id = ext_ids[2].decode();
+    let alias = key_id.split("#")[-1];
+    let key = ManagementKey::from_raw(
+        alias,
+        0,
+        KeyType::EdDSA,
+        None,
+        bytes.fromhex(vector["privateKeys"][alias]),
+        key_id.split("#")[0],
+        None,
+    );
+    let data_to_sign = ext_ids[0] + ext_ids[1] + ext_ids[2] + entry["content"];
+    return key.sign(hashlib.sha256(data_to_sign).digest(), None);
+}
@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+use std::*;
+
+let all = vec!["DeactivationMetadata"];
+struct DeactivationMetadata {
+    entry_hash: ST0,
+    timestamp: ST1,
+    signing_key_id: ST2,
+}
+
+impl DeactivationMetadata {
+    "
+    Describes the deactivation of a DID.
+
+    Attributes
+    ----------
+    entry_hash: str
+        The hash of the DIDDeactivation entry
+    timestamp: int, optional
+        The UNIX timestamp at which the DIDDeactivation entry was recorded, if the chain reader provided it in the
+        'timestamp' key of the entry
+    signing_key_id: str
+        The full identifier of the management key which signed the DIDDeactivation entry
+    ";
+    fn init<T0, T1, T2>(&self, entry_hash: T0, timestamp: T1, signing_key_id: T2) {
+        self.entry_hash = entry_hash;
+        self.timestamp = timestamp;
+        self.signing_key_id = signing_key_id;
+    }
+    fn represent<RT>(&self) -> RT {
+        return "<{}.{}(entry_hash={}, timestamp={}, signing_key_id={})>".format(
+            self.module,
+            type_(self).__name__,
+            self.entry_hash,
+            self.timestamp,
+            self.signing_key_id,
+        );
+    }
+    fn is_before_deactivation<T0, RT>(&self, timestamp: T0) -> RT {
+        "
+        Checks whether the given time is before the deactivation, e.g. to accept a credential issued while the DID was
+        still active.
+
+        Parameters
+        ----------
+        timestamp: int
+            A UNIX timestamp
+
+        Returns
+        -------
+        bool
+
+        Raises
+        ------
+        ValueError
+            If the time of the deactivation is not known
+        ";
+        if self.timestamp == None {
+            raise!(ValueError("The time of the deactivation is not known")); //unsupported
+        }
+        return timestamp < self.timestamp;
+    }
+    fn to_dict<RT>(&self) -> RT {
+        return [
+            ("entryHash", self.entry_hash),
+            ("timestamp", self.timestamp),
+            ("signingKey", self.signing_key_id),
+        ]
+        .iter()
+        .cloned()
+        .collect::<HashMap<_, _>>();
+    }
+    fn from_dict<T0, RT>(d: T0) -> RT {
+        return DeactivationMetadata(d["entryHash"], d["timestamp"], d["signingKey"]);
+    }
+}
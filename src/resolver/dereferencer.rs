@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use std::*;
+
+use factom::exceptions::FactomAPIError;
+use factom_did::client::validators::validate_did;
+use factom_did::resolver::document::{build_did_document, to_service_object, to_verification_method};
+use factom_did::resolver::exceptions::InvalidDIDChain;
+use urllib::parse::{parse_qs, unquote, urljoin, urlsplit};
+let all = vec!["dereference", "parse_did_url"];
+const DID_DOCUMENT_CONTENT_TYPE: _ = "application/did+json";
+const URI_LIST_CONTENT_TYPE: _ = "text/uri-list";
+const INVALID_DID_URL: _ = "invalidDidUrl";
+const NOT_FOUND: _ = "notFound";
+fn parse_did_url<T0, RT>(did_url: T0) -> RT {
+    "
+    Splits a DID URL into its components.
+
+    Parameters
+    ----------
+    did_url: str
+        The DID URL, e.g. did:factom:mainnet:<chain-id>?service=inbox&relativeRef=/path#fragment
+
+    Returns
+    -------
+    tuple
+        A 4-tuple containing the DID, the path, the query parameters (a dict of lists of str) and the fragment. The
+        path and the fragment are None if they are not present in the DID URL.
+
+    Raises
+    ------
+    ValueError
+        If the DID URL is not valid
+    ";
+    if !isinstance(did_url, str) {
+        raise!(ValueError("DID URL must be a string")); //unsupported
+    }
+    let parts = urlsplit(did_url);
+    if parts.scheme != "did" || parts.netloc {
+        raise!(ValueError("Invalid DID URL: {}".format(did_url))); //unsupported
+    }
+    let did_end = parts.path.find("/");
+    let (did, path) = if did_end == -1 {
+        ("did:{}".format(parts.path), None)
+    } else {
+        (
+            "did:{}".format(parts.path[..did_end]),
+            parts.path[did_end..],
+        )
+    };
+    validate_did(did);
+    let query = parse_qs(parts.query, true, true);
+    let fragment = if parts.fragment { unquote(parts.fragment) } else { None };
+    return (did, path, query, fragment);
+}
+fn dereference<T0, T1, RT>(did_url: T0, resolver: T1) -> RT {
+    "
+    Dereferences a DID URL, following the DID URL dereferencing algorithm of the DID Core specification.
+
+    The following DID URLs are supported:
+        * a plain DID, which is dereferenced to the DID Document
+        * a DID URL with a fragment, e.g. did:factom:<chain-id>#management-1, which is dereferenced to the
+          verification method or the service with the corresponding ID
+        * a DID URL with a 'service' query parameter and an optional 'relativeRef' query parameter, e.g.
+          did:factom:<chain-id>?service=inbox&relativeRef=/path, which is dereferenced to the endpoint URL of the
+          service, with the relative reference resolved against it as per RFC 3986. A fragment in the DID URL is
+          appended to the resulting URL.
+
+    Parameters
+    ----------
+    did_url: str
+        The DID URL to dereference
+    resolver: DIDResolver or CachingResolver
+        The resolver used to resolve the DID contained in the DID URL
+
+    Returns
+    -------
+    tuple
+        A 3-tuple containing the dereferencing metadata, the content stream and the content metadata. If
+        dereferencing fails, the content stream is None and the dereferencing metadata contains an 'error' key,
+        whose value is either 'invalidDidUrl' or 'notFound'.
+    ";
+    let try_dummy = {
+        //unsupported
+        let (did, path, query, fragment) = parse_did_url(did_url);
+    };
+    let except!(ValueError as e) = {
+        //unsupported
+        return _error(INVALID_DID_URL, e.args[0]);
+    };
+    if query.iter().any(|(k, v)| v.len() > 1) {
+        return _error(INVALID_DID_URL, "Query parameters must not be repeated");
+    }
+    if query.iter().any(|&x| x == "relativeRef") && query.iter().all(|&x| x != "service") {
+        return _error(
+            INVALID_DID_URL,
+            "The relativeRef parameter requires a service parameter",
+        );
+    }
+    if path != None {
+        return _error(NOT_FOUND, "DID URL paths are not supported by the Factom DID method");
+    }
+    let try_dummy = {
+        //unsupported
+        let (management_keys, did_keys, services, _) = resolver.resolve(did);
+    };
+    let except!((InvalidDIDChain, FactomAPIError)) = {
+        //unsupported
+        return _error(NOT_FOUND, "DID {} not found".format(did));
+    };
+    let options = getattr(resolver, "options", None);
+    let public_key_format = if options == None {
+        None
+    } else {
+        options.public_key_format
+    };
+    if query.iter().any(|&x| x == "service") {
+        let service = services.get(query["service"][0]);
+        if service == None {
+            return _error(NOT_FOUND, "Service {} not found".format(query["service"][0]));
+        }
+        let mut url = service.endpoint;
+        if query.iter().any(|&x| x == "relativeRef") {
+            url = urljoin(url, query["relativeRef"][0]);
+        }
+        if fragment != None {
+            url = "{}#{}".format(url.split("#")[0], fragment);
+        }
+        return _result(url, URI_LIST_CONTENT_TYPE);
+    }
+    if fragment == None {
+        return _result(
+            build_did_document(did, management_keys, did_keys, services, public_key_format),
+            DID_DOCUMENT_CONTENT_TYPE,
+        );
+    }
+    if management_keys.iter().any(|&x| x == fragment) {
+        return _result(
+            to_verification_method(management_keys[fragment], did, public_key_format),
+            DID_DOCUMENT_CONTENT_TYPE,
+        );
+    }
+    if did_keys.iter().any(|&x| x == fragment) {
+        return _result(
+            to_verification_method(did_keys[fragment], did, public_key_format),
+            DID_DOCUMENT_CONTENT_TYPE,
+        );
+    }
+    if services.iter().any(|&x| x == fragment) {
+        return _result(
+            to_service_object(services[fragment], did),
+            DID_DOCUMENT_CONTENT_TYPE,
+        );
+    }
+    return _error(NOT_FOUND, "{}#{} not found".format(did, fragment));
+}
+fn _result<T0, T1, RT>(content: T0, content_type: T1) -> RT {
+    return (
+        [("contentType", content_type)]
+            .iter()
+            .cloned()
+            .collect::<HashMap<_, _>>(),
+        content,
+        HashMap::new(),
+    );
+}
+fn _error<T0, T1, RT>(error: T0, message: T1) -> RT {
+    return (
+        [("error", error), ("errorMessage", message)]
+            .iter()
+            .cloned()
+            .collect::<HashMap<_, _>>(),
+        None,
+        HashMap::new(),
+    );
+}
@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+use std::*;
+
+let all = vec!["DIDDiff", "diff"];
+// The fields of the on-chain representation of a key which hold its public key
+const PUBLIC_KEY_FIELDS: _ = ("publicKeyBase58", "publicKeyPem");
+struct DIDDiff {
+    did: ST9,
+    added_management_keys: ST0,
+    revoked_management_keys: ST1,
+    changed_management_keys: ST2,
+    added_did_keys: ST3,
+    revoked_did_keys: ST4,
+    changed_did_keys: ST5,
+    added_purposes: HashMap<_, _>,
+    revoked_purposes: HashMap<_, _>,
+    added_services: ST6,
+    revoked_services: ST7,
+    changed_services: ST8,
+}
+
+impl DIDDiff {
+    "
+    The difference between two resolved states of a DID.
+
+    Keys and services are matched by alias and compared on their on-chain representation only, so that a state built
+    from an in-memory DID (which includes private keys) can be compared with a resolved one. A key or service present
+    in both states under the same alias, but with different on-chain properties (e.g. a rotated key), is reported as
+    changed, with all its changes, including any change of purpose. A DID key whose only difference is its purpose is
+    reported in added_purposes and revoked_purposes instead.
+
+    Attributes
+    ----------
+    did: str
+        The DID whose states are compared
+    added_management_keys: ManagementKey[]
+    revoked_management_keys: ManagementKey[]
+    changed_management_keys: list of tuple
+        (old, new) pairs of management keys
+    added_did_keys: DIDKey[]
+    revoked_did_keys: DIDKey[]
+    changed_did_keys: list of tuple
+        (old, new) pairs of DID keys
+    added_purposes: dict
+        DIDKeyPurpose[] added to a DID key, keyed by alias
+    revoked_purposes: dict
+        DIDKeyPurpose[] revoked from a DID key, keyed by alias
+    added_services: Service[]
+    revoked_services: Service[]
+    changed_services: list of tuple
+        (old, new) pairs of services
+    ";
+    fn init<T0>(&self, did: T0) {
+        self.did = did;
+        self.added_management_keys = vec![];
+        self.revoked_management_keys = vec![];
+        self.changed_management_keys = vec![];
+        self.added_did_keys = vec![];
+        self.revoked_did_keys = vec![];
+        self.changed_did_keys = vec![];
+        self.added_purposes = HashMap::new();
+        self.revoked_purposes = HashMap::new();
+        self.added_services = vec![];
+        self.revoked_services = vec![];
+        self.changed_services = vec![];
+    }
+    fn is_empty<RT>(&self) -> RT {
+        return !self.added_management_keys
+            && !self.revoked_management_keys
+            && !self.changed_management_keys
+            && !self.added_did_keys
+            && !self.revoked_did_keys
+            && !self.changed_did_keys
+            && !self.added_purposes
+            && !self.revoked_purposes
+            && !self.added_services
+            && !self.revoked_services
+            && !self.changed_services;
+    }
+    fn to_dict<T0, RT>(&self, did: T0) -> RT {
+        "
+        Converts the diff to a JSON serializable dictionary. Keys and services are represented in their on-chain
+        format.
+
+        Parameters
+        ----------
+        did: str
+            The DID with which the keys and services are associated
+
+        Returns
+        -------
+        dict
+        ";
+        fn section<T0, T1, T2, RT>(added: T0, revoked: T1, changed: T2) -> RT {
+            return [
+                ("added", added.iter().map(|x| x.to_entry_dict(did)).collect::<Vec<_>>()),
+                ("revoked", revoked.iter().map(|x| x.to_entry_dict(did)).collect::<Vec<_>>()),
+                (
+                    "changed",
+                    changed
+                        .iter()
+                        .map(|(old, new)| {
+                            [("old", old.to_entry_dict(did)), ("new", new.to_entry_dict(did))]
+                                .iter()
+                                .cloned()
+                                .collect::<HashMap<_, _>>()
+                        })
+                        .collect::<Vec<_>>(),
+                ),
+            ]
+            .iter()
+            .cloned()
+            .collect::<HashMap<_, _>>();
+        }
+        let did_keys = section(self.added_did_keys, self.revoked_did_keys, self.changed_did_keys);
+        did_keys["addedPurposes"] = self
+            .added_purposes
+            .items()
+            .iter()
+            .map(|(alias, purposes)| (alias, purposes.iter().map(|p| p.value).collect::<Vec<_>>()))
+            .collect::<HashMap<_, _>>();
+        did_keys["revokedPurposes"] = self
+            .revoked_purposes
+            .items()
+            .iter()
+            .map(|(alias, purposes)| (alias, purposes.iter().map(|p| p.value).collect::<Vec<_>>()))
+            .collect::<HashMap<_, _>>();
+        return [
+            (
+                "managementKey",
+                section(
+                    self.added_management_keys,
+                    self.revoked_management_keys,
+                    self.changed_management_keys,
+                ),
+            ),
+            ("didKey", did_keys),
+            (
+                "service",
+                section(self.added_services, self.revoked_services, self.changed_services),
+            ),
+        ]
+        .iter()
+        .cloned()
+        .collect::<HashMap<_, _>>();
+    }
+    fn to_json<T0, RT>(&self, did: T0) -> RT {
+        "
+        Renders the diff as JSON.
+
+        Parameters
+        ----------
+        did: str
+            The DID with which the keys and services are associated
+
+        Returns
+        -------
+        str
+        ";
+        return json.dumps(self.to_dict(did), true);
+    }
+    fn to_text<RT>(&self) -> RT {
+        "
+        Renders the diff as human-readable text, one change per line.
+
+        Returns
+        -------
+        str
+        ";
+        let lines = vec![];
+        for key in self.added_management_keys {
+            lines.push("+ management key {} (priority {}, {})".format(key.alias, key.priority, key.key_type.value));
+        }
+        for key in self.revoked_management_keys {
+            lines.push("- management key {} (priority {}, {})".format(key.alias, key.priority, key.key_type.value));
+        }
+        for (old, new) in self.changed_management_keys {
+            lines.push("~ management key {}: {}".format(old.alias, _describe_changes(old, new, self.did)));
+        }
+        for key in self.added_did_keys {
+            lines.push("+ DID key {} ({})".format(key.alias, ", ".join(key.purpose.iter().map(|p| p.value))));
+        }
+        for key in self.revoked_did_keys {
+            lines.push("- DID key {} ({})".format(key.alias, ", ".join(key.purpose.iter().map(|p| p.value))));
+        }
+        for (old, new) in self.changed_did_keys {
+            lines.push("~ DID key {}: {}".format(old.alias, _describe_changes(old, new, self.did)));
+        }
+        for (alias, purposes) in self.added_purposes.items() {
+            lines.push("+ DID key {} purpose {}".format(alias, ", ".join(purposes.iter().map(|p| p.value))));
+        }
+        for (alias, purposes) in self.revoked_purposes.items() {
+            lines.push("- DID key {} purpose {}".format(alias, ", ".join(purposes.iter().map(|p| p.value))));
+        }
+        for service in self.added_services {
+            lines.push("+ service {} ({}: {})".format(service.alias, service.service_type, service.endpoint));
+        }
+        for service in self.revoked_services {
+            lines.push("- service {} ({}: {})".format(service.alias, service.service_type, service.endpoint));
+        }
+        for (old, new) in self.changed_services {
+            lines.push("~ service {}: {}".format(old.alias, _describe_changes(old, new, self.did)));
+        }
+        if !lines {
+            return "No changes";
+        }
+        return "\n".join(lines);
+    }
+}
+fn diff<T0, T1, RT>(old: T0, new: T1) -> RT {
+    "
+    Computes the difference between two resolved states of a DID.
+
+    Parameters
+    ----------
+    old: DIDState
+        The earlier state
+    new: DIDState
+        The later state
+
+    Returns
+    -------
+    DIDDiff
+    ";
+    let did = new.did;
+    let result = DIDDiff(did);
+    let (added, revoked, changed) = _diff_by_alias(old.management_keys, new.management_keys, did);
+    result.added_management_keys = added;
+    result.revoked_management_keys = revoked;
+    result.changed_management_keys = changed;
+    let (added, revoked, changed) = _diff_by_alias(old.did_keys, new.did_keys, did);
+    result.added_did_keys = added;
+    result.revoked_did_keys = revoked;
+    for (old_key, new_key) in changed {
+        if _without_purpose(old_key, did) != _without_purpose(new_key, did) {
+            result.changed_did_keys.push((old_key, new_key));
+            continue;
+        }
+        let added_purposes = new_key.purpose.iter().filter(|p| old_key.purpose.iter().all(|&x| x != p)).collect::<Vec<_>>();
+        let revoked_purposes = old_key.purpose.iter().filter(|p| new_key.purpose.iter().all(|&x| x != p)).collect::<Vec<_>>();
+        if added_purposes {
+            result.added_purposes[new_key.alias] = added_purposes;
+        }
+        if revoked_purposes {
+            result.revoked_purposes[new_key.alias] = revoked_purposes;
+        }
+    }
+    let (added, revoked, changed) = _diff_by_alias(old.services, new.services, did);
+    result.added_services = added;
+    result.revoked_services = revoked;
+    result.changed_services = changed;
+    return result;
+}
+fn _diff_by_alias<T0, T1, T2, RT>(old: T0, new: T1, did: T2) -> RT {
+    let added = sorted(new.keys())
+        .iter()
+        .filter(|alias| old.iter().all(|&x| x != alias))
+        .map(|alias| new[alias])
+        .collect::<Vec<_>>();
+    let revoked = sorted(old.keys())
+        .iter()
+        .filter(|alias| new.iter().all(|&x| x != alias))
+        .map(|alias| old[alias])
+        .collect::<Vec<_>>();
+    let changed = sorted(old.keys())
+        .iter()
+        .filter(|alias| new.iter().any(|&x| x == alias) && old[alias].to_entry_dict(did) != new[alias].to_entry_dict(did))
+        .map(|alias| (old[alias], new[alias]))
+        .collect::<Vec<_>>();
+    return (added, revoked, changed);
+}
+fn _without_purpose<T0, T1, RT>(did_key: T0, did: T1) -> RT {
+    let entry_dict = did_key.to_entry_dict(did);
+    entry_dict.pop("purpose", None);
+    return entry_dict;
+}
+fn _describe_changes<T0, T1, T2, RT>(old: T0, new: T1, did: T2) -> RT {
+    let old_dict = old.to_entry_dict(did);
+    let new_dict = new.to_entry_dict(did);
+    let changes = vec![];
+    if PUBLIC_KEY_FIELDS.iter().any(|f| old_dict.get(f) != new_dict.get(f)) {
+        changes.push("key rotated");
+    }
+    for field in sorted(set(old_dict.keys()) | set(new_dict.keys())) {
+        if PUBLIC_KEY_FIELDS.iter().any(|&x| x == field) || old_dict.get(field) == new_dict.get(field) {
+            continue;
+        }
+        changes.push("{} {} -> {}".format(field, old_dict.get(field), new_dict.get(field)));
+    }
+    return ", ".join(changes);
+}
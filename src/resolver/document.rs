@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::*;
+
+use factom_did::client::enums::DIDKeyPurpose;
+let all = vec!["build_did_document", "to_verification_method", "to_service_object"];
+fn build_did_document<T0, T1, T2, T3, T4, RT>(
+    did: T0,
+    management_keys: T1,
+    did_keys: T2,
+    services: T3,
+    public_key_format: T4,
+) -> RT {
+    "
+    Builds a W3C DID Document from the active management keys, DID keys and services of a DID.
+
+    All keys are listed as verification methods. DID keys are referenced from the 'authentication' and
+    'assertionMethod' verification relationships according to their purposes.
+
+    Parameters
+    ----------
+    did: str
+        The DID
+    management_keys: dict
+        The active management keys, keyed by alias
+    did_keys: dict
+        The active DID keys, keyed by alias
+    services: dict
+        The active services, keyed by alias
+    public_key_format: PublicKeyFormat, optional
+        The representation of the public keys. Defaults to the one used on-chain.
+
+    Returns
+    -------
+    dict
+        The DID Document
+    ";
+    let did_document = [
+        ("id", did),
+        (
+            "verificationMethod",
+            sorted(management_keys.values(), op.attrgetter("priority"))
+                .iter()
+                .chain(sorted(did_keys.values(), op.attrgetter("alias")).iter())
+                .map(|k| to_verification_method(k, did, public_key_format))
+                .collect::<Vec<_>>(),
+        ),
+    ]
+    .iter()
+    .cloned()
+    .collect::<HashMap<_, _>>();
+    let authentication = sorted(did_keys.values(), op.attrgetter("alias"))
+        .iter()
+        .filter(|k| k.purpose.iter().any(|&x| x == DIDKeyPurpose::AuthenticationKey))
+        .map(|k| k.full_id(did))
+        .collect::<Vec<_>>();
+    if authentication {
+        did_document["authentication"] = authentication;
+    }
+    let assertion_method = sorted(did_keys.values(), op.attrgetter("alias"))
+        .iter()
+        .filter(|k| k.purpose.iter().any(|&x| x == DIDKeyPurpose::PublicKey))
+        .map(|k| k.full_id(did))
+        .collect::<Vec<_>>();
+    if assertion_method {
+        did_document["assertionMethod"] = assertion_method;
+    }
+    if services {
+        did_document["service"] = sorted(services.values(), op.attrgetter("alias"))
+            .iter()
+            .map(|s| to_service_object(s, did))
+            .collect::<Vec<_>>();
+    }
+    return did_document;
+}
+fn to_verification_method<T0, T1, T2, RT>(key: T0, did: T1, public_key_format: T2) -> RT {
+    "
+    Converts a management or DID key to a W3C verification method.
+
+    Parameters
+    ----------
+    key: AbstractDIDKey
+    did: str
+    public_key_format: PublicKeyFormat, optional
+        The representation of the public key. Defaults to the one used on-chain.
+
+    Returns
+    -------
+    dict
+        Dictionary with `id`, `type`, `controller` and the public key field (`publicKeyBase58` or `publicKeyPem` by
+        default, otherwise `publicKeyJwk` or `publicKeyMultibase`)
+    ";
+    let (public_key_field, public_key_value) = key.get_public_key_repr(public_key_format);
+    return [
+        ("id", key.full_id(did)),
+        ("type", key.key_type.value),
+        ("controller", key.controller),
+        (public_key_field, public_key_value),
+    ]
+    .iter()
+    .cloned()
+    .collect::<HashMap<_, _>>();
+}
+fn to_service_object<T0, T1, RT>(service: T0, did: T1) -> RT {
+    "
+    Converts a service to a W3C service object, including its custom fields.
+
+    Parameters
+    ----------
+    service: Service
+    did: str
+
+    Returns
+    -------
+    dict
+    ";
+    let d = service.to_entry_dict(did);
+    d.pop("priorityRequirement", None);
+    return d;
+}
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::*;
+
+use factom::exceptions::FactomAPIError;
+use factom_did::client::cbor::dumps_cbor;
+use factom_did::client::validators::validate_did;
+use factom_did::resolver::document::build_did_document;
+use factom_did::resolver::exceptions::{is_missing_chain_error, InvalidDIDChain, InvalidDIDChainEntry};
+use factom_did::resolver::jsonld::to_json_ld;
+use urllib::parse::unquote;
+let all = vec!["UniversalResolverDriver"];
+const DID_RESOLUTION_CONTEXT: _ = "https://w3id.org/did-resolution/v1";
+const DID_JSON: _ = "application/did+json";
+const DID_LD_JSON: _ = "application/did+ld+json";
+const DID_CBOR: _ = "application/did+cbor";
+const LD_JSON: _ = "application/ld+json";
+const DID_RESOLUTION_PROFILE: _ = "https://w3id.org/did-resolution";
+const DID_RESOLUTION_RESULT: _ = "application/ld+json;profile=\"https://w3id.org/did-resolution\"";
+const IDENTIFIERS_PATH: _ = "/1.0/identifiers/";
+const HEALTH_PATH: _ = "/health";
+struct UniversalResolverDriver {
+    resolver: ST0,
+}
+
+impl UniversalResolverDriver {
+    "
+    Implements the HTTP interface of a DIF Universal Resolver driver for the Factom DID method.
+
+    The driver is transport agnostic: handle_request maps a request path and Accept header to an HTTP status code, a
+    content type and a response body, and is used by the HTTP server in the server module. The body is a str, except
+    for application/did+cbor responses, whose body is bytes.
+
+    The representation is negotiated from the Accept header: the supported media type with the highest quality value
+    is used, and a DID Resolution Result is returned for wildcards or when there is no Accept header. A plain
+    application/ld+json request yields the JSON-LD DID Document.
+
+    Resolution errors are reported as DID Resolution Results: invalidDid (400) for a malformed DID, notFound (404)
+    when the DIDManagement chain does not exist or is not a valid DID chain, and internalError when factomd cannot be
+    queried (503) or when resolving in strict mode and the chain contains an invalid entry (500).
+
+    Attributes
+    ----------
+    resolver: DIDResolver or CachingResolver
+        The resolver used to resolve the DIDs
+    ";
+    fn init<T0>(&self, resolver: T0) {
+        self.resolver = resolver;
+    }
+    fn handle_request<T0, T1, RT>(&self, path: T0, accept: T1) -> RT {
+        "
+        Handles a GET request.
+
+        Parameters
+        ----------
+        path: str
+            The request path, either /1.0/identifiers/{did} or /health
+        accept: str, optional
+            The value of the Accept header of the request
+
+        Returns
+        -------
+        tuple
+            A 3-tuple containing the HTTP status code, the content type and the response body (str)
+        ";
+        if path == HEALTH_PATH {
+            return (200, "application/json", json.dumps([("status", "ok")].iter().cloned().collect::<HashMap<_, _>>()));
+        }
+        if !path.startswith(IDENTIFIERS_PATH) {
+            return (404, "text/plain", "Not found");
+        }
+        let did = unquote(path[IDENTIFIERS_PATH.len()..]);
+        let content_type = self._negotiate(accept);
+        if content_type == None {
+            return self._resolution_error(406, "representationNotSupported", DID_RESOLUTION_RESULT);
+        }
+        let try_dummy = {
+            //unsupported
+            validate_did(did);
+        };
+        let except!(ValueError) = {
+            //unsupported
+            return self._resolution_error(400, "invalidDid", content_type);
+        };
+        let try_dummy = {
+            //unsupported
+            let state = self._resolve_chain_state(did);
+        };
+        let except!(InvalidDIDChain) = {
+            //unsupported
+            return self._resolution_error(404, "notFound", content_type);
+        };
+        let except!(FactomAPIError as e) = {
+            //unsupported
+            if is_missing_chain_error(e) {
+                return self._resolution_error(404, "notFound", content_type);
+            }
+            return self._resolution_error(503, "internalError", content_type);
+        };
+        let except!(RuntimeError) = {
+            //unsupported
+            return self._resolution_error(503, "internalError", content_type);
+        };
+        let except!(InvalidDIDChainEntry) = {
+            //unsupported
+            return self._resolution_error(500, "internalError", content_type);
+        };
+        let deactivated = state.deactivation != None;
+        let did_document = build_did_document(
+            did,
+            state.management_keys,
+            state.did_keys,
+            state.services,
+            self._public_key_format(),
+        );
+        let did_document_metadata = if deactivated {
+            [
+                ("deactivated", true),
+                ("deactivationEntryHash", state.deactivation.entry_hash),
+                ("deactivationTimestamp", state.deactivation.timestamp),
+                ("deactivationSigningKey", state.deactivation.signing_key_id),
+            ]
+            .iter()
+            .cloned()
+            .collect::<HashMap<_, _>>()
+        } else {
+            HashMap::new()
+        };
+        let status = if deactivated { 410 } else { 200 };
+        if content_type == DID_RESOLUTION_RESULT {
+            return (
+                status,
+                content_type,
+                json.dumps(
+                    [
+                        ("@context", DID_RESOLUTION_CONTEXT),
+                        ("didDocument", to_json_ld(did_document, None)),
+                        (
+                            "didResolutionMetadata",
+                            [("contentType", DID_LD_JSON)].iter().cloned().collect::<HashMap<_, _>>(),
+                        ),
+                        ("didDocumentMetadata", did_document_metadata),
+                    ]
+                    .iter()
+                    .cloned()
+                    .collect::<HashMap<_, _>>(),
+                ),
+            );
+        }
+        if content_type == DID_LD_JSON || content_type == LD_JSON {
+            did_document = to_json_ld(did_document, None);
+        }
+        if content_type == DID_CBOR {
+            return (status, content_type, dumps_cbor(did_document, false));
+        }
+        return (status, content_type, json.dumps(did_document));
+    }
+    fn _public_key_format<RT>(&self) -> RT {
+        let options = getattr(self.resolver, "options", None);
+        return if options == None {
+            None
+        } else {
+            options.public_key_format
+        };
+    }
+    fn _resolve_chain_state<T0, RT>(&self, did: T0) -> RT {
+        if hasattr(self.resolver, "resolve_chain_state") {
+            return self.resolver.resolve_chain_state(did);
+        }
+        return self.resolver.resolve_incremental(did, None);
+    }
+    fn _negotiate<T0, RT>(accept: T0) -> RT {
+        if !accept {
+            return DID_RESOLUTION_RESULT;
+        }
+        let mut best = None;
+        let mut best_rank = (0, 0);
+        for media_range in accept.split(",") {
+            let parts = media_range.split(";").iter().map(|p| p.strip()).collect::<Vec<_>>();
+            let media_type = parts[0].lower();
+            let parameters = parts[1..]
+                .iter()
+                .filter(|p| p.iter().any(|&x| x == "="))
+                .map(|p| p.split("=", 1))
+                .map(|(k, v)| (k.strip().lower(), v.strip().strip("\"")))
+                .collect::<HashMap<_, _>>();
+            let quality = _parse_quality(parameters.pop("q", "1"));
+            let representation = _representation(media_type, parameters);
+            // At equal quality, a specific media type is preferred over a wildcard
+            let rank = (quality, if media_type.endswith("/*") { 0 } else { 1 });
+            if representation != None && quality > 0 && rank > best_rank {
+                best = representation;
+                best_rank = rank;
+            }
+        }
+        return best;
+    }
+    fn _resolution_error<T0, T1, T2, RT>(status: T0, error: T1, content_type: T2) -> RT {
+        return (
+            status,
+            DID_RESOLUTION_RESULT,
+            json.dumps(
+                [
+                    ("@context", DID_RESOLUTION_CONTEXT),
+                    ("didDocument", None),
+                    (
+                        "didResolutionMetadata",
+                        [("error", error), ("contentType", content_type)]
+                            .iter()
+                            .cloned()
+                            .collect::<HashMap<_, _>>(),
+                    ),
+                    ("didDocumentMetadata", HashMap::new()),
+                ]
+                .iter()
+                .cloned()
+                .collect::<HashMap<_, _>>(),
+            ),
+        );
+    }
+}
+fn _representation<T0, T1, RT>(media_type: T0, parameters: T1) -> RT {
+    if (DID_JSON, DID_LD_JSON, DID_CBOR).iter().any(|&x| x == media_type) {
+        return media_type;
+    }
+    if media_type == LD_JSON {
+        let profile = parameters.get("profile");
+        if profile == None {
+            return LD_JSON;
+        }
+        return if profile.split().iter().any(|&x| x == DID_RESOLUTION_PROFILE) {
+            DID_RESOLUTION_RESULT
+        } else {
+            None
+        };
+    }
+    if ("*/*", "application/*").iter().any(|&x| x == media_type) {
+        return DID_RESOLUTION_RESULT;
+    }
+    return None;
+}
+fn _parse_quality<T0, RT>(value: T0) -> RT {
+    let try_dummy = {
+        //unsupported
+        let quality = float(value);
+    };
+    let except!(ValueError) = {
+        //unsupported
+        return 0;
+    };
+    return if 0 <= quality && quality <= 1 { quality } else { 0 };
+}
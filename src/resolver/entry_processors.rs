@@ -3,29 +3,19 @@ use std::collections::HashMap;
 
 "Helper functions for parser.py which are used to update the currently active management and DID keys,
 and services.";
-use packaging::{version};
-use factom_did::client::constants::{DID_METHOD_SPEC_V020};
-use factom_did::client::enums::{DIDKeyPurpose, Network};
+use factom_did::client::enums::{DIDKeyPurpose, EntryType, Network};
 use factom_did::client::keys::did::{DIDKey};
 use factom_did::client::keys::management::{ManagementKey};
 use factom_did::client::service::{Service};
+use factom_did::resolver::deactivation::{DeactivationMetadata};
 use factom_did::resolver::exceptions::{MalformedDIDManagementEntry};
-use factom_did::resolver::validators::{validate_management_key_id_against_chain_id, validate_id_against_network, validate_signature};
-fn _is_method_version_upgrade<T0, T1, RT>(current_version: T0, new_version: T1) -> RT {
-"
-    Checks if the new version is an upgrade over the current version
-
-    Parameters
-    ----------
-    current_version: str
-    new_version: str
-
-    Returns
-    -------
-    bool
-    ";
-return version::parse(current_version) < version::parse(new_version);
-}
+use factom_did::resolver::method_versions::{is_method_version_upgrade, DID_METHOD_SPEC_V020_RULES};
+use factom_did::resolver::registry::{EntryProcessor};
+use factom_did::resolver::schema::{get_schema_validator};
+use factom_did::resolver::validators::{validate_management_key_id_against_chain_id, validate_id_against_network, validate_signature, validate_did_management_ext_ids_v100, validate_did_update_ext_ids_v100, validate_did_method_version_upgrade_ext_ids_v100, validate_did_deactivation_ext_ids_v100, EmptyEntryContentValidator};
+const DID_MANAGEMENT_SCHEMA: _ = "did_management_entry.json";
+const DID_UPDATE_SCHEMA: _ = "did_update_entry.json";
+const DID_METHOD_VERSION_UPGRADE_SCHEMA: _ = "did_method_version_upgrade_entry.json";
 fn _get_alias<T0, RT>(full_or_partial_id: T0) -> RT {
 "
     Returns the alias from a full or partial id
@@ -38,7 +28,7 @@ fn _get_alias<T0, RT>(full_or_partial_id: T0) -> RT {
     -------
     str
     ";
-return full_or_partial_id.split("#")[-1];
+return DID_METHOD_SPEC_V020_RULES.alias_from_id(full_or_partial_id);
 }
 fn exists_management_key_with_priority_zero<T0, T1, T2, RT>(active_management_keys: T0, new_management_keys: T1, management_keys_to_revoke: T2) -> RT {
 "
@@ -65,7 +55,7 @@ orig_management_keys[alias].drop();
 orig_management_keys.update(new_management_keys);
 return orig_management_keys.values().iter().map(|key| key.priority).iter().min().unwrap() == 0;
 }
-fn process_did_management_entry_v100<T0, T1, T2, T3, T4, T5, T6, RT>(chain_id: T0, parsed_content: T1, management_keys: T2, did_keys: T3, services: T4, skipped_entries: T5, network: T6) -> RT {
+fn process_did_management_entry_v100<T0, T1>(parsed_content: T0, state: T1)  {
 "
     Extracts the management keys, DID keys and services from a DIDManagement entry.
 
@@ -75,27 +65,11 @@ fn process_did_management_entry_v100<T0, T1, T2, T3, T4, T5, T6, RT>(chain_id: T
 
     Parameters
     ----------
-    chain_id: str
-        The DIDManagement chain ID.
     parsed_content: dict
         The parsed DIDManagement entry.
-    management_keys: dict
-        Will be updated to contain the management keys found in the entry.
-    did_keys: dict
-        Will be updated to contain the DID keys found in the entry.
-    services: dict
-        Will be updated to contain the services found in the entry.
-    skipped_entries: int
-        Will be incremented by one in case the DIDManagement entry is not valid.
-    network: Network
-        The Factom network on which the DID is recorded
-
-    Returns
-    -------
-    tuple
-        3-tuple (bool, str, int). The first element signifies if the caller should continue parsing the chain; the
-        second element contains the current DID method specification version; the third element contains the number
-        of skipped entries in the DIDManagement chain.
+    state: DIDChainState
+        The chain state. Will be updated to contain the management keys, DID keys and services found in the entry, and
+        the DID method specification version.
 
     Raises
     ------
@@ -105,7 +79,8 @@ fn process_did_management_entry_v100<T0, T1, T2, T3, T4, T5, T6, RT>(chain_id: T
 let new_management_keys = HashMap::new();
 let new_did_keys = HashMap::new();
 let new_services = HashMap::new();
-let method_version = parsed_content["didMethodVersion"];
+let chain_id = state.chain_id;
+let network = state.network;
 let mut found_key_with_priority_zero = false;
 for key_data in parsed_content["managementKey"] {
 if !validate_management_key_id_against_chain_id(key_data["id"], chain_id) {
@@ -146,12 +121,12 @@ raise!(MalformedDIDManagementEntry("Duplicate service found")); //unsupported
 }
 new_services[alias] = Service::from_entry_dict(service_data);
 }
-management_keys.update(new_management_keys);
-did_keys.update(new_did_keys);
-services.update(new_services);
-return (true, method_version, skipped_entries);
+state.management_keys.update(new_management_keys);
+state.did_keys.update(new_did_keys);
+state.services.update(new_services);
+state.method_version = parsed_content["didMethodVersion"];
 }
-fn process_did_update_entry_v100<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, RT>(chain_id: T0, ext_ids: T1, binary_content: T2, parsed_content: T3, method_version: T4, active_management_keys: T5, active_did_keys: T6, active_services: T7, skipped_entries: T8, all_keys: T9, network: T10) -> RT {
+fn process_did_update_entry_v100<T0, T1, T2, T3, T4>(ext_ids: T0, binary_content: T1, parsed_content: T2, state: T3, registry: T4)  {
 "
     Updates the management keys, DID keys and services based on the contents of the entry.
 
@@ -161,35 +136,17 @@ fn process_did_update_entry_v100<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, RT
 
     Parameters
     ----------
-    chain_id: str
-        The DIDManagement chain ID.
     ext_ids: list
         The ExtIDs of the entry, as bytes.
     binary_content: bytes
         The raw entry content.
     parsed_content: dict
         The parsed DIDUpdate entry.
-    method_version: str
-        The current DID method spec version.
-    active_management_keys: dict
-        The currently active management keys. Will be updated to contain the management keys found in the entry.
-    active_did_keys: dict
-        The currently active DID keys. Will be updated to contain the DID keys found in the entry.
-    active_services: dict
-        The currently active services. Will be updated to contain the services found in the entry.
-    skipped_entries: int
-        The current number of skipped entries. Will be incremented by one in case the DIDManagement entry is not valid.
-    all_keys: set
-        The set of all management and DID keys that have been active at some point for the current DIDManagement chain.
-    network: Network
-        The Factom network on which the DID is recorded
-
-    Returns
-    -------
-    tuple
-        3-tuple (bool, str, int). The first element signifies if the caller should continue parsing the chain; the
-        second element contains the current DID method specification version; the third element contains the number
-        of skipped entries in the DIDManagement chain.
+    state: DIDChainState
+        The chain state. The active management keys, DID keys and services will be updated according to the entry. The
+        number of skipped entries will be incremented by one in case the entry is not valid.
+    registry: EntryProcessorRegistry
+        The registry providing the rules of the DID method version.
     ";
 let management_keys_to_revoke = set();
 let did_keys_to_revoke = set();
@@ -198,50 +155,67 @@ let services_to_revoke = set();
 let new_management_keys = HashMap::new();
 let new_did_keys = HashMap::new();
 let new_services = HashMap::new();
-if method_version == DID_METHOD_SPEC_V020 {
+let chain_id = state.chain_id;
+let network = state.network;
+let all_keys = state.all_keys;
+let active_management_keys = state.management_keys;
+let active_did_keys = state.did_keys;
+let active_services = state.services;
+let rules = registry.get_method_version_rules(state.method_version);
+if rules != None&&rules.allows_entry_type(EntryType::Update) {
 let key_id = ext_ids[2].decode();
-let signing_key = active_management_keys.get(_get_alias(key_id));
-if !signing_key||!validate_signature(ext_ids, binary_content, signing_key) {
-return (true, method_version, (skipped_entries + 1));
+let signing_key = active_management_keys.get(rules.alias_from_id(key_id));
+if !signing_key||!rules.allows_signing_key(signing_key)||!validate_signature(ext_ids, binary_content, signing_key, rules.signature_digest) {
+state.skip_entry("Invalid signature or unknown signing key");
+return;
 }
 let signing_key_required_priority = math.inf;
 if parsed_content.iter().any(|&x| x == "revoke") {
 let (skip_entry, signing_key_required_priority) = _process_management_key_revocations(parsed_content, signing_key_required_priority, management_keys_to_revoke, active_management_keys, chain_id, network);
 if skip_entry {
-return (true, method_version, (skipped_entries + 1));
+state.skip_entry("Invalid management key revocation");
+return;
 }
 let (skip_entry, signing_key_required_priority) = _process_did_key_revocations(parsed_content, signing_key_required_priority, did_keys_to_revoke, did_key_purposes_to_revoke, active_did_keys, network);
 if skip_entry {
-return (true, method_version, (skipped_entries + 1));
+state.skip_entry("Invalid DID key revocation");
+return;
 }
 let (skip_entry, signing_key_required_priority) = _process_service_revocations(parsed_content, signing_key_required_priority, services_to_revoke, active_services, network);
 if skip_entry {
-return (true, method_version, (skipped_entries + 1));
+state.skip_entry("Invalid service revocation");
+return;
 }
 }
 if parsed_content.iter().any(|&x| x == "add") {
 let (skip_entry, signing_key_required_priority) = _process_management_key_additions(parsed_content, signing_key_required_priority, new_management_keys, active_management_keys, all_keys, chain_id, network);
 if skip_entry {
-return (true, method_version, (skipped_entries + 1));
+state.skip_entry("Invalid management key addition");
+return;
 }
 let (skip_entry, signing_key_required_priority) = _process_did_key_additions(parsed_content, signing_key_required_priority, new_did_keys, active_did_keys, all_keys, network);
 if skip_entry {
-return (true, method_version, (skipped_entries + 1));
+state.skip_entry("Invalid DID key addition");
+return;
 }
 let mut skip_entry = _process_service_additions(parsed_content, new_services, active_services, network);
 if skip_entry {
-return (true, method_version, (skipped_entries + 1));
+state.skip_entry("Invalid service addition");
+return;
 }
 }
 if signing_key.priority > signing_key_required_priority {
-return (true, method_version, (skipped_entries + 1));
+state.skip_entry("Signing key priority is insufficient");
+return;
 }
 if !exists_management_key_with_priority_zero(active_management_keys, new_management_keys, management_keys_to_revoke) {
-return (true, method_version, (skipped_entries + 1));
+state.skip_entry("No management key with priority 0 would remain active");
+return;
 }
 let mut skip_entry = _apply_self_revocation_rules(signing_key, new_management_keys, management_keys_to_revoke);
 if skip_entry {
-return (true, method_version, (skipped_entries + 1));
+state.skip_entry("Invalid management key self-revocation");
+return;
 }
 for alias in management_keys_to_revoke {
 active_management_keys[alias].drop();
@@ -261,11 +235,10 @@ active_services[alias].drop();
 }
 active_services.update(new_services);
 } else {
-skipped_entries += 1;
+state.skip_entry("Entry type not allowed by the DID method version");
 }
-return (true, method_version, skipped_entries);
 }
-fn process_did_deactivation_entry_v100<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, RT>(_chain_id: T0, ext_ids: T1, binary_content: T2, _parsed_content: T3, method_version: T4, active_management_keys: T5, active_did_keys: T6, active_services: T7, skipped_entries: T8, _all_keys: T9, _network: T10) -> RT {
+fn process_did_deactivation_entry_v100<T0, T1, T2, T3>(ext_ids: T0, binary_content: T1, state: T2, registry: T3)  {
 "
     Deactivates the DID by resetting the currently active management and DID keys, and services.
 
@@ -275,51 +248,32 @@ fn process_did_deactivation_entry_v100<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T
 
     Parameters
     ----------
-    _chain_id: str
-        Unused
     ext_ids: list
         The ExtIDs of the entry, as bytes.
     binary_content: bytes
         The raw entry content.
-    _parsed_content: dict
-        Unused
-    method_version: str
-        The current DID method spec version.
-    active_management_keys: dict
-        The currently active management keys. Will be reset.
-    active_did_keys: dict
-        The currently active DID keys. Will be reset.
-    active_services: dict
-        The currently active services. Will be reset.
-    skipped_entries: int
-        The current number of skipped entries. Will be incremented by one in case the DIDManagement entry is not valid.
-    _all_keys: set
-        Unused
-    _network: Network
-        Unused
-
-    Returns
-    -------
-    tuple
-        3-tuple (bool, str, int). The first element signifies if the caller should continue parsing the chain; the
-        second element contains the current DID method specification version; the third element contains the number
-        of skipped entries in the DIDManagement chain.
+    state: DIDChainState
+        The chain state. The active management keys, DID keys and services will be reset, the deactivation metadata
+        will be recorded and parsing of the chain will stop. The number of skipped entries will be incremented by one in case the entry is not valid.
     ";
-if method_version == DID_METHOD_SPEC_V020 {
+let rules = registry.get_method_version_rules(state.method_version);
+if rules != None&&rules.allows_entry_type(EntryType::Deactivation) {
 let key_id = ext_ids[2].decode();
-let signing_key = active_management_keys.get(_get_alias(key_id));
-if !signing_key||signing_key.priority != 0||!validate_signature(ext_ids, binary_content, signing_key) {
-return (true, method_version, (skipped_entries + 1));
-}
-active_management_keys.clear();
-active_did_keys.clear();
-active_services.clear();
+let signing_key = state.management_keys.get(rules.alias_from_id(key_id));
+if !signing_key||!rules.can_deactivate(signing_key)||!rules.allows_signing_key(signing_key)||!validate_signature(ext_ids, binary_content, signing_key, rules.signature_digest) {
+state.skip_entry("Invalid signature, signing key priority or unknown signing key");
+return;
+}
+state.deactivation = DeactivationMetadata(state.last_entry_hash, state.last_entry_timestamp, signing_key.full_id(state.did()));
+state.management_keys.clear();
+state.did_keys.clear();
+state.services.clear();
 } else {
-skipped_entries += 1;
+state.skip_entry("Entry type not allowed by the DID method version");
 }
-return (false, method_version, skipped_entries);
+state.keep_parsing = false;
 }
-fn process_did_method_version_upgrade_entry_v100<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, RT>(_chain_id: T0, ext_ids: T1, binary_content: T2, parsed_content: T3, method_version: T4, active_management_keys: T5, _active_did_keys: T6, _active_services: T7, skipped_entries: T8, _all_keys: T9, _network: T10) -> RT {
+fn process_did_method_version_upgrade_entry_v100<T0, T1, T2, T3, T4>(ext_ids: T0, binary_content: T1, parsed_content: T2, state: T3, registry: T4)  {
 "
     Upgrades the DID method version.
 
@@ -329,49 +283,33 @@ fn process_did_method_version_upgrade_entry_v100<T0, T1, T2, T3, T4, T5, T6, T7,
 
     Parameters
     ----------
-    _chain_id: str
-        Unused
     ext_ids: list
         The ExtIDs of the entry, as bytes.
     binary_content: bytes
         The raw entry content.
     parsed_content: dict
-        Unused
-    method_version: str
-        The current DID method spec version.
-    active_management_keys: dict
-        The currently active DID management keys.
-    _active_did_keys: dict
-        Unused
-    _active_services: dict
-        Unused
-    skipped_entries: int
-        The current number of skipped entries. Will be incremented by one in case the DIDManagement entry is not valid.
-    _all_keys: set
-        Unused
-    _network: Network
-        Unused
-
-    Returns
-    -------
-    tuple
-        3-tuple (bool, str, int). The first element signifies if the caller should continue parsing the chain; the
-        second element contains the current DID method specification version; the third element contains the number
-        of skipped entries in the DIDManagement chain.
+        The parsed DIDMethodVersionUpgrade entry.
+    state: DIDChainState
+        The chain state. The DID method specification version will be upgraded. The number of skipped entries will be
+        incremented by one in case the entry is not valid.
+    registry: EntryProcessorRegistry
+        The registry providing the rules of the DID method versions. Upgrades to a version without registered rules
+        are skipped, as the DID could not be resolved after them.
     ";
-let mut new_method_version = method_version;
-if method_version == DID_METHOD_SPEC_V020 {
+let rules = registry.get_method_version_rules(state.method_version);
+if rules != None&&rules.allows_entry_type(EntryType::VersionUpgrade) {
 let key_id = ext_ids[2].decode();
-let signing_key = active_management_keys.get(_get_alias(key_id));
-if signing_key&&_is_method_version_upgrade(method_version, parsed_content["didMethodVersion"])&&validate_signature(ext_ids, binary_content, signing_key) {
-new_method_version = parsed_content["didMethodVersion"];
+let signing_key = state.management_keys.get(rules.alias_from_id(key_id));
+if !registry.is_supported_method_version(parsed_content["didMethodVersion"]) {
+state.skip_entry("Unsupported DID method version");
+} else if signing_key&&rules.can_upgrade_version(signing_key)&&is_method_version_upgrade(state.method_version, parsed_content["didMethodVersion"])&&rules.allows_signing_key(signing_key)&&validate_signature(ext_ids, binary_content, signing_key, rules.signature_digest) {
+state.method_version = parsed_content["didMethodVersion"];
 } else {
-skipped_entries += 1;
+state.skip_entry("Invalid signature, unknown signing key or not a version upgrade");
 }
 } else {
-skipped_entries += 1;
+state.skip_entry("Entry type not allowed by the DID method version");
 }
-return (true, new_method_version, skipped_entries);
 }
 fn _process_management_key_revocations<T0, T1, T2, T3, T4, T5, RT>(entry_content: T0, signing_key_required_priority: T1, keys_to_revoke: T2, active_keys: T3, chain_id: T4, network: T5) -> RT {
 for key in entry_content["revoke"].get("managementKey", vec![]) {
@@ -491,4 +429,98 @@ if management_keys_to_revoke.iter().all(|&x| x != signing_key.alias) {
 management_keys_to_revoke.add(signing_key.alias);
 return false;
 }
-}
\ No newline at end of file
+}
+struct DIDManagementEntryProcessorV100 {
+schema_validator: ST0,
+}
+
+impl EntryProcessor for DIDManagementEntryProcessorV100 {
+"
+    Processes DIDManagement entries with entry schema version 1.0.0.
+    ";
+fn init(&self)  {
+self.schema_validator = get_schema_validator(DID_MANAGEMENT_SCHEMA);
+}
+fn validate_ext_ids<T0, T1, RT>(&self, ext_ids: T0, _state: T1) -> RT {
+validate_did_management_ext_ids_v100(ext_ids);
+return true;
+}
+fn parse_content<T0, RT>(&self, binary_content: T0) -> RT {
+return json.loads(binary_content.decode());
+}
+fn validate_content<T0>(&self, parsed_content: T0)  {
+self.schema_validator.validate(parsed_content);
+}
+fn process<T0, T1, T2, T3, T4>(&self, _ext_ids: T0, _binary_content: T1, parsed_content: T2, state: T3, _registry: T4)  {
+process_did_management_entry_v100(parsed_content, state);
+}
+}
+struct DIDUpdateEntryProcessorV100 {
+schema_validator: ST0,
+}
+
+impl EntryProcessor for DIDUpdateEntryProcessorV100 {
+"
+    Processes DIDUpdate entries with entry schema version 1.0.0.
+    ";
+fn init(&self)  {
+self.schema_validator = get_schema_validator(DID_UPDATE_SCHEMA);
+}
+fn validate_ext_ids<T0, T1, RT>(&self, ext_ids: T0, state: T1) -> RT {
+return validate_did_update_ext_ids_v100(ext_ids, state.chain_id, state.network);
+}
+fn parse_content<T0, RT>(&self, binary_content: T0) -> RT {
+return json.loads(binary_content.decode());
+}
+fn validate_content<T0>(&self, parsed_content: T0)  {
+self.schema_validator.validate(parsed_content);
+}
+fn process<T0, T1, T2, T3, T4>(&self, ext_ids: T0, binary_content: T1, parsed_content: T2, state: T3, registry: T4)  {
+process_did_update_entry_v100(ext_ids, binary_content, parsed_content, state, registry);
+}
+}
+struct DIDMethodVersionUpgradeEntryProcessorV100 {
+schema_validator: ST0,
+}
+
+impl EntryProcessor for DIDMethodVersionUpgradeEntryProcessorV100 {
+"
+    Processes DIDMethodVersionUpgrade entries with entry schema version 1.0.0.
+    ";
+fn init(&self)  {
+self.schema_validator = get_schema_validator(DID_METHOD_VERSION_UPGRADE_SCHEMA);
+}
+fn validate_ext_ids<T0, T1, RT>(&self, ext_ids: T0, state: T1) -> RT {
+return validate_did_method_version_upgrade_ext_ids_v100(ext_ids, state.chain_id, state.network);
+}
+fn parse_content<T0, RT>(&self, binary_content: T0) -> RT {
+return json.loads(binary_content.decode());
+}
+fn validate_content<T0>(&self, parsed_content: T0)  {
+self.schema_validator.validate(parsed_content);
+}
+fn process<T0, T1, T2, T3, T4>(&self, ext_ids: T0, binary_content: T1, parsed_content: T2, state: T3, registry: T4)  {
+process_did_method_version_upgrade_entry_v100(ext_ids, binary_content, parsed_content, state, registry);
+}
+}
+struct DIDDeactivationEntryProcessorV100 {
+
+}
+
+impl EntryProcessor for DIDDeactivationEntryProcessorV100 {
+"
+    Processes DIDDeactivation entries with entry schema version 1.0.0.
+    ";
+fn validate_ext_ids<T0, T1, RT>(&self, ext_ids: T0, state: T1) -> RT {
+return validate_did_deactivation_ext_ids_v100(ext_ids, state.chain_id, state.network);
+}
+fn parse_content<T0, RT>(&self, binary_content: T0) -> RT {
+return binary_content.decode();
+}
+fn validate_content<T0>(&self, parsed_content: T0)  {
+EmptyEntryContentValidator::validate(parsed_content);
+}
+fn process<T0, T1, T2, T3, T4>(&self, ext_ids: T0, binary_content: T1, _parsed_content: T2, state: T3, registry: T4)  {
+process_did_deactivation_entry_v100(ext_ids, binary_content, state, registry);
+}
+}
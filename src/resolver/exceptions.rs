@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 use std::*;
 
+// The error code returned by factomd for a chain which does not exist
+const MISSING_CHAIN_ERROR_CODE: _ = -32009;
+
 struct MalformedDIDManagementEntry {}
 
 impl MalformedDIDManagementEntry {
@@ -11,3 +14,41 @@ struct InvalidDIDChain {}
 impl InvalidDIDChain {
     /*pass*/
 }
+struct InvalidDIDChainEntry {
+    entry_hash: ST0,
+    reason: ST1,
+}
+
+impl InvalidDIDChainEntry {
+    "
+    Raised when resolving in strict mode and an entry of the chain is not valid.
+
+    Attributes
+    ----------
+    entry_hash: str
+        The hash of the invalid entry
+    reason: str
+        Why the entry is not valid
+    ";
+    fn init<T0, T1>(&self, entry_hash: T0, reason: T1) {
+        super().__init__("Invalid entry {}: {}".format(entry_hash, reason));
+        self.entry_hash = entry_hash;
+        self.reason = reason;
+    }
+}
+fn is_missing_chain_error<T0, RT>(error: T0) -> RT {
+    "
+    Checks whether a FactomAPIError was raised because the requested chain does not exist, as opposed to factomd being
+    unavailable or failing.
+
+    Parameters
+    ----------
+    error: FactomAPIError
+
+    Returns
+    -------
+    bool
+    ";
+    let data = getattr(error, "data", None);
+    return isinstance(data, dict) && data.get("code") == MISSING_CHAIN_ERROR_CODE;
+}
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::*;
+
+use factom_did::client::enums::KeyType;
+use factom_did::client::keys::formats::public_key_to_multibase;
+use pyld::jsonld;
+let all = vec![
+    "to_json_ld",
+    "canonicalize_urdna2015",
+    "offline_document_loader",
+];
+const DID_CONTEXT: _ = "https://www.w3.org/ns/did/v1";
+const ED25519_2018_CONTEXT: _ = "https://w3id.org/security/suites/ed25519-2018/v1";
+const ED25519_2020_CONTEXT: _ = "https://w3id.org/security/suites/ed25519-2020/v1";
+const SECP256K1_2019_CONTEXT: _ = "https://w3id.org/security/suites/secp256k1-2019/v1";
+const SECURITY_V2_CONTEXT: _ = "https://w3id.org/security/v2";
+const JWS_2020_CONTEXT: _ = "https://w3id.org/security/suites/jws-2020/v1";
+const CREDENTIALS_V2_CONTEXT: _ = "https://www.w3.org/ns/credentials/v2";
+// Offline copies of the contexts referenced by the JSON-LD DID Documents and Verifiable Credentials, keyed by URL.
+// They are embedded at build time, so that loading them does not depend on the working directory. See
+// contexts/PROVENANCE.md for how each copy was obtained.
+const BUNDLED_CONTEXTS: _ = [
+    (DID_CONTEXT, include_str!("contexts/did-v1.jsonld")),
+    (ED25519_2018_CONTEXT, include_str!("contexts/ed25519-2018-v1.jsonld")),
+    (ED25519_2020_CONTEXT, include_str!("contexts/ed25519-2020-v1.jsonld")),
+    (SECP256K1_2019_CONTEXT, include_str!("contexts/secp256k1-2019-v1.jsonld")),
+    (SECURITY_V2_CONTEXT, include_str!("contexts/security-v2.jsonld")),
+    (JWS_2020_CONTEXT, include_str!("contexts/jws-2020-v1.jsonld")),
+    (CREDENTIALS_V2_CONTEXT, include_str!("contexts/credentials-v2.jsonld")),
+]
+.iter()
+.cloned()
+.collect::<HashMap<_, _>>();
+fn to_json_ld<T0, T1, RT>(did_document: T0, ed25519_suite: T1) -> RT {
+    "
+    Converts a DID Document produced by build_did_document to JSON-LD.
+
+    The verification method types are mapped to the ones defined by the W3C security vocabularies, and the
+    '@context' lists the DID context followed by the context of each verification method type in use. Verification
+    methods with a 'publicKeyJwk' are of type JsonWebKey2020.
+
+    Parameters
+    ----------
+    did_document: dict
+        The DID Document, which is not modified
+    ed25519_suite: str, optional
+        The verification method type to use for Ed25519 keys: '2018' (the default) for Ed25519VerificationKey2018,
+        with the key in 'publicKeyBase58', or '2020' for Ed25519VerificationKey2020, with the key in
+        'publicKeyMultibase'
+
+    Returns
+    -------
+    dict
+        The JSON-LD DID Document
+
+    Raises
+    ------
+    ValueError
+        If the Ed25519 suite is not supported
+    ";
+    let ed25519_suite = if ed25519_suite == None {
+        "2018"
+    } else {
+        ed25519_suite
+    };
+    if ("2018", "2020").iter().all(|&x| x != ed25519_suite) {
+        raise!(ValueError("Unsupported Ed25519 suite: {}".format(ed25519_suite))); //unsupported
+    }
+    let contexts = vec![DID_CONTEXT];
+    let verification_methods = vec![];
+    for method in did_document.get("verificationMethod", vec![]) {
+        let (method, context) = _to_json_ld_verification_method(method, ed25519_suite);
+        if contexts.iter().all(|&x| x != context) {
+            contexts.append(context);
+        }
+        verification_methods.append(method);
+    }
+    let document = [("@context", contexts)]
+        .iter()
+        .cloned()
+        .collect::<HashMap<_, _>>();
+    for (key, value) in did_document.items() {
+        document[key] = if key == "verificationMethod" {
+            verification_methods
+        } else {
+            value
+        };
+    }
+    return document;
+}
+fn _to_json_ld_verification_method<T0, T1, RT>(method: T0, ed25519_suite: T1) -> RT {
+    let method = dict(method);
+    if method.iter().any(|&x| x == "publicKeyJwk") {
+        method["type"] = "JsonWebKey2020";
+        return (method, JWS_2020_CONTEXT);
+    }
+    if method["type"] == KeyType::EdDSA.value {
+        if ed25519_suite == "2020" {
+            method["type"] = "Ed25519VerificationKey2020";
+            if method.iter().any(|&x| x == "publicKeyBase58") {
+                method["publicKeyMultibase"] = public_key_to_multibase(
+                    KeyType::EdDSA,
+                    base58.b58decode(method.pop("publicKeyBase58")),
+                );
+            }
+            return (method, ED25519_2020_CONTEXT);
+        }
+        method["type"] = "Ed25519VerificationKey2018";
+        return (method, ED25519_2018_CONTEXT);
+    }
+    if method["type"] == KeyType::ECDSA.value {
+        method["type"] = "EcdsaSecp256k1VerificationKey2019";
+        return (method, SECP256K1_2019_CONTEXT);
+    }
+    method["type"] = "RsaVerificationKey2018";
+    return (method, SECURITY_V2_CONTEXT);
+}
+fn offline_document_loader<T0, T1, RT>(url: T0, _options: T1) -> RT {
+    "
+    A JSON-LD document loader which only serves the bundled contexts, so that JSON-LD processing never accesses the
+    network.
+
+    Parameters
+    ----------
+    url: str
+    _options: dict, optional
+
+    Returns
+    -------
+    dict
+        The remote document, as expected by pyld
+
+    Raises
+    ------
+    jsonld.JsonLdError
+        If the URL is not one of the bundled contexts
+    ";
+    if BUNDLED_CONTEXTS.iter().all(|&x| x != url) {
+        raise!(jsonld.JsonLdError(
+            "Context not available offline: {}".format(url),
+            "jsonld.LoadDocumentError",
+            [("url", url)].iter().cloned().collect::<HashMap<_, _>>(),
+            "loading remote context failed",
+        )); //unsupported
+    }
+    return [
+        ("contextUrl", None),
+        ("documentUrl", url),
+        ("document", json.loads(BUNDLED_CONTEXTS[url])),
+    ]
+    .iter()
+    .cloned()
+    .collect::<HashMap<_, _>>();
+}
+fn canonicalize_urdna2015<T0, RT>(document: T0) -> RT {
+    "
+    Canonicalizes a JSON-LD document with the URDNA2015 algorithm, using only the bundled contexts.
+
+    Parameters
+    ----------
+    document: dict
+        A JSON-LD document, e.g. one produced by to_json_ld
+
+    Returns
+    -------
+    str
+        The canonical N-Quads of the document, suitable for hashing and signing
+    ";
+    return jsonld.normalize(
+        document,
+        [
+            ("algorithm", "URDNA2015"),
+            ("format", "application/n-quads"),
+            ("documentLoader", offline_document_loader),
+        ]
+        .iter()
+        .cloned()
+        .collect::<HashMap<_, _>>(),
+    );
+}
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::*;
+
+use factom_did::client::constants::DID_METHOD_SPEC_V020;
+use factom_did::client::enums::{EntryType, KeyType};
+let all = vec![
+    "MethodVersionRules",
+    "parse_method_version",
+    "is_method_version_upgrade",
+    "DID_METHOD_SPEC_V020_RULES",
+];
+struct MethodVersionRules {
+    version: ST0,
+    allowed_entry_types: ST1,
+    deactivation_max_priority: ST2,
+    version_upgrade_max_priority: ST3,
+    id_separator: ST4,
+    signing_key_types: ST5,
+    signature_digest: ST6,
+}
+
+impl MethodVersionRules {
+    "
+    The rules of a DID method specification version, which the entry processors apply to the entries recorded while
+    the DID is at that version.
+
+    Attributes
+    ----------
+    version: str
+        The DID method specification version, as a semantic version
+    allowed_entry_types: set of EntryType
+        The entry types which may be recorded after the DIDManagement entry
+    deactivation_max_priority: int
+        The highest priority value (i.e. the lowest priority) of a management key allowed to sign a DIDDeactivation
+        entry
+    version_upgrade_max_priority: int, optional
+        The highest priority value of a management key allowed to sign a DIDMethodVersionUpgrade entry. None means
+        that any active management key is allowed.
+    id_separator: str
+        The separator between the DID and the alias in key and service identifiers
+    signing_key_types: set of KeyType
+        The types of the management keys allowed to sign DIDUpdate, DIDMethodVersionUpgrade and DIDDeactivation
+        entries
+    signature_digest: str
+        The name of the hashlib hash function applied to the first 3 ExtIDs and the content of an entry before they
+        are signed
+    ";
+    fn init<T0, T1, T2, T3, T4, T5, T6>(
+        &self,
+        version: T0,
+        allowed_entry_types: T1,
+        deactivation_max_priority: T2,
+        version_upgrade_max_priority: T3,
+        id_separator: T4,
+        signing_key_types: T5,
+        signature_digest: T6,
+    ) {
+        parse_method_version(version);
+        self.version = version;
+        self.allowed_entry_types = set(allowed_entry_types);
+        self.deactivation_max_priority = deactivation_max_priority;
+        self.version_upgrade_max_priority = version_upgrade_max_priority;
+        self.id_separator = id_separator;
+        self.signing_key_types = set(signing_key_types);
+        if hashlib.algorithms_available.iter().all(|&x| x != signature_digest) {
+            raise!(ValueError("Unknown signature digest: {}".format(signature_digest))); //unsupported
+        }
+        self.signature_digest = signature_digest;
+    }
+    fn allows_entry_type<T0, RT>(&self, entry_type: T0) -> RT {
+        return self.allowed_entry_types.iter().any(|&x| x == entry_type);
+    }
+    fn can_deactivate<T0, RT>(&self, signing_key: T0) -> RT {
+        return signing_key.priority <= self.deactivation_max_priority;
+    }
+    fn allows_signing_key<T0, RT>(&self, signing_key: T0) -> RT {
+        return self.signing_key_types.iter().any(|&x| x == signing_key.key_type);
+    }
+    fn can_upgrade_version<T0, RT>(&self, signing_key: T0) -> RT {
+        return self.version_upgrade_max_priority == None
+            || signing_key.priority <= self.version_upgrade_max_priority;
+    }
+    fn alias_from_id<T0, RT>(&self, full_or_partial_id: T0) -> RT {
+        return full_or_partial_id.split(self.id_separator)[-1];
+    }
+    fn format_id<T0, T1, RT>(&self, did: T0, alias: T1) -> RT {
+        return "{}{}{}".format(did, self.id_separator, alias);
+    }
+}
+// The rules of the only DID method specification version defined so far. They are registered in the default
+// EntryProcessorRegistry and determine the format of key and service identifiers.
+const DID_METHOD_SPEC_V020_RULES: _ = MethodVersionRules(
+    DID_METHOD_SPEC_V020,
+    vec![
+        EntryType::Update,
+        EntryType::VersionUpgrade,
+        EntryType::Deactivation,
+    ],
+    0,
+    None,
+    "#",
+    vec![KeyType::EdDSA, KeyType::ECDSA, KeyType::RSA],
+    "sha256",
+);
+fn parse_method_version<T0, RT>(version: T0) -> RT {
+    "
+    Parses a DID method specification version.
+
+    Parameters
+    ----------
+    version: str
+        A semantic version of the form MAJOR.MINOR.PATCH
+
+    Returns
+    -------
+    tuple
+        A 3-tuple of ints containing the major, minor and patch versions
+
+    Raises
+    ------
+    ValueError
+        If the version is not a valid semantic version
+    ";
+    if !isinstance(version, str) || !re.match("^(0|[1-9][0-9]*)\\.(0|[1-9][0-9]*)\\.(0|[1-9][0-9]*)$", version) {
+        raise!(ValueError("Invalid DID method version: {}".format(version))); //unsupported
+    }
+    return tuple(version.split(".").iter().map(int).collect::<Vec<_>>());
+}
+fn is_method_version_upgrade<T0, T1, RT>(current_version: T0, new_version: T1) -> RT {
+    "
+    Checks if the new version is an upgrade over the current version.
+
+    Parameters
+    ----------
+    current_version: str
+    new_version: str
+
+    Returns
+    -------
+    bool
+        False if either version is not a valid semantic version
+    ";
+    let try_dummy = {
+        //unsupported
+        return parse_method_version(current_version) < parse_method_version(new_version);
+    };
+    let except!(ValueError) = {
+        //unsupported
+        return false;
+    };
+}
@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::*;
+
+let all = vec!["ResolutionMode", "PublicKeyFormat", "ResolverOptions"];
+struct ResolutionMode {}
+
+impl ResolutionMode {
+    const Strict: _ = "strict";
+    const SpecCompliant: _ = "spec-compliant";
+    const Lenient: _ = "lenient";
+    fn from_str<T0, RT>(string: T0) -> RT {
+        if string == "strict" {
+            return ResolutionMode::Strict;
+        } else {
+            if string == "spec-compliant" {
+                return ResolutionMode::SpecCompliant;
+            } else {
+                if string == "lenient" {
+                    return ResolutionMode::Lenient;
+                } else {
+                    raise!(NotImplementedError(
+                        "Unknown ResolutionMode value: {}".format(string)
+                    )); //unsupported
+                }
+            }
+        }
+    }
+}
+struct PublicKeyFormat {}
+
+impl PublicKeyFormat {
+    const OnChain: _ = "onChain";
+    const Jwk: _ = "jwk";
+    const Multibase: _ = "multibase";
+}
+struct ResolverOptions {
+    mode: ST0,
+    public_key_format: ST1,
+}
+
+impl ResolverOptions {
+    "
+    Options controlling how a DIDManagement chain is resolved.
+
+    Attributes
+    ----------
+    mode: ResolutionMode, optional
+        How invalid entries are handled. Defaults to ResolutionMode.SpecCompliant.
+
+        * ResolutionMode.Strict: resolution fails with InvalidDIDChainEntry on the first invalid entry, including
+          duplicate entries and entries recorded after the DID was deactivated
+        * ResolutionMode.SpecCompliant: resolution fails with InvalidDIDChain if the first entry is invalid, and any
+          other invalid entry is skipped, as required by the DID method specification
+        * ResolutionMode.Lenient: as ResolutionMode.SpecCompliant, but unknown fields in the entry content are
+          ignored, and entries with a newer minor schema version than the ones registered are processed with the
+          processor for the latest registered schema version with the same major version
+    public_key_format: PublicKeyFormat, optional
+        The representation of the public keys in the verification methods of resolved DID Documents:
+        PublicKeyFormat.OnChain ('publicKeyBase58' or 'publicKeyPem', the default), PublicKeyFormat.Jwk
+        ('publicKeyJwk') or PublicKeyFormat.Multibase ('publicKeyMultibase')
+    ";
+    fn init<T0, T1>(&self, mode: T0, public_key_format: T1) {
+        self.mode = if mode == None {
+            ResolutionMode::SpecCompliant
+        } else {
+            mode
+        };
+        if (
+            ResolutionMode::Strict,
+            ResolutionMode::SpecCompliant,
+            ResolutionMode::Lenient,
+        )
+            .iter()
+            .all(|&x| x != self.mode)
+        {
+            raise!(ValueError("Mode must be a valid ResolutionMode.")); //unsupported
+        }
+        self.public_key_format = if public_key_format == None {
+            PublicKeyFormat::OnChain
+        } else {
+            public_key_format
+        };
+        if (
+            PublicKeyFormat::OnChain,
+            PublicKeyFormat::Jwk,
+            PublicKeyFormat::Multibase,
+        )
+            .iter()
+            .all(|&x| x != self.public_key_format)
+        {
+            raise!(ValueError("Public key format must be a valid PublicKeyFormat.")); //unsupported
+        }
+    }
+    fn strict<RT>() -> RT {
+        return ResolverOptions(ResolutionMode::Strict, None);
+    }
+    fn lenient<RT>() -> RT {
+        return ResolverOptions(ResolutionMode::Lenient, None);
+    }
+    fn is_strict<RT>(&self) -> RT {
+        return self.mode == ResolutionMode::Strict;
+    }
+    fn is_lenient<RT>(&self) -> RT {
+        return self.mode == ResolutionMode::Lenient;
+    }
+}
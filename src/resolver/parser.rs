@@ -1,94 +1,23 @@
 use std::collections::HashMap;
 use std::*;
 
-use factom_did::client::constants::ENTRY_SCHEMA_V100;
 use factom_did::client::enums::{EntryType, Network};
-use factom_did::resolver::entry_processors::{
-    process_did_deactivation_entry_v100, process_did_management_entry_v100,
-    process_did_method_version_upgrade_entry_v100, process_did_update_entry_v100,
-};
-use factom_did::resolver::exceptions::{InvalidDIDChain, MalformedDIDManagementEntry};
-use factom_did::resolver::schema::get_schema_validator;
-use factom_did::resolver::validators::{
-    validate_did_deactivation_ext_ids_v100, validate_did_management_ext_ids_v100,
-    validate_did_method_version_upgrade_ext_ids_v100, validate_did_update_ext_ids_v100,
-    EmptyEntryContentValidator,
+use factom_did::resolver::chain_state::DIDChainState;
+use factom_did::resolver::exceptions::{
+    InvalidDIDChain, InvalidDIDChainEntry, MalformedDIDManagementEntry,
 };
+use factom_did::resolver::options::ResolverOptions;
+use factom_did::resolver::registry::EntryProcessorRegistry;
 use json::JSONDecodeError;
 use jsonschema::exceptions::ValidationError;
-const DID_MANAGEMENT_SCHEMA: _ = "did_management_entry.json";
-const DID_UPDATE_SCHEMA: _ = "did_update_entry.json";
-const DID_METHOD_VERSION_UPGRADE_SCHEMA: _ = "did_method_version_upgrade_entry.json";
-const ENTRY_SCHEMA_VALIDATORS: _ = [(
-    ENTRY_SCHEMA_V100,
-    [
-        (
-            EntryType::Create.value,
-            get_schema_validator(DID_MANAGEMENT_SCHEMA),
-        ),
-        (
-            EntryType::Update.value,
-            get_schema_validator(DID_UPDATE_SCHEMA),
-        ),
-        (
-            EntryType::VersionUpgrade.value,
-            get_schema_validator(DID_METHOD_VERSION_UPGRADE_SCHEMA),
-        ),
-        (EntryType::Deactivation.value, EmptyEntryContentValidator),
-    ]
-    .iter()
-    .cloned()
-    .collect::<HashMap<_, _>>(),
-)]
-.iter()
-.cloned()
-.collect::<HashMap<_, _>>();
-const ENTRY_EXT_ID_VALIDATORS: _ = [(
-    ENTRY_SCHEMA_V100,
-    [
-        (
-            EntryType::Create.value,
-            validate_did_management_ext_ids_v100,
-        ),
-        (EntryType::Update.value, validate_did_update_ext_ids_v100),
-        (
-            EntryType::VersionUpgrade.value,
-            validate_did_method_version_upgrade_ext_ids_v100,
-        ),
-        (
-            EntryType::Deactivation.value,
-            validate_did_deactivation_ext_ids_v100,
-        ),
-    ]
-    .iter()
-    .cloned()
-    .collect::<HashMap<_, _>>(),
-)]
-.iter()
-.cloned()
-.collect::<HashMap<_, _>>();
-const ENTRY_PROCESSORS: _ = [(
-    ENTRY_SCHEMA_V100,
-    [
-        (EntryType::Create.value, process_did_management_entry_v100),
-        (EntryType::Update.value, process_did_update_entry_v100),
-        (
-            EntryType::VersionUpgrade.value,
-            process_did_method_version_upgrade_entry_v100,
-        ),
-        (
-            EntryType::Deactivation.value,
-            process_did_deactivation_entry_v100,
-        ),
-    ]
-    .iter()
-    .cloned()
-    .collect::<HashMap<_, _>>(),
-)]
-.iter()
-.cloned()
-.collect::<HashMap<_, _>>();
-fn parse_did_chain_entries<T0, T1, T2, RT>(entries: T0, chain_id: T1, network: T2) -> RT {
+const DEFAULT_ENTRY_PROCESSOR_REGISTRY: _ = EntryProcessorRegistry::default();
+fn parse_did_chain_entries<T0, T1, T2, T3, T4, RT>(
+    entries: T0,
+    chain_id: T1,
+    network: T2,
+    registry: T3,
+    options: T4,
+) -> RT {
     "
     Attempts to parse the entries in a DIDManagement chain.
 
@@ -102,6 +31,11 @@ fn parse_did_chain_entries<T0, T1, T2, RT>(entries: T0, chain_id: T1, network: T
         The DIDManagement chain ID
     network: Network
         The Factom network on which the DID is recorded
+    registry: EntryProcessorRegistry, optional
+        The entry processors to use. Defaults to the processors for all entry types defined by the DID method
+        specification.
+    options: ResolverOptions, optional
+        The resolution options. Defaults to spec-compliant resolution.
 
     Returns
     -------
@@ -113,137 +47,302 @@ fn parse_did_chain_entries<T0, T1, T2, RT>(entries: T0, chain_id: T1, network: T
     ------
     InvalidDIDChain
        If the first entry in the chain is not a valid DIDManagement entry
+    InvalidDIDChainEntry
+       If resolving in strict mode and an entry in the chain is not valid
     ";
-    let active_management_keys = HashMap::new();
-    let active_did_keys = HashMap::new();
-    let active_services = HashMap::new();
-    let all_keys = set();
-    let processed_entry_hashes = set();
-    let method_version = None;
-    let mut skipped_entries = 0;
-    let keep_parsing = true;
-    for (i, entry) in entries.iter().enumerate() {
-        if !keep_parsing {
-            return (
-                active_management_keys,
-                active_did_keys,
-                active_services,
-                ((skipped_entries + entries.len()) - i),
-            );
-        }
-        let ext_ids = entry["extids"];
-        let binary_content = entry["content"];
-        let entry_hash = entry["entryhash"];
-        if processed_entry_hashes.iter().any(|&x| x == entry_hash) {
-            skipped_entries += 1;
-            continue;
+    let state = DIDChainState(chain_id, network);
+    apply_entries(state, entries, registry, options);
+    return state.to_tuple();
+}
+fn resolve_incremental<T0, T1, T2, T3, RT>(
+    snapshot: T0,
+    new_entries: T1,
+    registry: T2,
+    options: T3,
+) -> RT {
+    "
+    Resumes parsing of a DIDManagement chain from a previously saved chain state.
+
+    Applying the entries recorded after the snapshot was taken yields exactly the same state as parsing the whole
+    chain with parse_did_chain_entries.
+
+    Parameters
+    ----------
+    snapshot: DIDChainState or dict or str
+        The saved chain state, either as an object or as a snapshot produced by DIDChainState.to_dict or
+        DIDChainState.to_json. The snapshot is not modified.
+    new_entries: list of dict
+        The entries recorded in the chain after the last entry processed in the snapshot, in the same format as the one
+        accepted by parse_did_chain_entries
+    registry: EntryProcessorRegistry, optional
+        The entry processors to use
+    options: ResolverOptions, optional
+        The resolution options
+
+    Returns
+    -------
+    DIDChainState
+        The updated chain state
+
+    Raises
+    ------
+    InvalidDIDChain
+       If the snapshot has not been initialized and the first new entry is not a valid DIDManagement entry
+    InvalidDIDChainEntry
+       If resolving in strict mode and a new entry is not valid
+    ";
+    let state = if isinstance(snapshot, DIDChainState) {
+        snapshot.copy()
+    } else {
+        if isinstance(snapshot, str) {
+            DIDChainState::from_json(snapshot)
+        } else {
+            DIDChainState::from_dict(snapshot)
         }
-        processed_entry_hashes.add(entry_hash);
-        if i == 0 {
-            let try_dummy = {
-                //unsupported
-                let mut entry_type = ext_ids[0].decode();
-                if entry_type != EntryType::Create.value {
-                    raise!(InvalidDIDChain("First entry must be of type DIDManagement"));
-                    //unsupported
-                }
-                let mut parsed_content = json.loads(binary_content.decode());
-                let mut schema_version = ext_ids[1].decode();
-                ENTRY_EXT_ID_VALIDATORS[schema_version][entry_type](ext_ids);
-                ENTRY_SCHEMA_VALIDATORS[schema_version][entry_type].validate(parsed_content);
-                let (keep_parsing, method_version, skipped_entries) = ENTRY_PROCESSORS
-                    [schema_version][entry_type](
-                    chain_id,
-                    parsed_content,
-                    active_management_keys,
-                    active_did_keys,
-                    active_services,
-                    skipped_entries,
-                    network,
-                );
-                all_keys.update(active_management_keys.values(), active_did_keys.values());
-            };
-            let except!((UnicodeDecodeError, JSONDecodeError)) = {
-                //unsupported
-                raise!(InvalidDIDChain(
-                    "DIDManagement entry content must be valid JSON"
-                )); //unsupported
-            };
-            let except!(KeyError) = {
-                //unsupported
-                raise!(InvalidDIDChain("Unknown schema version or entry type"));
-                //unsupported
-            };
-            let except!(IndexError) = {
-                //unsupported
-                raise!(InvalidDIDChain(
-                    "DIDManagement entry has insufficient ExtIDs"
-                )); //unsupported
-            };
-            let except!(ValidationError) = {
-                //unsupported
-                raise!(InvalidDIDChain("Invalid DIDManagement entry content")); //unsupported
-            };
-            let except!(MalformedDIDManagementEntry) = {
-                //unsupported
-                raise!(InvalidDIDChain(
-                    "Malformed DIDManagement entry: {}".format(e.args[0])
-                )); //unsupported
-            };
+    };
+    apply_entries(state, new_entries, registry, options);
+    return state;
+}
+fn apply_entries<T0, T1, T2, T3>(state: T0, entries: T1, registry: T2, options: T3) {
+    "
+    Applies the given entries, in order, to the chain state.
+
+    Parameters
+    ----------
+    state: DIDChainState
+        The chain state. Will be updated according to the entries.
+    entries: list of dict
+        The entries to apply, in the same format as the one accepted by parse_did_chain_entries
+    registry: EntryProcessorRegistry, optional
+        The entry processors to use
+    options: ResolverOptions, optional
+        The resolution options
+
+    Raises
+    ------
+    InvalidDIDChain
+       If the chain state has not been initialized and the first entry is not a valid DIDManagement entry
+    InvalidDIDChainEntry
+       If resolving in strict mode and an entry is not valid
+    ";
+    let registry = if registry == None {
+        DEFAULT_ENTRY_PROCESSOR_REGISTRY
+    } else {
+        registry
+    };
+    let options = if options == None {
+        ResolverOptions()
+    } else {
+        options
+    };
+    for entry in entries {
+        process_entry(state, entry, registry, options);
+    }
+}
+fn process_entry<T0, T1, T2, T3>(state: T0, entry: T1, registry: T2, options: T3) {
+    "
+    Applies a single entry to the chain state.
+
+    The first entry of the chain must be a valid DIDManagement entry. Any subsequent entry which is not valid, or
+    which is a duplicate of an entry already processed, is skipped, unless resolving in strict mode.
+
+    Parameters
+    ----------
+    state: DIDChainState
+        The chain state. Will be updated according to the entry.
+    entry: dict
+        The entry, with keys 'content', 'extids', 'entryhash' and optionally 'timestamp'
+    registry: EntryProcessorRegistry
+        The entry processors to use
+    options: ResolverOptions
+        The resolution options
+
+    Raises
+    ------
+    InvalidDIDChain
+       If the chain state has not been initialized and the entry is not a valid DIDManagement entry
+    InvalidDIDChainEntry
+       If resolving in strict mode and the entry is not valid
+    ";
+    let ext_ids = entry["extids"];
+    let binary_content = entry["content"];
+    let entry_hash = entry["entryhash"];
+    let skipped_entries = state.skipped_entries;
+    state.processed_entries += 1;
+    state.last_entry_hash = entry_hash;
+    state.last_entry_timestamp = entry.get("timestamp");
+    if !state.keep_parsing {
+        state.skip_entry("Entry recorded after the DID was deactivated");
+    } else {
+        if state.processed_entry_hashes.iter().any(|&x| x == entry_hash) {
+            state.skip_entry("Duplicate entry");
         } else {
-            if ext_ids.len() >= 4 {
-                let try_dummy = {
-                    //unsupported
-                    entry_type = ext_ids[0].decode();
-                    schema_version = ext_ids[1].decode();
-                    if entry_type == EntryType::Create.value
-                        || ENTRY_SCHEMA_VALIDATORS.iter().all(|&x| x != schema_version)
-                        || ENTRY_SCHEMA_VALIDATORS[schema_version]
-                            .iter()
-                            .all(|&x| x != entry_type)
-                        || !ENTRY_EXT_ID_VALIDATORS[schema_version][entry_type](
-                            ext_ids, chain_id, network,
-                        )
-                    {
-                        skipped_entries += 1;
-                        continue;
-                    }
-                    let decoded_content = binary_content.decode();
-                    parsed_content = decoded_content;
-                    if entry_type != EntryType::Deactivation.value {
-                        parsed_content = json.loads(decoded_content);
-                    }
-                    ENTRY_SCHEMA_VALIDATORS[schema_version][entry_type].validate(parsed_content);
-                    let (keep_parsing, method_version, skipped_entries) = ENTRY_PROCESSORS
-                        [schema_version][entry_type](
-                        chain_id,
-                        ext_ids,
-                        binary_content,
-                        parsed_content,
-                        method_version,
-                        active_management_keys,
-                        active_did_keys,
-                        active_services,
-                        skipped_entries,
-                        all_keys,
-                        network,
-                    );
-                    all_keys.update(active_management_keys.values(), active_did_keys.values());
-                };
-                let except!((UnicodeDecodeError, JSONDecodeError, ValidationError)) = {
-                    //unsupported
-                    skipped_entries += 1;
-                    continue;
-                };
+            state.processed_entry_hashes.add(entry_hash);
+            if !state.is_initialized() {
+                _process_first_entry(state, ext_ids, binary_content, registry, options);
             } else {
-                skipped_entries += 1;
+                _process_subsequent_entry(state, ext_ids, binary_content, registry, options);
             }
         }
     }
+    if options.is_strict() && state.skipped_entries > skipped_entries {
+        raise!(InvalidDIDChainEntry(entry_hash, state.last_skip_reason)); //unsupported
+    }
+}
+fn _process_first_entry<T0, T1, T2, T3, T4>(
+    state: T0,
+    ext_ids: T1,
+    binary_content: T2,
+    registry: T3,
+    options: T4,
+) {
+    let try_dummy = {
+        //unsupported
+        let entry_type = ext_ids[0].decode();
+        if entry_type != EntryType::Create.value {
+            raise!(InvalidDIDChain("First entry must be of type DIDManagement"));
+            //unsupported
+        }
+        let (processor, validated_ext_ids) = _find_processor(registry, ext_ids, options);
+        if processor == None {
+            raise!(KeyError(ext_ids[1].decode())); //unsupported
+        }
+        processor.validate_ext_ids(validated_ext_ids, state);
+        let parsed_content = processor.parse_content(binary_content);
+        _validate_content(processor, parsed_content, options);
+        processor.process(ext_ids, binary_content, parsed_content, state, registry);
+        state.record_active_keys();
+    };
+    let except!((UnicodeDecodeError, JSONDecodeError)) = {
+        //unsupported
+        raise!(InvalidDIDChain(
+            "DIDManagement entry content must be valid JSON"
+        )); //unsupported
+    };
+    let except!(KeyError) = {
+        //unsupported
+        raise!(InvalidDIDChain("Unknown schema version or entry type"));
+        //unsupported
+    };
+    let except!(IndexError) = {
+        //unsupported
+        raise!(InvalidDIDChain(
+            "DIDManagement entry has insufficient ExtIDs"
+        )); //unsupported
+    };
+    let except!(ValidationError) = {
+        //unsupported
+        raise!(InvalidDIDChain("Invalid DIDManagement entry content")); //unsupported
+    };
+    let except!(MalformedDIDManagementEntry as e) = {
+        //unsupported
+        raise!(InvalidDIDChain(
+            "Malformed DIDManagement entry: {}".format(e.args[0])
+        )); //unsupported
+    };
+}
+fn _process_subsequent_entry<T0, T1, T2, T3, T4>(
+    state: T0,
+    ext_ids: T1,
+    binary_content: T2,
+    registry: T3,
+    options: T4,
+) {
+    if ext_ids.len() < 4 {
+        state.skip_entry("Entry has insufficient ExtIDs");
+        return;
+    }
+    let try_dummy = {
+        //unsupported
+        let entry_type = ext_ids[0].decode();
+        if entry_type == EntryType::Create.value {
+            state.skip_entry("DIDManagement entry after the first entry");
+            return;
+        }
+        let (processor, validated_ext_ids) = _find_processor(registry, ext_ids, options);
+        if processor == None {
+            state.skip_entry("Unknown schema version or entry type");
+            return;
+        }
+        if !processor.validate_ext_ids(validated_ext_ids, state) {
+            state.skip_entry("Invalid ExtIDs");
+            return;
+        }
+        let parsed_content = processor.parse_content(binary_content);
+        _validate_content(processor, parsed_content, options);
+        processor.process(ext_ids, binary_content, parsed_content, state, registry);
+        state.record_active_keys();
+    };
+    let except!((UnicodeDecodeError, JSONDecodeError)) = {
+        //unsupported
+        state.skip_entry("Entry content must be valid JSON");
+    };
+    let except!(ValidationError as e) = {
+        //unsupported
+        state.skip_entry("Invalid entry content: {}".format(e.message));
+    };
+}
+fn _find_processor<T0, T1, T2, RT>(registry: T0, ext_ids: T1, options: T2) -> RT {
+    "
+    Finds the processor for an entry.
+
+    In lenient mode, an entry with a newer minor schema version than the registered ones is processed with the
+    processor for the latest compatible schema version. Its ExtIDs are then validated as if they carried that schema
+    version, while the original ExtIDs are used for processing, since they are covered by the entry signature.
+
+    Returns
+    -------
+    tuple
+        A 2-tuple containing the processor (or None if there is none) and the ExtIDs to validate
+    ";
+    let entry_type = ext_ids[0].decode();
+    let schema_version = ext_ids[1].decode();
+    let processor = registry.get(schema_version, entry_type);
+    if processor != None || !options.is_lenient() {
+        return (processor, ext_ids);
+    }
+    let compatible = registry.get_compatible(schema_version, entry_type);
+    if compatible == None {
+        return (None, ext_ids);
+    }
+    let (compatible_version, processor) = compatible;
     return (
-        active_management_keys,
-        active_did_keys,
-        active_services,
-        skipped_entries,
+        processor,
+        vec![ext_ids[0], compatible_version.encode()] + ext_ids[2..],
     );
 }
+fn _validate_content<T0, T1, T2>(processor: T0, parsed_content: T1, options: T2) {
+    "
+    Validates the parsed content of an entry. In lenient mode, fields which are not allowed by the entry schema are
+    removed from the parsed content instead of failing the validation.
+    ";
+    if !options.is_lenient() {
+        processor.validate_content(parsed_content);
+        return;
+    }
+    loop {
+        let try_dummy = {
+            //unsupported
+            processor.validate_content(parsed_content);
+            return;
+        };
+        let except!(ValidationError as e) = {
+            //unsupported
+            if e.validator != "additionalProperties" || !isinstance(e.instance, dict) {
+                raise!(); //unsupported
+            }
+            let allowed = e.schema.get("properties", HashMap::new());
+            let unknown = e
+                .instance
+                .iter()
+                .filter(|k| allowed.iter().all(|&x| x != k))
+                .collect::<Vec<_>>();
+            if !unknown {
+                raise!(); //unsupported
+            }
+            for key in unknown {
+                e.instance.drop(key);
+            }
+        };
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+use std::*;
+
+use factom_did::client::constants::ENTRY_SCHEMA_V100;
+use factom_did::client::enums::EntryType;
+use factom_did::resolver::entry_processors::{
+    DIDDeactivationEntryProcessorV100, DIDManagementEntryProcessorV100,
+    DIDMethodVersionUpgradeEntryProcessorV100, DIDUpdateEntryProcessorV100,
+};
+use factom_did::resolver::method_versions::DID_METHOD_SPEC_V020_RULES;
+let all = vec!["EntryProcessor", "EntryProcessorRegistry"];
+// Processes a single type of entry in a DIDManagement chain, for a given entry schema version.
+//
+// The parser calls the methods in the following order: validate_ext_ids, parse_content, validate_content and process.
+// For the first entry in the chain, any exception raised by these methods makes the chain invalid. For any subsequent
+// entry, a validate_ext_ids result of False or an exception raised by parse_content or validate_content makes the
+// parser skip the entry.
+trait EntryProcessor {
+    // Validates the ExtIDs of the entry.
+    //
+    // Parameters
+    // ----------
+    // ext_ids: list of bytes
+    // state: DIDChainState
+    //
+    // Returns
+    // -------
+    // bool
+    //     True if the ExtIDs are valid, False otherwise.
+    fn validate_ext_ids<T0, T1, RT>(&self, ext_ids: T0, state: T1) -> RT;
+    // Decodes the entry content.
+    //
+    // Raises
+    // ------
+    // UnicodeDecodeError, JSONDecodeError
+    //     If the content cannot be decoded
+    fn parse_content<T0, RT>(&self, binary_content: T0) -> RT;
+    // Validates the decoded entry content.
+    //
+    // Raises
+    // ------
+    // ValidationError
+    //     If the content is not valid
+    fn validate_content<T0>(&self, parsed_content: T0);
+    // Applies the logic rules for the entry and updates the chain state accordingly, including the number of skipped
+    // entries and whether parsing should continue.
+    //
+    // Parameters
+    // ----------
+    // ext_ids: list of bytes
+    // binary_content: bytes
+    // parsed_content: dict or str
+    // state: DIDChainState
+    // registry: EntryProcessorRegistry
+    //     The registry the processor was found in, which provides the rules of the DID method versions
+    fn process<T0, T1, T2, T3, T4>(
+        &self,
+        ext_ids: T0,
+        binary_content: T1,
+        parsed_content: T2,
+        state: T3,
+        registry: T4,
+    );
+}
+struct EntryProcessorRegistry {
+    processors: HashMap<_, _>,
+    method_versions: HashMap<_, _>,
+}
+
+impl EntryProcessorRegistry {
+    "
+    A registry of entry processors, keyed by (entry schema version, entry type), and of the rules of the DID method
+    specification versions, keyed by version.
+
+    New schema versions, experimental entry types or DID method versions can be supported by registering additional
+    processors or rules, without changes to the parser.
+    ";
+    fn init(&self) {
+        self.processors = HashMap::new();
+        self.method_versions = HashMap::new();
+    }
+    fn default<RT>() -> RT {
+        "
+        Returns
+        -------
+        EntryProcessorRegistry
+            A registry containing the processors for all entry types and the rules for all versions defined by the DID
+            method specification
+        ";
+        let registry = EntryProcessorRegistry();
+        registry.register(ENTRY_SCHEMA_V100, EntryType::Create, DIDManagementEntryProcessorV100());
+        registry.register(ENTRY_SCHEMA_V100, EntryType::Update, DIDUpdateEntryProcessorV100());
+        registry.register(
+            ENTRY_SCHEMA_V100,
+            EntryType::VersionUpgrade,
+            DIDMethodVersionUpgradeEntryProcessorV100(),
+        );
+        registry.register(
+            ENTRY_SCHEMA_V100,
+            EntryType::Deactivation,
+            DIDDeactivationEntryProcessorV100(),
+        );
+        registry.register_method_version(DID_METHOD_SPEC_V020_RULES);
+        return registry;
+    }
+    fn register<T0, T1, T2, RT>(&self, schema_version: T0, entry_type: T1, processor: T2) -> RT {
+        "
+        Registers a processor, replacing any processor already registered for the same schema version and entry type.
+
+        Parameters
+        ----------
+        schema_version: str
+            The entry schema version, e.g. 1.0.0
+        entry_type: EntryType or str
+            The entry type. Custom entry types can be given as strings.
+        processor: EntryProcessor
+
+        Returns
+        -------
+        EntryProcessorRegistry
+            The registry, to allow chaining
+        ";
+        self.processors[(schema_version, _entry_type_value(entry_type))] = processor;
+        return self;
+    }
+    fn unregister<T0, T1>(&self, schema_version: T0, entry_type: T1) {
+        "
+        Removes the processor registered for the given schema version and entry type, if any.
+        ";
+        self.processors.pop((schema_version, _entry_type_value(entry_type)), None);
+    }
+    fn get<T0, T1, RT>(&self, schema_version: T0, entry_type: T1) -> RT {
+        "
+        Returns
+        -------
+        EntryProcessor
+            The processor for the given schema version and entry type, or None if there is none
+        ";
+        return self.processors.get((schema_version, _entry_type_value(entry_type)));
+    }
+    fn get_compatible<T0, T1, RT>(&self, schema_version: T0, entry_type: T1) -> RT {
+        "
+        Finds the processor for the latest registered schema version which has the same major version as the given
+        one and is not newer than it.
+
+        Returns
+        -------
+        tuple
+            A 2-tuple containing the registered schema version and its processor, or None if there is no compatible
+            processor
+        ";
+        let requested = _parse_schema_version(schema_version);
+        if requested == None {
+            return None;
+        }
+        let entry_type = _entry_type_value(entry_type);
+        let mut best = None;
+        for ((version, registered_type), processor) in self.processors.items() {
+            let registered = _parse_schema_version(version);
+            if registered_type != entry_type
+                || registered == None
+                || registered[0] != requested[0]
+                || registered > requested
+            {
+                continue;
+            }
+            if best == None || registered > _parse_schema_version(best[0]) {
+                best = (version, processor);
+            }
+        }
+        return best;
+    }
+    fn register_method_version<T0, RT>(&self, rules: T0) -> RT {
+        "
+        Registers the rules of a DID method specification version, replacing any rules registered for the same version.
+
+        Parameters
+        ----------
+        rules: MethodVersionRules
+
+        Returns
+        -------
+        EntryProcessorRegistry
+            The registry, to allow chaining
+        ";
+        self.method_versions[rules.version] = rules;
+        return self;
+    }
+    fn get_method_version_rules<T0, RT>(&self, version: T0) -> RT {
+        "
+        Returns
+        -------
+        MethodVersionRules
+            The rules of the given DID method specification version, or None if the version is not supported
+        ";
+        return self.method_versions.get(version);
+    }
+    fn is_supported_method_version<T0, RT>(&self, version: T0) -> RT {
+        "
+        Returns
+        -------
+        bool
+            True if DIDs at the given DID method specification version can be resolved with this registry
+        ";
+        return self.method_versions.iter().any(|&x| x == version);
+    }
+    fn copy<RT>(&self) -> RT {
+        "
+        Returns
+        -------
+        EntryProcessorRegistry
+            A copy of the registry, which can be extended without affecting this registry
+        ";
+        let registry = EntryProcessorRegistry();
+        registry.processors = self.processors.copy();
+        registry.method_versions = self.method_versions.copy();
+        return registry;
+    }
+}
+fn _entry_type_value<T0, RT>(entry_type: T0) -> RT {
+    return if isinstance(entry_type, str) { entry_type } else { entry_type.value };
+}
+fn _parse_schema_version<T0, RT>(schema_version: T0) -> RT {
+    let try_dummy = {
+        //unsupported
+        return tuple(schema_version.split(".").iter().map(int).collect::<Vec<_>>());
+    };
+    let except!(ValueError) = {
+        //unsupported
+        return None;
+    };
+}
@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+use std::*;
+
+use factom_did::client::did::DID;
+use factom_did::client::validators::validate_did;
+use factom_did::resolver::chain_state::DIDChainState;
+use factom_did::resolver::document::build_did_document;
+use factom_did::resolver::options::ResolverOptions;
+use factom_did::resolver::parser::{apply_entries, parse_did_chain_entries, resolve_incremental};
+let all = vec!["DIDResolver"];
+struct DIDResolver {
+    factomd: ST0,
+    options: ST1,
+}
+
+impl DIDResolver {
+    "
+    Resolves DIDs recorded on the Factom blockchain by reading the DIDManagement chain from a factomd node and
+    re-constructing the currently active DID Document with the parser.
+
+    Attributes
+    ----------
+    factomd: obj
+        Factomd instance, instantiated from the Python factom-api package.
+    options: ResolverOptions, optional
+        The resolution options. Defaults to spec-compliant resolution.
+    ";
+    fn init<T0, T1>(&self, factomd: T0, options: T1) {
+        self.factomd = factomd;
+        self.options = if options == None {
+            ResolverOptions()
+        } else {
+            options
+        };
+    }
+    fn resolve<T0, RT>(&self, did: T0) -> RT {
+        "
+        Resolves the given DID.
+
+        Parameters
+        ----------
+        did: str
+            The DID to resolve, e.g. did:factom:mainnet:<chain-id>
+
+        Returns
+        -------
+        tuple
+            A 4-tuple containing the active management keys, the active DID key, the active services and the number of
+            entries skipped while parsing the chain, as returned by parse_did_chain_entries. A deactivated DID resolves
+            to empty keys and services, like a DID which has none; use resolve_state, which reports the deactivation
+            metadata, to tell them apart.
+
+        Raises
+        ------
+        ValueError
+            If the DID is not valid
+        InvalidDIDChain
+            If the first entry in the chain is not a valid DIDManagement entry
+        InvalidDIDChainEntry
+            If resolving in strict mode and an entry in the chain is not valid
+        ";
+        validate_did(did);
+        let chain_id = did.split(":")[-1];
+        let network = DID::_get_network_from_id(did);
+        let entries = self.read_entries(chain_id);
+        return parse_did_chain_entries(entries, chain_id, network, None, self.options);
+    }
+    fn resolve_document<T0, RT>(&self, did: T0) -> RT {
+        "
+        Resolves the given DID to a W3C DID Document, with the public keys in the representation selected in the
+        resolver options.
+
+        Parameters
+        ----------
+        did: str
+
+        Returns
+        -------
+        dict
+            The DID Document, as built by build_did_document
+
+        Raises
+        ------
+        ValueError
+            If the DID is not valid
+        InvalidDIDChain
+            If the first entry in the chain is not a valid DIDManagement entry
+        ";
+        let (management_keys, did_keys, services, _) = self.resolve(did);
+        return build_did_document(
+            did,
+            management_keys,
+            did_keys,
+            services,
+            self.options.public_key_format,
+        );
+    }
+    fn resolve_state<T0, T1, RT>(&self, did: T0, until_entry_hash: T1) -> RT {
+        "
+        Resolves the given DID to a DIDState, optionally at a historical version. If the DID has been deactivated, the
+        DIDState contains the deactivation metadata. This is the supported way of resolving a DID; resolve is kept for
+        compatibility with parse_did_chain_entries.
+
+        Parameters
+        ----------
+        did: str
+            The DID to resolve
+        until_entry_hash: bytes, optional
+            If provided, only the entries up to and including the entry with this hash are processed, yielding the
+            state of the DID right after that entry was recorded
+
+        Returns
+        -------
+        DIDState
+
+        Raises
+        ------
+        ValueError
+            If the DID is not valid or if the chain does not contain an entry with the given hash
+        InvalidDIDChain
+            If the first entry in the chain is not a valid DIDManagement entry
+        InvalidDIDChainEntry
+            If resolving in strict mode and an entry in the chain is not valid
+        ";
+        validate_did(did);
+        let chain_id = did.split(":")[-1];
+        let network = DID::_get_network_from_id(did);
+        let entries = self.read_entries(chain_id);
+        if until_entry_hash != None {
+            let entry_hashes = entries.iter().map(|e| e["entryhash"]).collect::<Vec<_>>();
+            if entry_hashes.iter().all(|&x| x != until_entry_hash) {
+                raise!(ValueError(
+                    "Entry {} not found in chain {}".format(until_entry_hash, chain_id)
+                )); //unsupported
+            }
+            entries = entries[..(entry_hashes.index(until_entry_hash) + 1)];
+        }
+        let state = DIDChainState(chain_id, network);
+        apply_entries(state, entries, None, self.options);
+        return state.to_did_state(did);
+    }
+    fn resolve_state_at<T0, T1, RT>(&self, did: T0, timestamp: T1) -> RT {
+        "
+        Resolves the state of the given DID at a point in time, e.g. to check that a key was active when a credential
+        was issued.
+
+        Parameters
+        ----------
+        did: str
+            The DID to resolve
+        timestamp: int or float
+            A UNIX timestamp. Only the entries recorded at or before this time are processed.
+
+        Returns
+        -------
+        DIDState
+            The state of the DID at the given time. If the DID did not exist yet, the state has no keys or services.
+
+        Raises
+        ------
+        ValueError
+            If the DID is not valid
+        InvalidDIDChain
+            If the first entry in the chain is not a valid DIDManagement entry
+        InvalidDIDChainEntry
+            If resolving in strict mode and an entry in the chain is not valid
+        ";
+        validate_did(did);
+        let chain_id = did.split(":")[-1];
+        let entries = self
+            .read_entries(chain_id)
+            .iter()
+            .filter(|e| e.get("timestamp") != None && e["timestamp"] <= timestamp)
+            .collect::<Vec<_>>();
+        let state = DIDChainState(chain_id, DID::_get_network_from_id(did));
+        apply_entries(state, entries, None, self.options);
+        return state.to_did_state(did);
+    }
+    fn resolve_incremental<T0, T1, RT>(&self, did: T0, snapshot: T1) -> RT {
+        "
+        Resolves the given DID, resuming from a previously saved chain state. Only the entries recorded after the
+        entries processed in the snapshot are processed.
+
+        Parameters
+        ----------
+        did: str
+            The DID to resolve
+        snapshot: DIDChainState or dict or str, optional
+            The saved chain state. If not provided, the whole chain is processed.
+
+        Returns
+        -------
+        DIDChainState
+            The updated chain state, which can be saved and used as the snapshot for the next resolution
+
+        Raises
+        ------
+        ValueError
+            If the DID is not valid, if the snapshot is for a different chain or if the last entry in the snapshot is
+            not at the same position in the chain
+        InvalidDIDChain
+            If the first entry in the chain is not a valid DIDManagement entry
+        InvalidDIDChainEntry
+            If resolving in strict mode and an entry in the chain is not valid
+        ";
+        validate_did(did);
+        let chain_id = did.split(":")[-1];
+        if snapshot == None {
+            snapshot = DIDChainState(chain_id, DID::_get_network_from_id(did));
+        }
+        let state = resolve_incremental(snapshot, vec![], None, self.options);
+        if state.chain_id != chain_id {
+            raise!(ValueError(
+                "Snapshot is for chain {}, not {}".format(state.chain_id, chain_id)
+            )); //unsupported
+        }
+        let entries = self.read_entries(chain_id);
+        // Resume by position rather than by hash: the same entry can be recorded several times in a chain
+        if state.processed_entries > 0 {
+            if entries.len() < state.processed_entries
+                || entries[state.processed_entries - 1]["entryhash"] != state.last_entry_hash
+            {
+                raise!(ValueError(
+                    "Entry {} not found at position {} in chain {}".format(
+                        state.last_entry_hash,
+                        state.processed_entries,
+                        chain_id
+                    )
+                )); //unsupported
+            }
+            entries = entries[state.processed_entries..];
+        }
+        return resolve_incremental(state, entries, None, self.options);
+    }
+    fn key_history<T0, RT>(&self, did: T0) -> RT {
+        "
+        Returns the history of all management and DID keys of the given DID.
+
+        Parameters
+        ----------
+        did: str
+
+        Returns
+        -------
+        list of dict
+            One record for each period during which a key was active, in the order in which the keys were added. Each
+            record has the keys 'alias', 'keyKind' ('managementKey' or 'didKey'), 'type', 'publicKeyField',
+            'publicKey', 'addedIn' and 'revokedIn', where the last two are the hashes of the entries which added and
+            revoked the key. 'revokedIn' is None if the key is still active.
+
+        Raises
+        ------
+        ValueError
+            If the DID is not valid
+        InvalidDIDChain
+            If the first entry in the chain is not a valid DIDManagement entry
+        InvalidDIDChainEntry
+            If resolving in strict mode and an entry in the chain is not valid
+        ";
+        return self.resolve_incremental(did, None).key_history;
+    }
+    fn is_key_ever_used<T0, T1, RT>(&self, did: T0, public_key: T1) -> RT {
+        "
+        Checks whether the given public key has been active at some point for the given DID, as a management or DID key.
+        Keys which have been revoked are included, so this can be used to prove that a rotated-out key has never been
+        re-added.
+
+        Parameters
+        ----------
+        did: str
+        public_key: bytes or str
+            The public key, either as raw bytes or in its on-chain representation (base58 or PEM)
+
+        Returns
+        -------
+        bool
+
+        Raises
+        ------
+        ValueError
+            If the DID is not valid
+        InvalidDIDChain
+            If the first entry in the chain is not a valid DIDManagement entry
+        InvalidDIDChainEntry
+            If resolving in strict mode and an entry in the chain is not valid
+        ";
+        return self.resolve_incremental(did, None).is_key_ever_used(public_key);
+    }
+    fn read_entries<T0, RT>(&self, chain_id: T0) -> RT {
+        "
+        Reads all entries in the given chain, in the order in which they were recorded.
+
+        Parameters
+        ----------
+        chain_id: str
+
+        Returns
+        -------
+        list of dict
+            The entries in the chain. Each element is a dictionary with keys 'content', 'extids' and 'entryhash'. If
+            the factomd library also provides the 'timestamp' key, it is used for the deactivation metadata.
+        ";
+        return self.factomd.read_chain(chain_id, 0, true);
+    }
+}
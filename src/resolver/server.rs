@@ -0,0 +1,77 @@
+use std::*;
+
+use factom::Factomd;
+use factom_did::resolver::cache::{CachePolicy, CachingResolver};
+use factom_did::resolver::driver::UniversalResolverDriver;
+use factom_did::resolver::options::{ResolutionMode, ResolverOptions};
+use factom_did::resolver::resolver::DIDResolver;
+use http::server::{BaseHTTPRequestHandler, ThreadingHTTPServer};
+let all = vec!["serve", "serve_from_environment"];
+struct DriverRequestHandler {}
+
+impl BaseHTTPRequestHandler for DriverRequestHandler {
+    fn do_GET(&self) {
+        let (status, content_type, body) = self
+            .server
+            .driver
+            .handle_request(self.path.split("?")[0], self.headers.get("Accept"));
+        let body = if isinstance(body, str) {
+            body.encode("utf-8")
+        } else {
+            body
+        };
+        self.send_response(status);
+        self.send_header("Content-Type", content_type);
+        self.send_header("Content-Length", str(body.len()));
+        self.end_headers();
+        self.wfile.write(body);
+    }
+}
+fn serve<T0, T1, T2, T3, T4>(factomd_host: T0, host: T1, port: T2, cache_ttl: T3, options: T4) {
+    "
+    Serves a DIF Universal Resolver driver for the Factom DID method over HTTP, until the process is stopped.
+
+    Parameters
+    ----------
+    factomd_host: str
+        URL of the factomd API
+    host: str
+        Interface on which the server listens
+    port: int
+        Port on which the server listens
+    cache_ttl: int
+        Number of seconds during which resolution results are served without checking the chain head
+    options: ResolverOptions, optional
+        The resolution options. Defaults to spec-compliant resolution.
+    ";
+    let resolver = CachingResolver(
+        DIDResolver(Factomd(factomd_host), options),
+        None,
+        CachePolicy(cache_ttl, 0),
+    );
+    let server = ThreadingHTTPServer((host, port), DriverRequestHandler);
+    server.driver = UniversalResolverDriver(resolver);
+    server.serve_forever();
+}
+fn serve_from_environment() {
+    "
+    Serves the driver with the configuration read from the environment:
+
+        FACTOMD_HOST          URL of the factomd API (default: http://localhost:8088)
+        RESOLVER_HOST         Interface on which the server listens (default: 0.0.0.0)
+        RESOLVER_PORT         Port on which the server listens (default: 8080)
+        RESOLVER_CACHE_TTL    Number of seconds during which resolution results are served without checking the chain
+                              head (default: 0)
+        RESOLVER_MODE         Resolution mode: strict, spec-compliant or lenient (default: spec-compliant)
+    ";
+    serve(
+        os.environ.get("FACTOMD_HOST", "http://localhost:8088"),
+        os.environ.get("RESOLVER_HOST", "0.0.0.0"),
+        int(os.environ.get("RESOLVER_PORT", "8080")),
+        int(os.environ.get("RESOLVER_CACHE_TTL", "0")),
+        ResolverOptions(
+            ResolutionMode::from_str(os.environ.get("RESOLVER_MODE", "spec-compliant")),
+            None,
+        ),
+    );
+}
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::*;
+
+let all = vec!["DIDState"];
+struct DIDState {
+    did: ST0,
+    management_keys: HashMap<_, _>,
+    did_keys: HashMap<_, _>,
+    services: HashMap<_, _>,
+    skipped_entries: ST1,
+    deactivation: ST2,
+}
+
+impl DIDState {
+    "
+    A resolved state of a DID: the management keys, DID keys and services active after a given number of entries in
+    the DIDManagement chain have been processed.
+
+    Attributes
+    ----------
+    did: str
+        The DID
+    management_keys: dict
+        The active management keys, keyed by alias
+    did_keys: dict
+        The active DID keys, keyed by alias
+    services: dict
+        The active services, keyed by alias
+    skipped_entries: int
+        The number of entries skipped while parsing the chain
+    deactivation: DeactivationMetadata, optional
+        The metadata of the DIDDeactivation entry, if the DID has been deactivated
+    ";
+    fn init<T0, T1, T2, T3, T4, T5>(
+        &self,
+        did: T0,
+        management_keys: T1,
+        did_keys: T2,
+        services: T3,
+        skipped_entries: T4,
+        deactivation: T5,
+    ) {
+        self.did = did;
+        self.management_keys = management_keys;
+        self.did_keys = did_keys;
+        self.services = services;
+        self.skipped_entries = skipped_entries;
+        self.deactivation = deactivation;
+    }
+    fn represent<RT>(&self) -> RT {
+        return "<{}.{}(did={}, management_keys={}, did_keys={}, services={})>".format(
+            self.module,
+            type_(self).__name__,
+            self.did,
+            self.management_keys.len(),
+            self.did_keys.len(),
+            self.services.len(),
+        );
+    }
+    fn is_deactivated<RT>(&self) -> RT {
+        "
+        Returns
+        -------
+        bool
+            True if the DID has been deactivated. A deactivated DID has no active keys or services, as opposed to a DID
+            which has not been created.
+        ";
+        return self.deactivation != None;
+    }
+    fn from_parse_result<T0, T1, RT>(did: T0, parse_result: T1) -> RT {
+        "
+        Creates a DIDState from the result of parse_did_chain_entries.
+
+        Parameters
+        ----------
+        did: str
+        parse_result: tuple
+            The 4-tuple returned by parse_did_chain_entries
+
+        Returns
+        -------
+        DIDState
+        ";
+        let (management_keys, did_keys, services, skipped_entries) = parse_result;
+        return DIDState(did, management_keys, did_keys, services, skipped_entries, None);
+    }
+    fn from_did<T0, RT>(did: T0) -> RT {
+        "
+        Creates a DIDState from an in-memory DID object.
+
+        Parameters
+        ----------
+        did: client.did.DID
+
+        Returns
+        -------
+        DIDState
+        ";
+        return DIDState(
+            did.id,
+            did.management_keys.iter().map(|k| (k.alias, k)).collect::<HashMap<_, _>>(),
+            did.did_keys.iter().map(|k| (k.alias, k)).collect::<HashMap<_, _>>(),
+            did.services.iter().map(|s| (s.alias, s)).collect::<HashMap<_, _>>(),
+            0,
+            None,
+        );
+    }
+    fn to_entry_dict<RT>(&self) -> RT {
+        "
+        Converts the state to a dictionary with the same structure as the content of a DIDManagement entry.
+
+        Returns
+        -------
+        dict
+        ";
+        return [
+            (
+                "managementKey",
+                self.management_keys.values().iter().map(|k| k.to_entry_dict(self.did)).collect::<Vec<_>>(),
+            ),
+            (
+                "didKey",
+                self.did_keys.values().iter().map(|k| k.to_entry_dict(self.did)).collect::<Vec<_>>(),
+            ),
+            (
+                "service",
+                self.services.values().iter().map(|s| s.to_entry_dict(self.did)).collect::<Vec<_>>(),
+            ),
+        ]
+        .iter()
+        .cloned()
+        .collect::<HashMap<_, _>>();
+    }
+}
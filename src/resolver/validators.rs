@@ -5,6 +5,7 @@ use factom_did::client::constants::ENTRY_SCHEMA_V100;
 use factom_did::client::enums::{EntryType, Network};
 use factom_did::client::validators::validate_full_key_identifier;
 use factom_did::resolver::exceptions::MalformedDIDManagementEntry;
+use factom_did::resolver::method_versions::DID_METHOD_SPEC_V020_RULES;
 use jsonschema::exceptions::ValidationError;
 struct EmptyEntryContentValidator {}
 
@@ -121,12 +122,12 @@ fn validate_did_deactivation_ext_ids_v100<T0, T1, T2, RT>(
         && validate_management_key_id_against_chain_id(ext_ids[2], chain_id)
         && validate_id_against_network(ext_ids[2], network);
 }
-fn validate_signature<T0, T1, T2, RT>(ext_ids: T0, content: T1, signing_key: T2) -> RT {
+fn validate_signature<T0, T1, T2, T3, RT>(ext_ids: T0, content: T1, signing_key: T2, digest: T3) -> RT {
     "
     Checks if the signature contained in the last element of ext_ids is valid.
 
-    The signature is for a DIDUpdate, DIDMethodVersionUpgrade or DIDDeactivation entry and covers the content of the
-    entry + the first 3 ext_ids. For more details on the signatures of these entries, refer to
+    The signature is for a DIDUpdate, DIDMethodVersionUpgrade or DIDDeactivation entry and covers the digest of the
+    content of the entry + the first 3 ext_ids. For more details on the signatures of these entries, refer to
     https://github.com/bi-foundation/FIS/blob/feature/DID/FIS/DID.md
 
     Parameters
@@ -134,6 +135,8 @@ fn validate_signature<T0, T1, T2, RT>(ext_ids: T0, content: T1, signing_key: T2)
     ext_ids: list of bytes
     content: bytes
     signing_key: ManagementKey
+    digest: str, optional
+        The name of the hashlib hash function applied to the signed data. Defaults to 'sha256'.
 
     Returns
     -------
@@ -144,7 +147,8 @@ fn validate_signature<T0, T1, T2, RT>(ext_ids: T0, content: T1, signing_key: T2)
         signed_data.extend(ext_ids[i]);
     }
     signed_data.extend(content);
-    return signing_key.verify(hashlib.sha256(signed_data).digest(), ext_ids[3]);
+    let digest = if digest == None { "sha256" } else { digest };
+    return signing_key.verify(hashlib.new(digest, signed_data).digest(), ext_ids[3]);
 }
 fn validate_management_key_id_against_chain_id<T0, T1, RT>(key_id: T0, chain_id: T1) -> RT {
     "
@@ -170,7 +174,7 @@ fn validate_management_key_id_against_chain_id<T0, T1, RT>(key_id: T0, chain_id:
         key_id = key_id.decode();
     }
     if key_id.iter().any(|&x| x == ":") {
-        let key_id_chain = key_id.split(":")[-1].split("#")[0];
+        let key_id_chain = key_id.split(":")[-1].split(DID_METHOD_SPEC_V020_RULES.id_separator)[0];
         return key_id_chain == chain_id;
     } else {
         return true;
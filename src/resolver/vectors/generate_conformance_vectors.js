@@ -0,0 +1,213 @@
+/*
+ * Generates conformance-node.json, conformance test vectors for the Factom DID method computed with Node.js.
+ *
+ * This is an independent JavaScript implementation of the entry serialization, chain ID, entry size, entry hash and
+ * signature algorithms, using only the Node.js standard library. It builds the same chains as
+ * generate_conformance_vectors.py, but from different key seeds and nonces, so that the two files contain different
+ * vectors computed by different implementations. The expected resolution outcomes are written by hand. Unlike
+ * generate_conformance_vectors.py, it does not use the reference factom-did JavaScript library yet.
+ *
+ * Usage: node generate_conformance_vectors.js
+ */
+"use strict";
+
+const crypto = require("crypto");
+const fs = require("fs");
+const path = require("path");
+
+const B58 = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+// PKCS #8 prefix of an Ed25519 private key, followed by the 32-byte seed
+const ED25519_PKCS8_PREFIX = Buffer.from("302e020100300506032b657004220420", "hex");
+
+function b58(bytes) {
+  let n = BigInt("0x" + (bytes.toString("hex") || "0"));
+  let s = "";
+  while (n > 0n) {
+    s = B58[Number(n % 58n)] + s;
+    n /= 58n;
+  }
+  let zeros = 0;
+  while (zeros < bytes.length && bytes[zeros] === 0) zeros++;
+  return "1".repeat(zeros) + s;
+}
+
+const sha256 = (data) => crypto.createHash("sha256").update(data).digest();
+const sha512 = (data) => crypto.createHash("sha512").update(data).digest();
+
+const ORDER = ["didMethodVersion", "revoke", "add", "managementKey", "didKey", "service", "id", "type", "controller",
+  "serviceEndpoint", "publicKeyBase58", "publicKeyPem", "priorityRequirement", "priority", "purpose"];
+
+function order(value) {
+  if (Array.isArray(value)) return value.map(order);
+  if (value !== null && typeof value === "object") {
+    const keys = ORDER.filter((k) => k in value).concat(Object.keys(value).filter((k) => !ORDER.includes(k)));
+    const ordered = {};
+    for (const k of keys) ordered[k] = order(value[k]);
+    return ordered;
+  }
+  return value;
+}
+
+const dumps = (content) => JSON.stringify(order(content));
+
+function u16(n) {
+  const b = Buffer.alloc(2);
+  b.writeUInt16BE(n);
+  return b;
+}
+
+const chainId = (extIds) => sha256(Buffer.concat(extIds.map(sha256))).toString("hex");
+const entrySize = (extIds, content) =>
+  35 + 2 * extIds.length + extIds.reduce((n, e) => n + e.length, 0) + content.length;
+
+function entryHash(cid, extIds, content) {
+  const parts = [Buffer.from([0]), Buffer.from(cid, "hex"), u16(extIds.reduce((n, e) => n + 2 + e.length, 0))];
+  for (const e of extIds) parts.push(u16(e.length), e);
+  parts.push(content);
+  const data = Buffer.concat(parts);
+  return sha256(Buffer.concat([sha512(data), data])).toString("hex");
+}
+
+class Key {
+  constructor(alias, label) {
+    this.alias = alias;
+    this.seed = sha256(Buffer.from("factom-did node conformance " + label));
+    this.privateKey = crypto.createPrivateKey({
+      key: Buffer.concat([ED25519_PKCS8_PREFIX, this.seed]),
+      format: "der",
+      type: "pkcs8",
+    });
+    const spki = crypto.createPublicKey(this.privateKey).export({ format: "der", type: "spki" });
+    this.publicKey = spki.subarray(spki.length - 32);
+  }
+
+  entry(did, extra) {
+    return Object.assign({
+      id: `${did}#${this.alias}`,
+      type: "Ed25519VerificationKey",
+      controller: did,
+      publicKeyBase58: b58(this.publicKey),
+    }, extra);
+  }
+
+  sign(message) {
+    return crypto.sign(null, sha256(sha256(message)), this.privateKey);
+  }
+}
+
+function signed(entryType, key, did, content) {
+  const keyId = `${did}#${key.alias}`;
+  const signature = key.sign(Buffer.from(entryType + "1.0.0" + keyId + content));
+  return [[Buffer.from(entryType), Buffer.from("1.0.0"), Buffer.from(keyId), signature], Buffer.from(content)];
+}
+
+function makeChain(label, keys) {
+  const nonce = sha256(Buffer.from("factom-did node conformance nonce " + label));
+  const createExtIds = [Buffer.from("DIDManagement"), Buffer.from("1.0.0"), nonce];
+  const cid = chainId(createExtIds);
+  const did = `did:factom:testnet:${cid}`;
+  const content = dumps({
+    didMethodVersion: "0.2.0",
+    managementKey: [keys["man-key-1"].entry(did, { priority: 0 }), keys["man-key-2"].entry(did, { priority: 1 })],
+    didKey: [keys["did-key-1"].entry(did, { purpose: ["publicKey", "authentication"] })],
+    service: [{ id: `${did}#inbox`, type: "SocialInbox", serviceEndpoint: "https://example.com/inbox" }],
+  });
+  return [cid, did, [[createExtIds, Buffer.from(content)]]];
+}
+
+function entryJson(cid, extIds, content, timestamp, validSignature = true) {
+  const entry = {
+    extIds: extIds.map((e) => e.toString("hex")),
+    content: content.toString(),
+    entryHash: entryHash(cid, extIds, content),
+    entrySize: entrySize(extIds, content),
+    timestamp,
+  };
+  if (extIds.length === 4) entry.validSignature = validSignature;
+  return entry;
+}
+
+function vector(name, description, label, build, expected) {
+  const keys = {};
+  for (const alias of ["man-key-1", "man-key-2", "did-key-1", "man-key-3"]) {
+    keys[alias] = new Key(alias, `${label} ${alias}`);
+  }
+  const [cid, did, entries] = makeChain(label, keys);
+  build(did, keys, entries);
+  const privateKeys = {};
+  for (const [alias, key] of Object.entries(keys)) privateKeys[alias] = key.seed.toString("hex");
+  return {
+    name,
+    description,
+    network: "testnet",
+    chainId: cid,
+    privateKeys,
+    entries: entries.map(([extIds, content, ...rest], i) => entryJson(cid, extIds, content, 1600000000 + 60 * i, ...rest)),
+    expected,
+  };
+}
+
+function update(did, signer, add, revoke) {
+  const content = {};
+  if (revoke) content.revoke = revoke;
+  if (add) content.add = add;
+  return signed("DIDUpdate", signer, did, dumps(content));
+}
+
+function lifecycle(did, k, entries) {
+  entries.push(update(did, k["man-key-1"], { managementKey: [k["man-key-3"].entry(did, { priority: 1 })] },
+    { service: [{ id: "inbox" }] }));
+  entries.push(update(did, k["man-key-2"], null, { didKey: [{ id: "did-key-1", purpose: ["authentication"] }] }));
+  entries.push(signed("DIDMethodVersionUpgrade", k["man-key-1"], did, dumps({ didMethodVersion: "0.3.0" })));
+}
+
+function deactivation(did, k, entries) {
+  entries.push(signed("DIDDeactivation", k["man-key-1"], did, ""));
+  entries.push(update(did, k["man-key-1"], { managementKey: [k["man-key-3"].entry(did, { priority: 1 })] }));
+}
+
+function adversarial(did, k, entries) {
+  const [extIds, content] = update(did, k["man-key-1"], { managementKey: [k["man-key-3"].entry(did, { priority: 1 })] });
+  const bad = Buffer.from(extIds[3]);
+  bad[0] ^= 1;
+  entries.push([extIds.slice(0, 3).concat([bad]), content, false]);
+  // man-key-2 (priority 1) may not add a priority 0 key
+  entries.push(update(did, k["man-key-2"], { managementKey: [k["man-key-3"].entry(did, { priority: 0 })] }));
+  // man-key-2 may not deactivate the DID
+  entries.push(signed("DIDDeactivation", k["man-key-2"], did, ""));
+  // a downgrade is not a version upgrade
+  entries.push(signed("DIDMethodVersionUpgrade", k["man-key-1"], did, dumps({ didMethodVersion: "0.1.0" })));
+  const valid = update(did, k["man-key-2"], null, { service: [{ id: "inbox" }] });
+  entries.push(valid);
+  entries.push(valid);
+}
+
+const vectors = [
+  vector("node-did-management", "A DIDManagement entry with two management keys, a DID key and a service",
+    "management", () => {},
+    { managementKeys: ["man-key-1", "man-key-2"], didKeys: { "did-key-1": ["publicKey", "authentication"] },
+      services: ["inbox"], skippedEntries: 0, didMethodVersion: "0.2.0", deactivated: false }),
+  vector("node-did-update-and-version-upgrade", "DIDUpdate entries signed by keys of different priorities, followed " +
+    "by a DIDMethodVersionUpgrade to 0.3.0, which is skipped as the resolver has no rules for that version",
+  "lifecycle", lifecycle,
+  { managementKeys: ["man-key-1", "man-key-2", "man-key-3"], didKeys: { "did-key-1": ["publicKey"] },
+    services: [], skippedEntries: 1, didMethodVersion: "0.2.0", deactivated: false }),
+  vector("node-did-deactivation", "A DIDDeactivation entry, followed by an entry which must be ignored",
+    "deactivation", deactivation,
+    { managementKeys: [], didKeys: {}, services: [], skippedEntries: 1, didMethodVersion: "0.2.0",
+      deactivated: true }),
+  vector("node-adversarial", "Entries with a bad signature, priority violations, a version downgrade and a " +
+    "duplicate entry hash", "adversarial", adversarial,
+  { managementKeys: ["man-key-1", "man-key-2"], didKeys: { "did-key-1": ["publicKey", "authentication"] },
+    services: [], skippedEntries: 5, didMethodVersion: "0.2.0", deactivated: false }),
+];
+
+const out = {
+  description: "Conformance test vectors for the Factom DID method: entry content serialization, chain IDs, entry " +
+    "sizes, entry hashes, Ed25519 signatures and resolution outcomes. Computed with an independent JavaScript " +
+    "implementation running on Node.js.",
+  generator: { script: "generate_conformance_vectors.js", node: process.version },
+  entrySchemaVersion: "1.0.0",
+  vectors,
+};
+fs.writeFileSync(path.join(__dirname, "conformance-node.json"), JSON.stringify(out, null, 2) + "\n");
@@ -0,0 +1,174 @@
+"""
+Generates conformance.json, the conformance test vectors for the Factom DID method.
+
+The entries are built, serialized and signed by the reference factom-did Python package, pinned to
+FACTOM_DID_VERSION, and the expected resolution outcomes are the output of its parse_did_chain_entries. Keys are
+created from fixed seeds and chains from fixed nonces, so that the vectors are reproducible. Entries which the
+library refuses to export (signatures by a key other than the highest priority one, a version downgrade and a
+corrupted signature) are signed with the library's own keys. The entry hash is Factom's entry hash, which the
+factom-did package does not expose.
+
+Usage: pip install factom-did==<FACTOM_DID_VERSION> && python3 generate_conformance_vectors.py
+"""
+import hashlib
+import json
+import os
+import platform
+from importlib.metadata import version
+
+from factom_did.client.blockchain import calculate_chain_id, calculate_entry_size
+from factom_did.client.constants import ENTRY_SCHEMA_V100
+from factom_did.client.did import DID
+from factom_did.client.enums import DIDKeyPurpose, EntryType, KeyType, Network
+from factom_did.client.keys.did import DIDKey
+from factom_did.client.keys.management import ManagementKey
+from factom_did.client.service import Service
+from factom_did.resolver.parser import parse_did_chain_entries
+
+FACTOM_DID_VERSION = "0.7.0"
+if version("factom-did") != FACTOM_DID_VERSION:
+    raise SystemExit(f"factom-did {FACTOM_DID_VERSION} is required, found {version('factom-did')}")
+
+
+def to_bytes(value):
+    return value if isinstance(value, bytes) else value.encode("utf-8")
+
+
+def entry_hash(cid, ext_ids, content):
+    data = b"\0" + bytes.fromhex(cid) + sum(2 + len(e) for e in ext_ids).to_bytes(2, "big")
+    for e in ext_ids:
+        data += len(e).to_bytes(2, "big") + e
+    data += content
+    return hashlib.sha256(hashlib.sha512(data).digest() + data).hexdigest()
+
+
+def seed(label):
+    return hashlib.sha256(("factom-did conformance " + label).encode()).digest()
+
+
+def management_key(did_id, label, alias, priority):
+    return ManagementKey(alias, priority, KeyType.EdDSA, did_id, private_key=seed(f"{label} {alias}"))
+
+
+def make_did(label):
+    nonce = seed("nonce " + label)
+    cid = calculate_chain_id([EntryType.Create.value, ENTRY_SCHEMA_V100, nonce])
+    did_id = f"did:factom:testnet:{cid}"
+    did = DID(did_id,
+              [management_key(did_id, label, "man-key-1", 0), management_key(did_id, label, "man-key-2", 1)],
+              [DIDKey("did-key-1", [DIDKeyPurpose.PublicKey, DIDKeyPurpose.AuthenticationKey], KeyType.EdDSA, did_id,
+                      private_key=seed(f"{label} did-key-1"))],
+              [Service("inbox", "SocialInbox", "https://example.com/inbox")])
+    did.nonce = nonce
+    return cid, did
+
+
+def exported(entry_data):
+    return [to_bytes(e) for e in entry_data["ext_ids"]], to_bytes(entry_data["content"])
+
+
+def signed_by(key, did, entry_type, content):
+    """Signs entry content with a key of the DID, the way the factom-did updater and deactivator do."""
+    key_id = key.full_id(did.id)
+    data_to_sign = "".join([entry_type.value, ENTRY_SCHEMA_V100, key_id]).encode("utf-8") + content
+    signature = key.sign(hashlib.sha256(data_to_sign).digest())
+    return [entry_type.value.encode("utf-8"), ENTRY_SCHEMA_V100.encode("utf-8"), key_id.encode("utf-8"), signature], content
+
+
+def find_key(did, alias):
+    return next(k for k in did.management_keys if k.alias == alias)
+
+
+def lifecycle(label, did, entries):
+    updater = did.update()
+    updater.did.management_keys.append(management_key(did.id, label, "man-key-3", 1))
+    updater.revoke_service("inbox")
+    entries.append(exported(updater.export_entry_data()))
+    did = updater.get_updated()
+    updater = did.update().revoke_did_key_purpose("did-key-1", DIDKeyPurpose.AuthenticationKey)
+    _, content = exported(updater.export_entry_data())
+    entries.append(signed_by(find_key(did, "man-key-2"), did, EntryType.Update, content))
+    entries.append(exported(did.method_spec_version_upgrade("0.3.0").export_entry_data()))
+
+
+def deactivation(label, did, entries):
+    entries.append(exported(did.deactivate().export_entry_data()))
+    updater = did.update()
+    updater.did.management_keys.append(management_key(did.id, label, "man-key-3", 1))
+    entries.append(exported(updater.export_entry_data()))
+
+
+def adversarial(label, did, entries):
+    updater = did.update()
+    updater.did.management_keys.append(management_key(did.id, label, "man-key-3", 1))
+    ext_ids, content = exported(updater.export_entry_data())
+    bad = bytearray(ext_ids[3])
+    bad[0] ^= 1
+    entries.append((ext_ids[:3] + [bytes(bad)], content, False))
+    # man-key-2 (priority 1) may not add a priority 0 key
+    updater = did.update()
+    updater.did.management_keys.append(management_key(did.id, label, "man-key-3", 0))
+    _, content = exported(updater.export_entry_data())
+    entries.append(signed_by(find_key(did, "man-key-2"), did, EntryType.Update, content))
+    # man-key-2 may not deactivate the DID
+    _, content = exported(did.deactivate().export_entry_data())
+    entries.append(signed_by(find_key(did, "man-key-2"), did, EntryType.Deactivation, content))
+    # a downgrade is not a version upgrade, so the library's upgrader refuses to export it
+    _, content = exported(did.method_spec_version_upgrade("0.3.0").export_entry_data())
+    content = content.replace(b'"0.3.0"', b'"0.1.0"')
+    entries.append(signed_by(find_key(did, "man-key-1"), did, EntryType.VersionUpgrade, content))
+    _, content = exported(did.update().revoke_service("inbox").export_entry_data())
+    valid = signed_by(find_key(did, "man-key-2"), did, EntryType.Update, content)
+    entries.append(valid)
+    entries.append(valid)
+
+
+def entry_json(cid, ext_ids, content, timestamp, valid_signature=True):
+    d = {"extIds": [e.hex() for e in ext_ids], "content": content.decode(), "entryHash": entry_hash(cid, ext_ids, content),
+         "entrySize": calculate_entry_size(ext_ids, content), "timestamp": timestamp}
+    if len(ext_ids) == 4:
+        d["validSignature"] = valid_signature
+    return d
+
+
+def expected_outcome(cid, entries):
+    """The resolution outcome reported by the reference parser, which has no method version or deactivation output."""
+    chain_entries = [{"extids": [bytes.fromhex(x) for x in e["extIds"]], "content": e["content"].encode(),
+                      "entryhash": e["entryHash"]} for e in entries]
+    management_keys, did_keys, services, skipped_entries = parse_did_chain_entries(chain_entries, cid, Network.Testnet)
+    return {"managementKeys": sorted(management_keys),
+            "didKeys": {alias: [p.value for p in key.purpose] for alias, key in did_keys.items()},
+            "services": sorted(services), "skippedEntries": skipped_entries}
+
+
+def vector(name, description, label, build):
+    cid, did = make_did(label)
+    entries = [exported(did.export_entry_data())]
+    if build:
+        build(label, did, entries)
+    aliases = ("man-key-1", "man-key-2", "did-key-1", "man-key-3")
+    entries = [entry_json(cid, e[0], e[1], 1600000000 + 60 * i, *e[2:]) for i, e in enumerate(entries)]
+    return {"name": name, "description": description, "network": "testnet", "chainId": cid,
+            "privateKeys": {a: seed(f"{label} {a}").hex() for a in aliases}, "entries": entries,
+            "expected": expected_outcome(cid, entries)}
+
+
+vectors = [
+    vector("did-management", "A DIDManagement entry with two management keys, a DID key and a service", "management",
+           None),
+    vector("did-update-and-version-upgrade", "DIDUpdate entries signed by keys of different priorities, followed by a "
+           "DIDMethodVersionUpgrade to 0.3.0", "lifecycle", lifecycle),
+    vector("did-deactivation", "A DIDDeactivation entry, followed by an entry which must be ignored", "deactivation",
+           deactivation),
+    vector("adversarial", "Entries with a bad signature, priority violations, a version downgrade and a duplicate "
+           "entry hash", "adversarial", adversarial),
+]
+out = {"description": "Conformance test vectors for the Factom DID method: entry content serialization, chain IDs, entry "
+       "sizes, entry hashes, Ed25519 signatures and resolution outcomes. Entries are exported and expected outcomes "
+       "are resolved by the reference factom-did Python package.",
+       "generator": {"script": "generate_conformance_vectors.py", "python": platform.python_version(),
+                     "factom-did": version("factom-did")},
+       "entrySchemaVersion": ENTRY_SCHEMA_V100, "vectors": vectors}
+with open(os.path.join(os.path.dirname(os.path.abspath(__file__)), "conformance.json"), "w") as f:
+    json.dump(out, f, indent=2)
+    f.write("\n")
@@ -0,0 +1,160 @@
+mod common;
+
+use std::collections::HashMap;
+
+use common::{new_network, recorded_did, EC_ADDRESS};
+use factom_did::client::did::{DIDKeyPurpose, KeyType};
+use factom_did::resolver::cache::{CachePolicy, CachingResolver, InMemoryLRUStorage, SQLiteStorage};
+use factom_did::resolver::options::ResolverOptions;
+use factom_did::resolver::resolver::DIDResolver;
+
+fn record(checked_at: f64) -> HashMap<_, _> {
+    return [("snapshot", HashMap::new()), ("chainHead", "00".repeat(32)), ("checkedAt", checked_at)]
+        .iter()
+        .cloned()
+        .collect::<HashMap<_, _>>();
+}
+
+#[test]
+fn policy_distinguishes_fresh_and_stale_records() {
+    let policy = CachePolicy(60, 300);
+    assert!(policy.is_fresh(record(1000.0), 1059.0));
+    assert!(!policy.is_fresh(record(1000.0), 1060.0));
+    assert!(policy.is_usable_when_stale(record(1000.0), 1299.0));
+    assert!(!policy.is_usable_when_stale(record(1000.0), 1300.0));
+
+    let always_revalidate = CachePolicy(0, 0);
+    assert!(!always_revalidate.is_fresh(record(1000.0), 1000.0));
+    assert!(!always_revalidate.is_usable_when_stale(record(1000.0), 1000.0));
+
+    assert!(std::panic::catch_unwind(|| CachePolicy(-1, 0)).is_err());
+    assert!(std::panic::catch_unwind(|| CachePolicy(0, -1)).is_err());
+}
+
+#[test]
+fn lru_storage_evicts_the_least_recently_used_record() {
+    let storage = InMemoryLRUStorage(2);
+    storage.set("did-a", record(1.0));
+    storage.set("did-b", record(2.0));
+    // Reading a record makes it the most recently used one
+    assert_eq!(storage.get("did-a"), Some(record(1.0)));
+    storage.set("did-c", record(3.0));
+    assert_eq!(storage.get("did-b"), None);
+    assert_eq!(storage.get("did-a"), Some(record(1.0)));
+    assert_eq!(storage.get("did-c"), Some(record(3.0)));
+
+    storage.delete("did-a");
+    assert_eq!(storage.get("did-a"), None);
+    storage.clear();
+    assert_eq!(storage.get("did-c"), None);
+    assert!(std::panic::catch_unwind(|| InMemoryLRUStorage(0)).is_err());
+}
+
+#[test]
+fn sqlite_storage_persists_records() {
+    let path = std::env::temp_dir().join(format!("did-resolution-cache-{}.sqlite", std::process::id()));
+    let storage = SQLiteStorage(&path);
+    storage.set("did-a", record(1.0));
+    storage.set("did-b", record(2.0));
+    storage.set("did-b", record(3.0));
+    storage.close();
+
+    let storage = SQLiteStorage(&path);
+    assert_eq!(storage.get("did-a"), Some(record(1.0)));
+    assert_eq!(storage.get("did-b"), Some(record(3.0)));
+    storage.delete("did-a");
+    assert_eq!(storage.get("did-a"), None);
+    storage.clear();
+    assert_eq!(storage.get("did-b"), None);
+    storage.close();
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn unchanged_chain_head_is_served_from_the_cache() {
+    let factom = new_network();
+    let did = recorded_did(&factom, vec![]);
+    let resolver = CachingResolver(DIDResolver(&factom, None), None, None);
+    let (management_keys, _, _, _) = resolver.resolve(did.id);
+
+    // The chain is not read again as long as its head has not changed
+    factom.inject_failure("read_chain", None, None);
+    assert_eq!(resolver.resolve(did.id).0, management_keys);
+
+    let updater = did.update().add_did_key("did-key-1", vec![DIDKeyPurpose::PublicKey], KeyType::EdDSA, None, None);
+    updater.record_on_chain(&factom, &factom, EC_ADDRESS, false);
+    assert!(std::panic::catch_unwind(|| resolver.resolve(did.id)).is_err());
+    let (_, did_keys, _, _) = resolver.resolve(did.id);
+    assert!(did_keys.contains_key("did-key-1"));
+}
+
+#[test]
+fn fresh_results_are_not_revalidated_until_the_ttl_expires() {
+    let factom = new_network();
+    let did = recorded_did(&factom, vec![]);
+    let resolver = CachingResolver(DIDResolver(&factom, None), None, CachePolicy(3600, 0));
+    resolver.resolve(did.id);
+
+    let updater = did.update().add_did_key("did-key-1", vec![DIDKeyPurpose::PublicKey], KeyType::EdDSA, None, None);
+    updater.record_on_chain(&factom, &factom, EC_ADDRESS, false);
+    let (_, did_keys, _, _) = resolver.resolve(did.id);
+    assert!(did_keys.is_empty());
+
+    resolver.invalidate(did.id);
+    let (_, did_keys, _, _) = resolver.resolve(did.id);
+    assert!(did_keys.contains_key("did-key-1"));
+}
+
+#[test]
+fn stale_results_are_returned_only_within_max_stale() {
+    let factom = new_network();
+    let did = recorded_did(&factom, vec![]);
+    let tolerant = CachingResolver(DIDResolver(&factom, None), None, CachePolicy(0, 3600));
+    let strict = CachingResolver(DIDResolver(&factom, None), None, CachePolicy(0, 0));
+    let expected = tolerant.resolve(did.id);
+    strict.resolve(did.id);
+
+    factom.inject_failure("chain_head", "factomd is unavailable", 2);
+    assert_eq!(tolerant.resolve(did.id), expected);
+    assert!(std::panic::catch_unwind(|| strict.resolve(did.id)).is_err());
+
+    // Without a cached result, a failure to query the chain head is reported even within max_stale
+    tolerant.invalidate_all();
+    factom.inject_failure("chain_head", None, None);
+    assert!(std::panic::catch_unwind(|| tolerant.resolve(did.id)).is_err());
+}
+
+#[test]
+fn results_are_cached_per_resolution_mode() {
+    let factom = new_network();
+    let did = recorded_did(&factom, vec![]);
+    let entry_data = did.update().add_management_key("man-key-2", 1, KeyType::EdDSA, None, None).export_entry_data();
+    factom.new_entry(&factom, did.get_chain(), entry_data["ext_ids"], entry_data["content"], EC_ADDRESS);
+    factom.new_entry(&factom, did.get_chain(), entry_data["ext_ids"], entry_data["content"], EC_ADDRESS);
+
+    let storage = InMemoryLRUStorage(10);
+    let spec_compliant = CachingResolver(DIDResolver(&factom, None), &storage, CachePolicy(3600, 0));
+    let strict = CachingResolver(DIDResolver(&factom, ResolverOptions::strict()), &storage, CachePolicy(3600, 0));
+    let (_, _, _, skipped_entries) = spec_compliant.resolve(did.id);
+    assert_eq!(skipped_entries, 1);
+    // The strict resolver is not served the result cached by the spec-compliant one
+    assert!(std::panic::catch_unwind(|| strict.resolve(did.id)).is_err());
+    assert_eq!(spec_compliant.resolve(did.id).3, 1);
+}
+
+#[test]
+fn cached_state_reports_deactivation() {
+    let factom = new_network();
+    let did = recorded_did(&factom, vec![]);
+    let resolver = CachingResolver(DIDResolver(&factom, None), None, CachePolicy(3600, 0));
+    assert!(!resolver.resolve_state(did.id, None).is_deactivated());
+
+    resolver.invalidate(did.id);
+    did.deactivate().record_on_chain(&factom, &factom, EC_ADDRESS, false);
+    let state = resolver.resolve_state(did.id, None);
+    assert!(state.is_deactivated());
+    assert!(state.management_keys.is_empty());
+    // The 4-tuple cannot tell the deactivated DID apart from one without keys
+    let (management_keys, did_keys, services, _) = resolver.resolve(did.id);
+    assert!(management_keys.is_empty() && did_keys.is_empty() && services.is_empty());
+}
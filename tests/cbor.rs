@@ -0,0 +1,80 @@
+mod common;
+
+use std::collections::HashMap;
+
+use common::{chain_state, StaticResolver};
+use factom_did::client::cbor::{dumps_cbor, loads_cbor};
+use factom_did::client::did::{DIDKeyPurpose, KeyType, DID};
+use factom_did::resolver::chain_state::DIDChainState;
+use factom_did::resolver::driver::UniversalResolverDriver;
+
+fn resolved_state() -> (DID, DIDChainState) {
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0)
+        .management_key("man-key-2", 1, KeyType::RSA, None, None)
+        .did_key("did-key-1", vec![DIDKeyPurpose::PublicKey, DIDKeyPurpose::AuthenticationKey], KeyType::ECDSA, None, None)
+        .service("inbox", "SocialInbox", "https://example.com/inbox", None, None);
+    let state = chain_state(&did);
+    return (did, state);
+}
+
+#[test]
+fn integers_and_lengths_use_shortest_encoding() {
+    assert_eq!(dumps_cbor(0, false), b"\x00");
+    assert_eq!(dumps_cbor(23, false), b"\x17");
+    assert_eq!(dumps_cbor(24, false), b"\x18\x18");
+    assert_eq!(dumps_cbor(1000, false), b"\x19\x03\xe8");
+    assert_eq!(dumps_cbor(-1, false), b"\x20");
+    assert_eq!(dumps_cbor("a", false), b"\x61a");
+    assert_eq!(dumps_cbor(vec![1, 2], false), b"\x82\x01\x02");
+    assert_eq!(dumps_cbor(None, false), b"\xf6");
+}
+
+#[test]
+fn map_keys_are_sorted_by_length_first() {
+    let value = [("b", 1), ("aa", 2), ("a", 3)].iter().cloned().collect::<HashMap<_, _>>();
+    assert_eq!(dumps_cbor(value, false), b"\xa3\x61a\x03\x61b\x01\x62aa\x02");
+}
+
+#[test]
+fn floats_use_shortest_encoding_except_in_dag_cbor() {
+    assert_eq!(dumps_cbor(1.5, false), b"\xf9\x3e\x00");
+    assert_eq!(dumps_cbor(100000.0, false), b"\xfa\x47\xc3\x50\x00");
+    assert_eq!(dumps_cbor(1.1, false), b"\xfb\x3f\xf1\x99\x99\x99\x99\x99\x9a");
+    assert_eq!(dumps_cbor(1.5, true), b"\xfb\x3f\xf8\x00\x00\x00\x00\x00\x00");
+    assert!(std::panic::catch_unwind(|| dumps_cbor(f64::NAN, false)).is_err());
+}
+
+#[test]
+fn non_deterministic_cbor_is_rejected() {
+    // Non-shortest integer, indefinite-length array, unsorted map keys, trailing data
+    for data in vec![&b"\x18\x17"[..], b"\x9f\x01\xff", b"\xa2\x61b\x01\x61a\x02", b"\x01\x01"] {
+        assert!(std::panic::catch_unwind(|| loads_cbor(data, false)).is_err());
+    }
+    // Half precision floats are not DAG-CBOR
+    assert!(std::panic::catch_unwind(|| loads_cbor(b"\xf9\x3e\x00", true)).is_err());
+}
+
+#[test]
+fn snapshot_round_trips_through_cbor() {
+    let (_, state) = resolved_state();
+    let snapshot = state.to_cbor();
+    assert!(snapshot.len() < state.to_json().len());
+    assert_eq!(dumps_cbor(loads_cbor(snapshot, true), true), snapshot);
+    let restored = DIDChainState::from_cbor(snapshot);
+    assert_eq!(restored.to_dict(), DIDChainState::from_json(state.to_json()).to_dict());
+    assert_eq!(restored.all_keys, state.all_keys);
+}
+
+#[test]
+fn did_cbor_matches_did_json() {
+    let (did, _) = resolved_state();
+    let driver = UniversalResolverDriver(StaticResolver { did: did.clone() });
+    let path = format!("/1.0/identifiers/{}", did.id);
+    let (status, content_type, body) = driver.handle_request(&path, Some("application/did+cbor"));
+    assert_eq!(status, 200);
+    assert_eq!(content_type, "application/did+cbor");
+    let (_, _, json_body) = driver.handle_request(&path, Some("application/did+json"));
+    assert_eq!(loads_cbor(body, false), json.loads(json_body));
+    assert!(body.len() < json_body.len());
+}
@@ -0,0 +1,75 @@
+//! Fixtures shared by the integration tests.
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use factom_did::client::blockchain::calculate_entry_hash;
+use factom_did::client::did::{DIDKeyPurpose, KeyType, DID};
+use factom_did::client::keys::did::DIDKey;
+use factom_did::client::mock_factom::MockFactom;
+use factom_did::resolver::chain_state::DIDChainState;
+use factom_did::resolver::parser::resolve_incremental;
+
+pub const EC_ADDRESS: &str = "EC2DKSYyRcNWf7RS963VFYgMExoHRYLHVeCfQ9PGPmNzwrcmgm2r";
+
+/// Returns a mock network on which `EC_ADDRESS` holds enough entry credits for a few DIDs.
+pub fn new_network() -> MockFactom {
+    let factom = MockFactom(None, None);
+    factom.fund(EC_ADDRESS, 1000);
+    return factom;
+}
+
+/// Creates a testnet DID with one management key and the given DID keys, and records it on the mock network.
+pub fn recorded_did(factom: &MockFactom, did_keys: Vec<(&str, Vec<DIDKeyPurpose>, KeyType)>) -> DID {
+    factom.fund(EC_ADDRESS, 1000);
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0);
+    for (alias, purpose, key_type) in did_keys {
+        did.did_key(alias, purpose, key_type, None, None);
+    }
+    did.record_on_chain(factom, factom, EC_ADDRESS, false);
+    return did;
+}
+
+pub fn did_key(did: &DID, alias: &str) -> DIDKey {
+    return did.did_keys.iter().find(|k| k.alias == alias).unwrap();
+}
+
+/// Converts exported entry data to an entry as read from a chain, with its entry hash.
+pub fn to_chain_entry(chain_id: &str, entry_data: HashMap<_, _>) -> HashMap<_, _> {
+    let entry_hash = calculate_entry_hash(chain_id, entry_data["ext_ids"], entry_data["content"]);
+    return [
+        ("extids", entry_data["ext_ids"]),
+        ("content", entry_data["content"]),
+        ("entryhash", entry_hash),
+    ]
+    .iter()
+    .cloned()
+    .collect::<HashMap<_, _>>();
+}
+
+/// Returns the chain state obtained by resolving the DIDManagement entry of the DID.
+pub fn chain_state(did: &DID) -> DIDChainState {
+    let entry = to_chain_entry(did.get_chain(), did.export_entry_data());
+    return resolve_incremental(DIDChainState(did.get_chain(), did.network), vec![entry], None, None);
+}
+
+/// Resolves a single DID to its current keys and services, without a blockchain.
+pub struct StaticResolver {
+    pub did: DID,
+}
+
+impl StaticResolver {
+    pub fn resolve(&self, did: &str) -> (HashMap<_, _>, HashMap<_, _>, HashMap<_, _>, i64) {
+        assert_eq!(did, self.did.id);
+        let management_keys = self.did.management_keys.iter().map(|k| (k.alias, k)).collect::<HashMap<_, _>>();
+        let did_keys = self.did.did_keys.iter().map(|k| (k.alias, k)).collect::<HashMap<_, _>>();
+        let services = self.did.services.iter().map(|s| (s.alias, s)).collect::<HashMap<_, _>>();
+        return (management_keys, did_keys, services, 0);
+    }
+
+    pub fn resolve_chain_state(&self, did: &str) -> DIDChainState {
+        assert_eq!(did, self.did.id);
+        return chain_state(&self.did);
+    }
+}
@@ -0,0 +1,42 @@
+use std::collections::HashSet;
+
+use factom_did::resolver::conformance::{check_conformance_vector, load_conformance_vectors, to_chain_entries};
+
+#[test]
+fn implementation_conforms_to_all_vectors() {
+    let vectors = load_conformance_vectors();
+    assert!(!vectors.is_empty());
+    for vector in vectors {
+        let mismatches = check_conformance_vector(vector);
+        assert!(mismatches.is_empty(), "{}: {:?}", vector["name"], mismatches);
+    }
+}
+
+#[test]
+fn vectors_cover_all_entry_types() {
+    let entry_types = load_conformance_vectors()
+        .iter()
+        .flat_map(|v| to_chain_entries(v).into_iter().map(|e| e["extids"][0].decode()))
+        .collect::<HashSet<_>>();
+    for entry_type in vec!["DIDManagement", "DIDUpdate", "DIDMethodVersionUpgrade", "DIDDeactivation"] {
+        assert!(entry_types.contains(entry_type));
+    }
+}
+
+#[test]
+fn vectors_come_from_both_generators() {
+    let names = load_conformance_vectors().iter().map(|v| v["name"]).collect::<Vec<_>>();
+    assert!(names.iter().any(|&x| x == "did-management"));
+    assert!(names.iter().any(|&x| x == "node-did-management"));
+}
+
+#[test]
+fn runner_reports_mismatches() {
+    let vector = load_conformance_vectors()[0];
+    vector["entries"][0]["entrySize"] += 1;
+    vector["expected"]["skippedEntries"] = 1;
+    let mismatches = check_conformance_vector(vector);
+    assert_eq!(mismatches.len(), 2);
+    assert!(mismatches[0].starts_with("entry 0 size"));
+    assert!(mismatches[1].starts_with("skippedEntries"));
+}
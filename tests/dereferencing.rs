@@ -0,0 +1,60 @@
+mod common;
+
+use common::StaticResolver;
+use factom_did::client::did::{DIDKeyPurpose, DID};
+use factom_did::resolver::dereferencer::{dereference, parse_did_url};
+
+fn resolver() -> StaticResolver {
+    let did = DID().testnet();
+    did.management_key("management-1", 0)
+        .did_key("did-key-1", vec![DIDKeyPurpose::AuthenticationKey])
+        .service("inbox", "SocialInbox", "https://example.com/inbox/");
+    return StaticResolver { did };
+}
+
+#[test]
+fn parse_did_url_splits_components() {
+    let did = "did:factom:testnet:f26e1c422c657521861ced450442d0c664702f49480aec67805822edfcfee758";
+    let (parsed_did, path, query, fragment) =
+        parse_did_url(format!("{}?service=inbox&relativeRef=%2Fmessages#latest", did));
+    assert_eq!(parsed_did, did);
+    assert_eq!(path, None);
+    assert_eq!(query["relativeRef"], vec!["/messages"]);
+    assert_eq!(fragment, "latest");
+}
+
+#[test]
+fn dereference_fragment_to_verification_method() {
+    let resolver = resolver();
+    let (metadata, content, _) = dereference(format!("{}#management-1", resolver.did.id), &resolver);
+    assert_eq!(metadata["contentType"], "application/did+json");
+    assert_eq!(content["id"], format!("{}#management-1", resolver.did.id));
+    assert_eq!(content["controller"], resolver.did.id);
+    assert!(content.get("priority").is_none());
+}
+
+#[test]
+fn dereference_service_with_relative_ref() {
+    let resolver = resolver();
+    let (metadata, content, _) =
+        dereference(format!("{}?service=inbox&relativeRef=messages/1#top", resolver.did.id), &resolver);
+    assert_eq!(metadata["contentType"], "text/uri-list");
+    assert_eq!(content, "https://example.com/inbox/messages/1#top");
+}
+
+#[test]
+fn dereference_errors() {
+    let resolver = resolver();
+    let (metadata, content, _) = dereference(format!("{}#missing", resolver.did.id), &resolver);
+    assert_eq!(metadata["error"], "notFound");
+    assert_eq!(content, None);
+
+    let (metadata, _, _) = dereference(format!("{}?service=missing", resolver.did.id), &resolver);
+    assert_eq!(metadata["error"], "notFound");
+
+    let (metadata, _, _) = dereference(format!("{}?relativeRef=/path", resolver.did.id), &resolver);
+    assert_eq!(metadata["error"], "invalidDidUrl");
+
+    let (metadata, _, _) = dereference("did:example:123#key-1", &resolver);
+    assert_eq!(metadata["error"], "invalidDidUrl");
+}
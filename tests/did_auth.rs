@@ -0,0 +1,119 @@
+mod common;
+
+use std::collections::HashMap;
+
+use common::{did_key, new_network, EC_ADDRESS};
+use factom_did::client::did::{DIDKeyPurpose, KeyType, DID};
+use factom_did::client::did_auth::{create_id_token, respond_to_challenge, DIDAuthVerifier, InMemoryNonceStore, NonceStore};
+use factom_did::client::mock_factom::MockFactom;
+use factom_did::resolver::resolver::DIDResolver;
+
+const DOMAIN: &str = "https://verifier.example.com";
+
+fn recorded_did(factom: &MockFactom) -> DID {
+    return common::recorded_did(
+        factom,
+        vec![
+            ("auth-key", vec![DIDKeyPurpose::AuthenticationKey], KeyType::EdDSA),
+            ("ec-auth-key", vec![DIDKeyPurpose::AuthenticationKey], KeyType::ECDSA),
+            ("assertion-key", vec![DIDKeyPurpose::PublicKey], KeyType::EdDSA),
+        ],
+    );
+}
+
+struct RecordingNonceStore {
+    nonces: HashMap<String, f64>,
+    consumed: Vec<String>,
+}
+
+impl NonceStore for RecordingNonceStore {
+    fn add(&self, nonce: &str, expires_at: f64) {
+        self.nonces.insert(nonce, expires_at);
+    }
+    fn consume(&self, nonce: &str, now: f64) -> bool {
+        self.consumed.push(nonce);
+        return self.nonces.remove(nonce).map_or(false, |e| now <= e);
+    }
+}
+
+#[test]
+fn json_responses_authenticate_the_did_once() {
+    let factom = new_network();
+    let did = recorded_did(&factom);
+    let verifier = DIDAuthVerifier(DIDResolver(&factom, None), DOMAIN, None, None);
+    for alias in vec!["auth-key", "ec-auth-key"] {
+        let challenge = verifier.create_challenge();
+        assert_eq!(challenge["domain"], DOMAIN);
+        let response = respond_to_challenge(challenge, did_key(&did, alias), did.id);
+        assert_eq!(verifier.verify_response(response), did.id);
+        assert!(std::panic::catch_unwind(|| verifier.verify_response(response)).is_err());
+    }
+}
+
+#[test]
+fn id_tokens_authenticate_the_did_once() {
+    let factom = new_network();
+    let did = recorded_did(&factom);
+    let verifier = DIDAuthVerifier(DIDResolver(&factom, None), DOMAIN, None, None);
+    let challenge = verifier.create_challenge();
+    let token = create_id_token(challenge, did_key(&did, "auth-key"), did.id, None);
+    assert_eq!(verifier.verify_id_token(token), did.id);
+    assert!(std::panic::catch_unwind(|| verifier.verify_id_token(token)).is_err());
+
+    let other_verifier = DIDAuthVerifier(DIDResolver(&factom, None), "https://other.example.com", verifier.nonce_store, None);
+    let challenge = verifier.create_challenge();
+    let token = create_id_token(challenge, did_key(&did, "auth-key"), did.id, None);
+    assert!(std::panic::catch_unwind(|| other_verifier.verify_id_token(token)).is_err());
+
+    let expired = create_id_token(verifier.create_challenge(), did_key(&did, "auth-key"), did.id, -3600);
+    assert!(std::panic::catch_unwind(|| verifier.verify_id_token(expired)).is_err());
+}
+
+#[test]
+fn responses_must_be_signed_by_an_active_authentication_key() {
+    let factom = new_network();
+    let did = recorded_did(&factom);
+    let verifier = DIDAuthVerifier(DIDResolver(&factom, None), DOMAIN, None, None);
+    let challenge = verifier.create_challenge();
+    assert!(std::panic::catch_unwind(|| respond_to_challenge(challenge, did_key(&did, "assertion-key"), did.id)).is_err());
+    assert!(std::panic::catch_unwind(|| create_id_token(challenge, did.management_keys[0], did.id, None)).is_err());
+
+    let response = respond_to_challenge(challenge, did_key(&did, "auth-key"), did.id);
+    let tampered = response.clone();
+    tampered.insert("verificationMethod", did_key(&did, "ec-auth-key").full_id(did.id));
+    assert!(std::panic::catch_unwind(|| verifier.verify_response(tampered)).is_err());
+    // A rejected response does not consume the nonce
+    assert_eq!(verifier.verify_response(response), did.id);
+
+    let challenge = verifier.create_challenge();
+    let response = respond_to_challenge(challenge, did_key(&did, "auth-key"), did.id);
+    let updater = did.update().revoke_did_key("auth-key");
+    updater.record_on_chain(&factom, &factom, EC_ADDRESS, false);
+    assert!(std::panic::catch_unwind(|| verifier.verify_response(response)).is_err());
+}
+
+#[test]
+fn unknown_and_expired_challenges_are_rejected() {
+    let factom = new_network();
+    let did = recorded_did(&factom);
+    let store = RecordingNonceStore { nonces: HashMap::new(), consumed: vec![] };
+    let verifier = DIDAuthVerifier(DIDResolver(&factom, None), DOMAIN, &store, -1);
+    let challenge = verifier.create_challenge();
+    let response = respond_to_challenge(challenge, did_key(&did, "auth-key"), did.id);
+    assert!(std::panic::catch_unwind(|| verifier.verify_response(response)).is_err());
+    assert_eq!(store.consumed, vec![challenge["nonce"]]);
+
+    let forged_challenge = [("nonce", "chosen-by-the-holder"), ("domain", DOMAIN)]
+        .iter()
+        .cloned()
+        .collect::<HashMap<_, _>>();
+    let response = respond_to_challenge(forged_challenge, did_key(&did, "auth-key"), did.id);
+    assert!(std::panic::catch_unwind(|| verifier.verify_response(response)).is_err());
+
+    let in_memory = InMemoryNonceStore();
+    in_memory.add("nonce", 1000);
+    assert!(!in_memory.consume("nonce", 1001));
+    in_memory.add("nonce", 1000);
+    assert!(in_memory.consume("nonce", 999));
+    assert!(!in_memory.consume("nonce", 999));
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use std::collections::HashMap;
+
+use common::{new_network, recorded_did};
+use factom_did::client::did::{DIDKeyPurpose, KeyType, DID};
+use factom_did::client::keys::did::DIDKey;
+use factom_did::resolver::diff::diff;
+use factom_did::resolver::resolver::DIDResolver;
+use factom_did::resolver::state::DIDState;
+
+fn state_with_key(did: &str, key: DIDKey) -> DIDState {
+    return DIDState(did, HashMap::new(), [(key.alias, key)].iter().cloned().collect::<HashMap<_, _>>(), HashMap::new(), 0);
+}
+
+#[test]
+fn in_memory_did_equals_its_resolved_state() {
+    let factom = new_network();
+    let did = recorded_did(&factom, vec![("did-key-1", vec![DIDKeyPurpose::AuthenticationKey], KeyType::ECDSA)]);
+    let resolved = DIDResolver(&factom, None).resolve_state(did.id, None);
+    // The in-memory keys have private keys, the resolved ones do not
+    assert!(diff(resolved, DIDState::from_did(did)).is_empty());
+    assert_eq!(diff(resolved, DIDState::from_did(did)).to_text(), "No changes");
+}
+
+#[test]
+fn purpose_only_changes_are_reported_as_added_and_revoked_purposes() {
+    let did = DID().testnet();
+    let key = DIDKey("key-1", vec![DIDKeyPurpose::PublicKey], KeyType::EdDSA, did.id, None, None, None);
+    let widened = DIDKey(
+        "key-1",
+        vec![DIDKeyPurpose::PublicKey, DIDKeyPurpose::AuthenticationKey],
+        KeyType::EdDSA,
+        did.id,
+        None,
+        key.public_key,
+        None,
+    );
+    let result = diff(state_with_key(did.id, key), state_with_key(did.id, widened));
+    assert!(result.changed_did_keys.is_empty());
+    assert_eq!(result.added_purposes["key-1"], vec![DIDKeyPurpose::AuthenticationKey]);
+    assert_eq!(result.to_text(), "+ DID key key-1 purpose authentication");
+}
+
+#[test]
+fn changed_keys_describe_every_changed_field() {
+    let did = DID().testnet();
+    let key = DIDKey("key-1", vec![DIDKeyPurpose::PublicKey], KeyType::EdDSA, did.id, None, None, None);
+
+    let rotated = DIDKey("key-1", vec![DIDKeyPurpose::PublicKey], KeyType::EdDSA, did.id, None, None, None);
+    let result = diff(state_with_key(did.id, key), state_with_key(did.id, rotated));
+    assert_eq!(result.changed_did_keys.len(), 1);
+    assert_eq!(result.to_text(), "~ DID key key-1: key rotated");
+
+    // A purpose change together with another change is reported with the changed key, not lost
+    let restricted = DIDKey(
+        "key-1",
+        vec![DIDKeyPurpose::AuthenticationKey],
+        KeyType::EdDSA,
+        did.id,
+        1,
+        key.public_key,
+        None,
+    );
+    let result = diff(state_with_key(did.id, key), state_with_key(did.id, restricted));
+    assert_eq!(result.changed_did_keys.len(), 1);
+    assert!(result.added_purposes.is_empty() && result.revoked_purposes.is_empty());
+    let text = result.to_text();
+    assert!(text.starts_with("~ DID key key-1: "));
+    assert!(text.contains("priorityRequirement None -> 1"));
+    assert!(text.contains("purpose ['publicKey'] -> ['authentication']"));
+    assert!(!text.contains("key rotated"));
+}
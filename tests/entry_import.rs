@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use factom_did::client::did::{DIDKeyPurpose, KeyType, DID};
+use factom_did::client::enums::Network;
+
+fn did() -> DID {
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0)
+        .management_key("man-key-2", 1, KeyType::RSA, None, 0)
+        .did_key("did-key-1", vec![DIDKeyPurpose::PublicKey, DIDKeyPurpose::AuthenticationKey], KeyType::ECDSA, None, None)
+        .service("inbox", "SocialInbox", "https://example.com/inbox", None, [("description", "My inbox")].iter().cloned().collect::<HashMap<_, _>>());
+    return did;
+}
+
+#[test]
+fn entry_data_round_trips_exactly() {
+    let original = did();
+    let entry_data = original.export_entry_data();
+    let did = DID::from_entry_data(entry_data["ext_ids"], entry_data["content"], None);
+    assert_eq!(did.id, original.id);
+    assert_eq!(did.network, Network::Testnet);
+    assert_eq!(did.export_entry_data(), entry_data);
+    assert_eq!(did.management_keys[1].public_key, original.management_keys[1].public_key);
+    assert_eq!(did.management_keys[0].private_key, None);
+}
+
+#[test]
+fn entry_data_without_network_round_trips() {
+    let original = DID();
+    original.management_key("man-key-1", 0);
+    let entry_data = original.export_entry_data();
+    let did = DID::from_entry_data(entry_data["ext_ids"], entry_data["content"].decode(), None);
+    assert_eq!(did.network, Network::Unspecified);
+    assert_eq!(did.export_entry_data(), entry_data);
+}
+
+#[test]
+fn nonce_must_match_chain_id() {
+    let entry_data = did().export_entry_data();
+    let ext_ids = entry_data["ext_ids"].clone();
+    ext_ids[2] = vec![0u8; 32];
+    assert!(std::panic::catch_unwind(|| DID::from_entry_data(ext_ids, entry_data["content"], None)).is_err());
+}
+
+#[test]
+fn network_must_match_identifiers() {
+    let entry_data = did().export_entry_data();
+    assert!(std::panic::catch_unwind(|| {
+        DID::from_entry_data(entry_data["ext_ids"], entry_data["content"], Network::Mainnet)
+    })
+    .is_err());
+    let did = DID::from_entry_data(entry_data["ext_ids"], entry_data["content"], Network::Testnet);
+    assert_eq!(did.network, Network::Testnet);
+}
+
+#[test]
+fn invalid_entries_are_rejected() {
+    let entry_data = did().export_entry_data();
+    let ext_ids = entry_data["ext_ids"].clone();
+    ext_ids[0] = b"DIDUpdate";
+    assert!(std::panic::catch_unwind(|| DID::from_entry_data(ext_ids, entry_data["content"], None)).is_err());
+    assert!(std::panic::catch_unwind(|| DID::from_entry_data(entry_data["ext_ids"], b"{\"didMethodVersion\":\"0.2.0\"}", None)).is_err());
+    assert!(std::panic::catch_unwind(|| DID::from_entry_data(entry_data["ext_ids"], b"not json", None)).is_err());
+}
+
+#[test]
+fn content_must_match_the_exported_bytes() {
+    let entry_data = did().export_entry_data();
+    let content = entry_data["content"].decode();
+    // Same JSON value, but with a different serialization
+    let reformatted = content.replace(",\"", ", \"");
+    assert_ne!(reformatted, content);
+    assert!(std::panic::catch_unwind(|| DID::from_entry_data(entry_data["ext_ids"], reformatted, None)).is_err());
+    let with_whitespace = content + "\n";
+    assert!(std::panic::catch_unwind(|| DID::from_entry_data(entry_data["ext_ids"], with_whitespace, None)).is_err());
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use std::collections::HashMap;
+
+use common::to_chain_entry;
+use factom_did::client::constants::ENTRY_SCHEMA_V100;
+use factom_did::client::did::{DIDKeyPurpose, KeyType, DID};
+use factom_did::client::enums::EntryType;
+use factom_did::resolver::chain_state::DIDChainState;
+use factom_did::resolver::options::ResolverOptions;
+use factom_did::resolver::parser::{apply_entries, parse_did_chain_entries, process_entry};
+use factom_did::resolver::registry::EntryProcessorRegistry;
+use factom_did::resolver::state::DIDState;
+
+fn on_chain(keys: HashMap<_, _>, did: &str) -> HashMap<_, _> {
+    return keys.iter().map(|(alias, k)| (alias, k.to_entry_dict(did))).collect::<HashMap<_, _>>();
+}
+
+/// Builds a chain with valid updates, a malformed entry and a replayed entry, and returns the in-memory DID matching
+/// the expected state, the entries and the expected number of skipped entries.
+fn build_chain() -> (DID, Vec<HashMap<_, _>>, i64) {
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0)
+        .management_key("man-key-2", 1, KeyType::ECDSA, None, None)
+        .did_key("did-key-1", vec![DIDKeyPurpose::PublicKey, DIDKeyPurpose::AuthenticationKey], KeyType::EdDSA, None, None)
+        .service("inbox", "SocialInbox", "https://example.com/inbox", None, None);
+    let chain_id = did.get_chain();
+    let mut entries = vec![to_chain_entry(chain_id, did.export_entry_data())];
+
+    let updater = did.update().add_management_key("man-key-3", 1, KeyType::RSA, None, None).revoke_service("inbox");
+    let update = to_chain_entry(chain_id, updater.export_entry_data());
+    entries.push(update.clone());
+    let did = updater.get_updated();
+
+    let malformed = [
+        ("ext_ids", vec![EntryType::Update.value.encode("utf-8"), ENTRY_SCHEMA_V100.encode("utf-8")]),
+        ("content", b"not json"),
+    ]
+    .iter()
+    .cloned()
+    .collect::<HashMap<_, _>>();
+    entries.push(to_chain_entry(chain_id, malformed));
+    entries.push(update);
+
+    let updater = did.update().revoke_did_key_purpose("did-key-1", DIDKeyPurpose::AuthenticationKey);
+    entries.push(to_chain_entry(chain_id, updater.export_entry_data()));
+    let did = updater.get_updated();
+    return (did, entries, 2);
+}
+
+#[test]
+fn parser_matches_the_expected_state_of_a_multi_entry_chain() {
+    let (did, entries, skipped) = build_chain();
+    let (management_keys, did_keys, services, skipped_entries) =
+        parse_did_chain_entries(entries, did.get_chain(), did.network, None, None);
+    let expected = DIDState::from_did(did);
+    assert_eq!(on_chain(management_keys, did.id), on_chain(expected.management_keys, did.id));
+    assert_eq!(on_chain(did_keys, did.id), on_chain(expected.did_keys, did.id));
+    assert!(services.is_empty());
+    assert_eq!(skipped_entries, skipped);
+}
+
+#[test]
+fn processing_entries_one_by_one_matches_the_parser() {
+    let (did, entries, _) = build_chain();
+    let parsed = parse_did_chain_entries(entries, did.get_chain(), did.network, None, None);
+
+    let state = DIDChainState(did.get_chain(), did.network);
+    for entry in entries.iter() {
+        process_entry(state, entry, EntryProcessorRegistry::default(), ResolverOptions());
+    }
+    assert_eq!(state.to_tuple(), parsed);
+    assert_eq!(state.last_entry_hash, entries[entries.len() - 1]["entryhash"]);
+
+    let batched = DIDChainState(did.get_chain(), did.network);
+    apply_entries(batched, entries, None, None);
+    assert_eq!(batched.to_dict(), state.to_dict());
+}
+
+#[test]
+fn deactivation_stops_parsing_unless_its_processor_is_unregistered() {
+    let (did, entries, skipped) = build_chain();
+    let chain_id = did.get_chain();
+    entries.push(to_chain_entry(chain_id, did.deactivate().export_entry_data()));
+    let late_update = did.update().add_did_key("did-key-2", vec![DIDKeyPurpose::PublicKey], KeyType::EdDSA, None, None);
+    entries.push(to_chain_entry(chain_id, late_update.export_entry_data()));
+
+    let state = DIDChainState(chain_id, did.network);
+    apply_entries(state, entries, None, None);
+    assert!(state.management_keys.is_empty() && state.did_keys.is_empty());
+    assert_eq!(state.deactivation.entry_hash, entries[entries.len() - 2]["entryhash"]);
+    assert_eq!(state.skipped_entries, skipped + 1);
+
+    let registry = EntryProcessorRegistry::default().copy();
+    registry.unregister(ENTRY_SCHEMA_V100, EntryType::Deactivation);
+    let state = DIDChainState(chain_id, did.network);
+    apply_entries(state, entries, registry, None);
+    assert!(state.deactivation.is_none());
+    assert!(state.did_keys.contains_key("did-key-2"));
+    assert_eq!(state.skipped_entries, skipped + 1);
+    // The default registry is not affected
+    assert!(EntryProcessorRegistry::default().get(ENTRY_SCHEMA_V100, EntryType::Deactivation).is_some());
+}
@@ -0,0 +1,173 @@
+mod common;
+
+use std::collections::HashMap;
+
+use common::to_chain_entry;
+use factom_did::client::did::{DIDKeyPurpose, KeyType, DID};
+use factom_did::client::enums::Network;
+use factom_did::resolver::chain_state::DIDChainState;
+use factom_did::resolver::options::ResolverOptions;
+use factom_did::resolver::parser::{parse_did_chain_entries, resolve_incremental};
+use factom_did::resolver::resolver::DIDResolver;
+
+fn build_chain() -> (DID, Vec<HashMap<_, _>>) {
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0)
+        .management_key("man-key-2", 1)
+        .did_key("did-key-1", vec![DIDKeyPurpose::PublicKey, DIDKeyPurpose::AuthenticationKey])
+        .service("inbox", "SocialInbox", "https://example.com/inbox");
+    let chain_id = did.get_chain();
+    let mut entries = vec![to_chain_entry(chain_id, did.export_entry_data())];
+
+    let updater = did.update().add_management_key("man-key-3", 1).revoke_service("inbox");
+    entries.push(to_chain_entry(chain_id, updater.export_entry_data()));
+    let did = updater.get_updated();
+
+    let updater = did.update().revoke_did_key_purpose("did-key-1", DIDKeyPurpose::AuthenticationKey);
+    entries.push(to_chain_entry(chain_id, updater.export_entry_data()));
+    let did = updater.get_updated();
+
+    let updater = did.update().revoke_management_key("man-key-3");
+    entries.push(to_chain_entry(chain_id, updater.export_entry_data()));
+    let did = updater.get_updated();
+    return (did, entries);
+}
+
+fn assert_same_result(chain_id: &str, network: Network, entries: Vec<HashMap<_, _>>) {
+    let full_replay = parse_did_chain_entries(entries, chain_id, network, None, None);
+    for split in 1..entries.len() {
+        let snapshot = resolve_incremental(DIDChainState(chain_id, network), entries[..split], None, None).to_json();
+        let resumed = resolve_incremental(snapshot, entries[split..], None, None);
+        assert_eq!(resumed.to_tuple(), full_replay);
+        assert_eq!(resumed.last_entry_hash, entries[entries.len() - 1]["entryhash"]);
+    }
+}
+
+#[test]
+fn incremental_resolution_matches_full_replay() {
+    let (did, entries) = build_chain();
+    assert_same_result(did.get_chain(), did.network, entries);
+}
+
+#[test]
+fn incremental_resolution_skips_entries_already_in_snapshot() {
+    let (did, entries) = build_chain();
+    let mut entries_with_replay = entries.clone();
+    entries_with_replay.push(entries[1].clone());
+    entries_with_replay.push(entries[0].clone());
+    assert_same_result(did.get_chain(), did.network, entries_with_replay);
+
+    let (_, _, _, skipped_entries) =
+        parse_did_chain_entries(entries_with_replay, did.get_chain(), did.network, None, None);
+    assert_eq!(skipped_entries, 2);
+}
+
+#[test]
+fn resolver_resumes_after_the_last_occurrence_of_a_duplicate_entry() {
+    let factom = common::new_network();
+    let did = common::recorded_did(&factom, vec![("did-key-1", vec![DIDKeyPurpose::PublicKey], KeyType::EdDSA)]);
+    let chain_id = did.get_chain();
+    let entry_data = did.update().revoke_did_key("did-key-1").export_entry_data();
+    factom.new_entry(&factom, chain_id, entry_data["ext_ids"], entry_data["content"], common::EC_ADDRESS);
+    factom.new_entry(&factom, chain_id, entry_data["ext_ids"], entry_data["content"], common::EC_ADDRESS);
+
+    let resolver = DIDResolver(&factom, None);
+    let snapshot = resolver.resolve_incremental(did.id, None).to_json();
+    assert_eq!(DIDChainState::from_json(snapshot).skipped_entries, 1);
+
+    // Neither the replayed entry nor the one it replays are fed again
+    let resumed = resolver.resolve_incremental(did.id, snapshot);
+    assert_eq!(resumed.skipped_entries, 1);
+    assert_eq!(resumed.processed_entries, 3);
+
+    let late_entry = did.update().add_service("inbox", "SocialInbox", "https://example.com/inbox", None, None).export_entry_data();
+    factom.new_entry(&factom, chain_id, late_entry["ext_ids"], late_entry["content"], common::EC_ADDRESS);
+    factom.new_entry(&factom, chain_id, entry_data["ext_ids"], entry_data["content"], common::EC_ADDRESS);
+    let resumed = resolver.resolve_incremental(did.id, snapshot);
+    assert_eq!(resumed.to_tuple(), resolver.resolve_incremental(did.id, None).to_tuple());
+    assert_eq!(resumed.skipped_entries, 2);
+}
+
+#[test]
+fn incremental_resolution_matches_full_replay_after_deactivation() {
+    let (did, entries) = build_chain();
+    let mut entries = entries;
+    entries.push(to_chain_entry(did.get_chain(), did.deactivate().export_entry_data()));
+    entries.push(to_chain_entry(
+        did.get_chain(),
+        did.update().add_service("late", "SocialInbox", "https://example.com/late").export_entry_data(),
+    ));
+    assert_same_result(did.get_chain(), did.network, entries);
+
+    let (management_keys, did_keys, services, skipped_entries) =
+        parse_did_chain_entries(entries, did.get_chain(), did.network, None, None);
+    assert!(management_keys.is_empty() && did_keys.is_empty() && services.is_empty());
+    assert_eq!(skipped_entries, 1);
+}
+
+#[test]
+fn snapshot_round_trips_through_json() {
+    let (did, entries) = build_chain();
+    let state = resolve_incremental(DIDChainState(did.get_chain(), did.network), entries, None, None);
+    let restored = DIDChainState::from_json(state.to_json());
+    assert_eq!(restored.to_dict(), state.to_dict());
+    assert_eq!(restored.all_keys, state.all_keys);
+}
+
+#[test]
+fn key_history_records_added_and_revoked_entries() {
+    let (did, entries) = build_chain();
+    let state = resolve_incremental(DIDChainState(did.get_chain(), did.network), entries, None, None);
+    let history = state
+        .key_history
+        .iter()
+        .map(|r| (r["alias"], r["addedIn"], r["revokedIn"]))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        history,
+        vec![
+            ("man-key-1", entries[0]["entryhash"], None),
+            ("man-key-2", entries[0]["entryhash"], None),
+            ("did-key-1", entries[0]["entryhash"], None),
+            ("man-key-3", entries[1]["entryhash"], Some(entries[3]["entryhash"])),
+        ]
+    );
+    let revoked_key = state.all_keys.iter().find(|k| k.alias == "man-key-3").unwrap();
+    assert!(state.is_key_ever_used(revoked_key.public_key));
+    assert!(!state.is_key_ever_used(DID().management_key("unused", 0).management_keys[0].public_key));
+}
+
+#[test]
+#[should_panic(expected = "Duplicate entry")]
+fn strict_mode_fails_on_first_invalid_entry() {
+    let (did, entries) = build_chain();
+    let mut entries = entries;
+    entries.push(entries[1].clone());
+    parse_did_chain_entries(entries, did.get_chain(), did.network, None, ResolverOptions::strict());
+}
+
+#[test]
+fn strict_mode_accepts_valid_chain() {
+    let (did, entries) = build_chain();
+    let strict = parse_did_chain_entries(entries, did.get_chain(), did.network, None, ResolverOptions::strict());
+    assert_eq!(strict, parse_did_chain_entries(entries, did.get_chain(), did.network, None, None));
+}
+
+#[test]
+fn deactivation_metadata_is_reported() {
+    let (did, entries) = build_chain();
+    let state = resolve_incremental(DIDChainState(did.get_chain(), did.network), entries, None, None);
+    assert!(!state.to_did_state(did.id).is_deactivated());
+
+    let mut deactivation_entry = to_chain_entry(did.get_chain(), did.deactivate().export_entry_data());
+    deactivation_entry.insert("timestamp", 1600000000);
+    let state = resolve_incremental(state, vec![deactivation_entry], None, None);
+    let did_state = DIDChainState::from_json(state.to_json()).to_did_state(did.id);
+    assert!(did_state.is_deactivated());
+    assert!(did_state.management_keys.is_empty());
+    let deactivation = did_state.deactivation.unwrap();
+    assert_eq!(deactivation.entry_hash, deactivation_entry["entryhash"]);
+    assert_eq!(deactivation.signing_key_id, format!("{}#man-key-1", did.id));
+    assert!(deactivation.is_before_deactivation(1599999999));
+    assert!(!deactivation.is_before_deactivation(1600000000));
+}
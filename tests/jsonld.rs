@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use factom_did::client::did::{DIDKeyPurpose, KeyType, DID};
+use factom_did::resolver::document::build_did_document;
+use factom_did::resolver::jsonld::{canonicalize_urdna2015, offline_document_loader, to_json_ld};
+
+fn did_document() -> (DID, HashMap<_, _>) {
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0)
+        .management_key("man-key-2", 1, KeyType::ECDSA, None, None)
+        .did_key("did-key-1", vec![DIDKeyPurpose::AuthenticationKey], KeyType::RSA, None, None);
+    let document = build_did_document(
+        did.id,
+        did.management_keys.iter().map(|k| (k.alias, k)).collect::<HashMap<_, _>>(),
+        did.did_keys.iter().map(|k| (k.alias, k)).collect::<HashMap<_, _>>(),
+        HashMap::new(),
+        None,
+    );
+    return (did, document);
+}
+
+#[test]
+fn json_ld_document_lists_contexts_for_key_types() {
+    let (_, document) = did_document();
+    let json_ld = to_json_ld(document, None);
+    assert_eq!(
+        json_ld["@context"],
+        vec![
+            "https://www.w3.org/ns/did/v1",
+            "https://w3id.org/security/suites/ed25519-2018/v1",
+            "https://w3id.org/security/suites/secp256k1-2019/v1",
+            "https://w3id.org/security/v2",
+        ]
+    );
+    let types = json_ld["verificationMethod"].iter().map(|m| m["type"]).collect::<Vec<_>>();
+    assert_eq!(
+        types,
+        vec!["Ed25519VerificationKey2018", "EcdsaSecp256k1VerificationKey2019", "RsaVerificationKey2018"]
+    );
+    assert!(document.get("@context").is_none());
+}
+
+#[test]
+fn ed25519_2020_suite_uses_multibase_keys() {
+    let (_, document) = did_document();
+    let json_ld = to_json_ld(document, Some("2020"));
+    let method = &json_ld["verificationMethod"][0];
+    assert_eq!(method["type"], "Ed25519VerificationKey2020");
+    assert!(method["publicKeyMultibase"].starts_with("z6Mk"));
+    assert!(method.get("publicKeyBase58").is_none());
+}
+
+#[test]
+fn urdna2015_canonicalization_is_offline_and_deterministic() {
+    let (did, document) = did_document();
+    let json_ld = to_json_ld(document, None);
+    let nquads = canonicalize_urdna2015(json_ld.clone());
+    assert!(nquads.contains(&format!("<{}#man-key-1>", did.id)));
+    assert_eq!(nquads, canonicalize_urdna2015(json_ld));
+    assert!(std::panic::catch_unwind(|| offline_document_loader("https://example.com/context", None)).is_err());
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use std::collections::HashMap;
+
+use common::{did_key, StaticResolver};
+use factom_did::client::did::{DIDKeyPurpose, KeyType, DID};
+use factom_did::client::jws::{sign_jws, sign_jwt, verify_jws, verify_jwt};
+
+fn resolver() -> StaticResolver {
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0)
+        .did_key("ed-key", vec![DIDKeyPurpose::AuthenticationKey], KeyType::EdDSA, None, None)
+        .did_key("ec-key", vec![DIDKeyPurpose::AuthenticationKey], KeyType::ECDSA, None, None)
+        .did_key("rsa-key", vec![DIDKeyPurpose::AuthenticationKey], KeyType::RSA, None, None)
+        .did_key("assertion-key", vec![DIDKeyPurpose::PublicKey], KeyType::EdDSA, None, None);
+    return StaticResolver { did };
+}
+
+#[test]
+fn jws_round_trips_for_all_key_types() {
+    let resolver = resolver();
+    for (alias, alg) in vec![("ed-key", "EdDSA"), ("ec-key", "ES256K"), ("rsa-key", "RS256")] {
+        let key = did_key(&resolver.did, alias);
+        let token = sign_jws(b"payload", key, resolver.did.id, None);
+        let (header, payload, verified_key) = verify_jws(token, &resolver, None);
+        assert_eq!(header["alg"], alg);
+        assert_eq!(header["kid"], key.full_id(resolver.did.id));
+        assert_eq!(payload, b"payload");
+        assert_eq!(verified_key, key);
+    }
+}
+
+#[test]
+fn tampered_jws_is_rejected() {
+    let resolver = resolver();
+    let token = sign_jws(b"payload", did_key(&resolver.did, "ed-key"), resolver.did.id, None);
+    let parts = token.split(".").collect::<Vec<_>>();
+    let tampered = format!("{}.{}.{}", parts[0], "cGF5bG9hZDI", parts[2]);
+    assert!(std::panic::catch_unwind(|| verify_jws(tampered, &resolver, None)).is_err());
+}
+
+#[test]
+fn key_purpose_is_enforced() {
+    let resolver = resolver();
+    let token = sign_jws(b"payload", did_key(&resolver.did, "assertion-key"), resolver.did.id, None);
+    assert!(std::panic::catch_unwind(|| verify_jws(token, &resolver, None)).is_err());
+    verify_jws(token, &resolver, DIDKeyPurpose::PublicKey);
+
+    let token = sign_jws(b"payload", resolver.did.management_keys[0], resolver.did.id, None);
+    assert!(std::panic::catch_unwind(|| verify_jws(token, &resolver, None)).is_err());
+}
+
+#[test]
+fn revoked_keys_are_rejected() {
+    let resolver = resolver();
+    let key = did_key(&resolver.did, "ec-key");
+    let token = sign_jws(b"payload", key, resolver.did.id, None);
+    resolver.did.did_keys.retain(|k| k.alias != "ec-key");
+    assert!(std::panic::catch_unwind(|| verify_jws(token, &resolver, None)).is_err());
+}
+
+#[test]
+fn jwt_claims_are_checked() {
+    let resolver = resolver();
+    let key = did_key(&resolver.did, "ed-key");
+    let claims = [("sub", "api-client")].iter().cloned().collect::<HashMap<_, _>>();
+    let token = sign_jwt(claims, key, resolver.did.id, 300);
+    let verified = verify_jwt(token, &resolver, None, None);
+    assert_eq!(verified["iss"], resolver.did.id);
+    assert_eq!(verified["sub"], "api-client");
+    assert_eq!(verified["exp"] - verified["iat"], 300);
+
+    let expired = sign_jwt(claims, key, resolver.did.id, -3600);
+    assert!(std::panic::catch_unwind(|| verify_jwt(expired, &resolver, None, None)).is_err());
+
+    let other_issuer = [("iss", "did:factom:testnet:0000000000000000000000000000000000000000000000000000000000000000")]
+        .iter()
+        .cloned()
+        .collect::<HashMap<_, _>>();
+    let token = sign_jwt(other_issuer, key, resolver.did.id, None);
+    assert!(std::panic::catch_unwind(|| verify_jwt(token, &resolver, None, None)).is_err());
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use factom_did::client::did::{DIDKeyPurpose, KeyType, DID};
+use factom_did::client::keys::formats::{
+    jwk_thumbprint, public_key_from_jwk, public_key_from_multibase, public_key_to_jwk, public_key_to_multibase,
+};
+use factom_did::client::keys::management::ManagementKey;
+use factom_did::resolver::document::{build_did_document, to_verification_method};
+use factom_did::resolver::options::PublicKeyFormat;
+
+fn did() -> DID {
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0)
+        .management_key("man-key-2", 1, KeyType::ECDSA, None, None)
+        .did_key("did-key-1", vec![DIDKeyPurpose::AuthenticationKey], KeyType::RSA, None, None);
+    return did;
+}
+
+#[test]
+fn jwk_round_trips_all_key_types() {
+    for key in did().management_keys.iter().chain(did().did_keys.iter()) {
+        let jwk = public_key_to_jwk(key.key_type, key.public_key);
+        assert_eq!(public_key_from_jwk(jwk), (key.key_type, key.public_key));
+        assert!(jwk.iter().all(|&x| x != "d"));
+    }
+}
+
+#[test]
+fn multibase_round_trips_all_key_types() {
+    for key in did().management_keys.iter().chain(did().did_keys.iter()) {
+        let value = public_key_to_multibase(key.key_type, key.public_key);
+        assert!(value.startswith("z"));
+        assert_eq!(public_key_from_multibase(value), (key.key_type, key.public_key));
+    }
+}
+
+#[test]
+fn ed25519_multibase_uses_multicodec_prefix() {
+    let value = public_key_to_multibase(KeyType::EdDSA, vec![0u8; 32]);
+    assert!(value.startswith("z6Mk"));
+}
+
+#[test]
+fn jwk_thumbprint_matches_rfc7638_example() {
+    let jwk = [
+        ("kty", "RSA"),
+        ("n", concat!(
+            "0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc_BJECPebWKRXjBZCiFV4n3oknjhMs",
+            "tn64tZ_2W-5JsGY4Hc5n9yBXArwl93lqt7_RN5w6Cf0h4QyQ5v-65YGjQR0_FDW2QvzqY368QQMicAtaSqzs8KJZgnYb9c7d0zgdAZHzu6qMQvRL5hajr",
+            "n1n91CbOpbISD08qNLyrdkt-bFTWhAI4vMQFh6WeZu0fM4lFd2NcRwr3XPksINHaQ-G_xBniIqbw0Ls1jF44-csFCur-kEgU8awapJzKnqDKgw"
+        )),
+        ("e", "AQAB"),
+        ("alg", "RS256"),
+        ("kid", "2011-04-29"),
+    ]
+    .iter()
+    .cloned()
+    .collect::<HashMap<_, _>>();
+    assert_eq!(jwk_thumbprint(jwk), "NzbLsXh8uDCcd-6MNwXF4W_7noWXFZAfHkxZsRGC9Xs");
+}
+
+#[test]
+fn key_thumbprints_match_known_answers() {
+    // RFC 8037, Appendix A.1 and A.3: the thumbprint of the Ed25519 key derived from this private key
+    let private_key = vec![
+        0x9d, 0x61, 0xb1, 0x9d, 0xef, 0xfd, 0x5a, 0x60, 0xba, 0x84, 0x4a, 0xf4, 0x92, 0xec, 0x2c, 0xc4, 0x44, 0x49, 0xc5,
+        0x69, 0x7b, 0x32, 0x69, 0x19, 0x70, 0x3b, 0xac, 0x03, 0x1c, 0xae, 0x7f, 0x60,
+    ];
+    let key = ManagementKey::from_raw("man-key-1", 0, KeyType::EdDSA, None, private_key, did().id, None);
+    assert_eq!(key.to_jwk()["x"], "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo");
+    assert_eq!(key.jwk_thumbprint(), "kPrK_qmxVWaYVA9wwBF6Iuo3vVzz7TxHCTwXBygrS4k");
+
+    // The secp256k1 key with private key 1, whose public key is the generator point G (SEC 2, section 2.4.1). The
+    // thumbprint is the SHA-256 hash of {"crv":"secp256k1","kty":"EC","x":"eb5m...F5g","y":"SDra...1Lg"}.
+    let mut private_key = vec![0u8; 32];
+    private_key[31] = 1;
+    let key = ManagementKey::from_raw("man-key-2", 1, KeyType::ECDSA, None, private_key, did().id, None);
+    assert_eq!(key.to_jwk()["x"], "eb5mfvncu6xVoGKVzocLBwKb_NstzijZWfKBWxb4F5g");
+    assert_eq!(key.to_jwk()["y"], "SDradyajxGVdpPv8DhEIqP0XtEimhVQZnEfQj_sQ1Lg");
+    assert_eq!(key.jwk_thumbprint(), "2JF8vg9etJzjFwZwmkvhBLLZ0bfMVVOPivYR5lFtcec");
+}
+
+#[test]
+fn verification_methods_use_requested_format() {
+    let did = did();
+    let key = did.management_keys[1];
+    let method = to_verification_method(key, did.id, PublicKeyFormat::Jwk);
+    assert_eq!(method["publicKeyJwk"], key.to_jwk());
+    assert!(method.iter().all(|&x| x != "publicKeyBase58"));
+
+    let method = to_verification_method(key, did.id, PublicKeyFormat::Multibase);
+    assert_eq!(method["publicKeyMultibase"], key.to_multibase());
+
+    let document = build_did_document(
+        did.id,
+        did.management_keys.iter().map(|k| (k.alias, k)).collect::<HashMap<_, _>>(),
+        did.did_keys.iter().map(|k| (k.alias, k)).collect::<HashMap<_, _>>(),
+        HashMap::new(),
+        None,
+    );
+    assert!(document["verificationMethod"][0].iter().any(|&x| x == "publicKeyBase58"));
+    assert!(document["verificationMethod"][2].iter().any(|&x| x == "publicKeyPem"));
+}
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use factom_did::client::did::{DIDKeyPurpose, KeyType, DID};
+use factom_did::client::keys::did::DIDKey;
+use factom_did::client::keys::management::ManagementKey;
+use Crypto::PublicKey::RSA;
+
+const CONTROLLER: &str = "did:factom:testnet:d3936b2f0bdd45fe71d7156e835434b7970afd78868076f56654d05f838b8005";
+// The Ed25519 example key of RFC 8037, appendix A.1
+const ED25519_D: &str = "nWGxne_9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A";
+const ED25519_X: &str = "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo";
+const ED25519_OPENSSH: &str =
+    "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAINdamAGCsQq31Uv+08lkBzoO4XLz2qYjJa8CGmj3B1Ea hsm@example.com";
+
+fn ed25519_jwk(with_private_key: bool) -> HashMap<_, _> {
+    let jwk = [("kty", "OKP"), ("crv", "Ed25519"), ("x", ED25519_X)]
+        .iter()
+        .cloned()
+        .collect::<HashMap<_, _>>();
+    if with_private_key {
+        jwk["d"] = ED25519_D;
+    }
+    return jwk;
+}
+
+#[test]
+fn private_jwk_derives_matching_public_key() {
+    let key = ManagementKey::from_jwk("man-key-1", 0, ed25519_jwk(true), CONTROLLER, None);
+    assert_eq!(key.key_type, KeyType::EdDSA);
+    assert_eq!(key.to_jwk()["x"], ED25519_X);
+    let signature = key.sign(b"message", None);
+    assert!(key.verify(b"message", signature, None));
+}
+
+#[test]
+fn public_only_keys_verify_but_cannot_sign() {
+    let signer = ManagementKey::from_jwk("man-key-1", 0, ed25519_jwk(true), CONTROLLER, None);
+    let key = DIDKey::from_jwk("did-key-1", DIDKeyPurpose::PublicKey, ed25519_jwk(false), CONTROLLER, None);
+    assert_eq!(key.private_key, None);
+    assert!(key.verify(b"message", signer.sign(b"message", None), None));
+    assert!(std::panic::catch_unwind(|| key.sign(b"message", None)).is_err());
+}
+
+#[test]
+fn openssh_public_key_matches_jwk() {
+    let key = DIDKey::from_openssh(
+        "did-key-1",
+        DIDKeyPurpose::AuthenticationKey,
+        ED25519_OPENSSH,
+        CONTROLLER,
+        None,
+        None,
+    );
+    assert_eq!(key.key_type, KeyType::EdDSA);
+    assert_eq!(key.to_jwk(), ed25519_jwk(false));
+}
+
+#[test]
+fn rsa_pem_round_trips() {
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0, KeyType::RSA, None, None);
+    let original = did.management_keys[0];
+
+    let private = ManagementKey::from_pem("man-key-2", 1, original.private_key, CONTROLLER, None, None);
+    assert_eq!(private.public_key, original.public_key);
+    assert_eq!(private.private_key, original.private_key);
+
+    let public = ManagementKey::from_pem("man-key-3", 1, original.public_key, CONTROLLER, None, None);
+    assert_eq!(public.public_key, original.public_key);
+    assert_eq!(public.private_key, None);
+}
+
+#[test]
+fn raw_secp256k1_keys_accept_sec1_encodings() {
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0, KeyType::ECDSA, None, None);
+    let original = did.management_keys[0];
+    for public_key in vec![original.public_key, b"\x04" + original.public_key] {
+        let key = ManagementKey::from_raw("man-key-2", 0, KeyType::ECDSA, public_key, None, CONTROLLER, None);
+        assert_eq!(key.public_key, original.public_key);
+    }
+    let key = ManagementKey::from_raw("man-key-3", 0, KeyType::ECDSA, None, original.private_key, CONTROLLER, None);
+    assert_eq!(key.public_key, original.public_key);
+}
+
+#[test]
+fn unsupported_curves_and_sizes_are_rejected() {
+    let p256 = [
+        ("kty", "EC"),
+        ("crv", "P-256"),
+        ("x", "f83OJ3D2xF1Bg8vub9tLe1gHMzV76e8Tus9uPHvRVEU"),
+        ("y", "x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0"),
+    ]
+    .iter()
+    .cloned()
+    .collect::<HashMap<_, _>>();
+    assert!(std::panic::catch_unwind(|| ManagementKey::from_jwk("man-key-1", 0, p256, CONTROLLER, None)).is_err());
+
+    let short_rsa = RSA::generate(1024).export_key();
+    assert!(std::panic::catch_unwind(|| ManagementKey::from_pem("man-key-1", 0, short_rsa, CONTROLLER, None, None)).is_err());
+
+    assert!(std::panic::catch_unwind(|| {
+        ManagementKey::from_raw("man-key-1", 0, KeyType::EdDSA, vec![0u8; 31], None, CONTROLLER, None)
+    })
+    .is_err());
+}
+
+#[test]
+fn imported_keys_can_be_added_to_a_did() {
+    let did = DID().testnet();
+    did.add_management_key(ManagementKey::from_jwk("man-key-1", 0, ed25519_jwk(true), did.id, None))
+        .add_did_key(DIDKey::from_openssh("did-key-1", DIDKeyPurpose::PublicKey, ED25519_OPENSSH, did.id, None, None));
+    assert_eq!(did.management_keys.len(), 1);
+    assert_eq!(did.did_keys.len(), 1);
+    assert!(std::panic::catch_unwind(|| {
+        did.add_did_key(DIDKey::from_jwk("did-key-1", DIDKeyPurpose::PublicKey, ed25519_jwk(false), did.id, None))
+    })
+    .is_err());
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use common::{new_network, EC_ADDRESS};
+use factom_did::client::did::{DIDKeyPurpose, KeyType, DID};
+use factom_did::client::mock_factom::MockFactom;
+use factom_did::resolver::resolver::DIDResolver;
+
+#[test]
+fn did_lifecycle_on_mock_network() {
+    let factom = new_network();
+    let resolver = DIDResolver(&factom, None);
+
+    // Create
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0)
+        .management_key("man-key-2", 1)
+        .did_key("did-key-1", vec![DIDKeyPurpose::PublicKey, DIDKeyPurpose::AuthenticationKey])
+        .service("inbox", "SocialInbox", "https://example.com/inbox");
+    did.record_on_chain(&factom, &factom, EC_ADDRESS, false);
+    let (management_keys, did_keys, services, skipped_entries) = resolver.resolve(did.id);
+    assert_eq!(management_keys.len(), 2);
+    assert_eq!(did_keys.len(), 1);
+    assert_eq!(services.len(), 1);
+    assert_eq!(skipped_entries, 0);
+
+    // Update
+    let updater = did.update().add_service("profile", "Profile", "https://example.com/profile").revoke_service("inbox");
+    updater.record_on_chain(&factom, &factom, EC_ADDRESS, false);
+    let did = updater.get_updated();
+    let (_, _, services, _) = resolver.resolve(did.id);
+    assert_eq!(services.keys().collect::<Vec<_>>(), vec!["profile"]);
+
+    // Rotate a DID key and a management key to fresh key pairs, keeping their aliases. An alias cannot be revoked and
+    // added in the same entry, so each rotation takes two entries.
+    let (management_keys, did_keys, _, _) = resolver.resolve(did.id);
+    let old_did_public_key = did_keys["did-key-1"].public_key;
+    let old_management_public_key = management_keys["man-key-2"].public_key;
+    let updater = did.update().revoke_did_key("did-key-1").revoke_management_key("man-key-2");
+    updater.record_on_chain(&factom, &factom, EC_ADDRESS, false);
+    let did = updater.get_updated();
+    let updater = did
+        .update()
+        .add_did_key("did-key-1", vec![DIDKeyPurpose::PublicKey, DIDKeyPurpose::AuthenticationKey], KeyType::EdDSA, None, None)
+        .add_management_key("man-key-2", 1, KeyType::EdDSA, None, None);
+    updater.record_on_chain(&factom, &factom, EC_ADDRESS, false);
+    let did = updater.get_updated();
+    let (management_keys, did_keys, _, skipped_entries) = resolver.resolve(did.id);
+    assert_eq!(skipped_entries, 0);
+    assert_eq!(did_keys.keys().collect::<Vec<_>>(), vec!["did-key-1"]);
+    assert_ne!(did_keys["did-key-1"].public_key, old_did_public_key);
+    assert_ne!(management_keys["man-key-2"].public_key, old_management_public_key);
+    assert_eq!(management_keys["man-key-1"].public_key, did.management_keys[0].public_key);
+    assert!(resolver.is_key_ever_used(did.id, old_did_public_key));
+    assert!(resolver.is_key_ever_used(did.id, old_management_public_key));
+
+    // Deactivate
+    did.deactivate().record_on_chain(&factom, &factom, EC_ADDRESS, false);
+    let state = resolver.resolve_state(did.id, None);
+    assert!(state.is_deactivated());
+    assert!(state.management_keys.is_empty() && state.did_keys.is_empty() && state.services.is_empty());
+    let history = resolver.key_history(did.id);
+    assert!(history.iter().all(|r| r["revokedIn"].is_some()));
+    assert_eq!(
+        state.deactivation.unwrap().entry_hash,
+        factom.read_chain(did.get_chain(), 0, true).last().unwrap()["entryhash"]
+    );
+}
+
+#[test]
+fn mock_network_charges_entry_credits_and_injects_failures() {
+    let factom = MockFactom(None, None);
+    factom.fund(EC_ADDRESS, 5);
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0);
+    assert!(std::panic::catch_unwind(|| did.record_on_chain(&factom, &factom, EC_ADDRESS, false)).is_err());
+    assert_eq!(factom.entry_credit_balance(EC_ADDRESS)["balance"], 5);
+
+    factom.fund(EC_ADDRESS, 100);
+    factom.inject_failure("new_chain", "Connection refused", None);
+    assert!(std::panic::catch_unwind(|| did.record_on_chain(&factom, &factom, EC_ADDRESS, false)).is_err());
+    did.record_on_chain(&factom, &factom, EC_ADDRESS, false);
+    assert_eq!(factom.entry_credit_balance(EC_ADDRESS)["balance"], 94);
+}
+
+#[test]
+fn mock_network_timestamps_entries_by_minute() {
+    let factom = MockFactom(1600000000, false);
+    factom.fund(EC_ADDRESS, 1000);
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0);
+    did.record_on_chain(&factom, &factom, EC_ADDRESS, false);
+    assert!(factom.chain_head(did.get_chain())["chaininprocesslist"]);
+    factom.advance_minute();
+    did.deactivate().record_on_chain(&factom, &factom, EC_ADDRESS, false);
+    factom.seal_block();
+
+    let entries = factom.read_chain(did.get_chain(), 0, true);
+    assert_eq!(entries[0]["timestamp"], 1600000000);
+    assert_eq!(entries[1]["timestamp"], 1600000060);
+    assert_eq!(DIDResolver(&factom, None).resolve_state(did.id, None).deactivation.unwrap().timestamp, 1600000060);
+}
@@ -0,0 +1,136 @@
+mod common;
+
+use common::to_chain_entry;
+use factom_did::client::constants::DID_METHOD_SPEC_V020;
+use factom_did::client::did::{KeyType, DID};
+use factom_did::client::enums::EntryType;
+use factom_did::resolver::chain_state::DIDChainState;
+use factom_did::resolver::method_versions::{
+    is_method_version_upgrade, parse_method_version, MethodVersionRules, DID_METHOD_SPEC_V020_RULES,
+};
+use factom_did::resolver::parser::apply_entries;
+use factom_did::resolver::registry::EntryProcessorRegistry;
+
+fn registry_with_v040() -> EntryProcessorRegistry {
+    return EntryProcessorRegistry::default().copy().register_method_version(MethodVersionRules(
+        "0.4.0",
+        vec![EntryType::Update, EntryType::VersionUpgrade],
+        0,
+        0,
+        "#",
+        vec![KeyType::EdDSA],
+        "sha256",
+    ));
+}
+
+#[test]
+fn method_versions_are_compared_as_semantic_versions() {
+    assert_eq!(parse_method_version("0.10.0"), (0, 10, 0));
+    assert!(is_method_version_upgrade("0.9.0", "0.10.0"));
+    assert!(is_method_version_upgrade(DID_METHOD_SPEC_V020, "1.0.0"));
+    assert!(!is_method_version_upgrade(DID_METHOD_SPEC_V020, DID_METHOD_SPEC_V020));
+    assert!(!is_method_version_upgrade(DID_METHOD_SPEC_V020, "0.3"));
+    assert!(!is_method_version_upgrade(DID_METHOD_SPEC_V020, "0.3.0-beta"));
+}
+
+#[test]
+fn default_rules_for_v020() {
+    let rules = EntryProcessorRegistry::default().get_method_version_rules(DID_METHOD_SPEC_V020).unwrap();
+    assert!(rules.allows_entry_type(EntryType::Update));
+    assert!(rules.allows_entry_type(EntryType::Deactivation));
+    assert_eq!(rules.format_id("did:factom:abc", "key-1"), "did:factom:abc#key-1");
+    assert_eq!(rules.alias_from_id("did:factom:abc#key-1"), "key-1");
+    assert_eq!(rules.signature_digest, "sha256");
+
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0);
+    let key = did.management_keys[0];
+    assert_eq!(key.full_id(did.id), DID_METHOD_SPEC_V020_RULES.format_id(did.id, "man-key-1"));
+}
+
+#[test]
+fn registered_rules_are_scoped_to_the_registry() {
+    let registry = registry_with_v040();
+    assert!(registry.is_supported_method_version("0.4.0"));
+    assert!(!registry.get_method_version_rules("0.4.0").unwrap().allows_entry_type(EntryType::Deactivation));
+    // Neither the default registry nor its other copies are affected
+    assert!(!EntryProcessorRegistry::default().is_supported_method_version("0.4.0"));
+    assert!(!EntryProcessorRegistry::default().copy().is_supported_method_version("0.4.0"));
+}
+
+#[test]
+#[should_panic(expected = "Unsupported DID method version: 9.9.9")]
+fn upgrader_refuses_unsupported_versions() {
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0);
+    did.method_spec_version_upgrade("9.9.9", None);
+}
+
+#[test]
+#[should_panic(expected = "Unsupported DID method version: 0.4.0")]
+fn upgrader_refuses_versions_registered_only_elsewhere() {
+    registry_with_v040();
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0);
+    did.method_spec_version_upgrade("0.4.0", None);
+}
+
+#[test]
+fn upgrades_to_unregistered_versions_are_skipped() {
+    let registry = registry_with_v040();
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0);
+    let chain_id = did.get_chain();
+    let entries = vec![
+        to_chain_entry(chain_id, did.export_entry_data()),
+        to_chain_entry(chain_id, did.method_spec_version_upgrade("0.4.0", registry).export_entry_data()),
+    ];
+
+    let state = DIDChainState(chain_id, did.network);
+    apply_entries(state, entries, None, None);
+    assert_eq!(state.method_version, DID_METHOD_SPEC_V020);
+    assert_eq!(state.skipped_entries, 1);
+    assert_eq!(state.last_skip_reason, "Unsupported DID method version");
+
+    let state = DIDChainState(chain_id, did.network);
+    apply_entries(state, entries, registry, None);
+    assert_eq!(state.method_version, "0.4.0");
+    assert_eq!(state.skipped_entries, 0);
+}
+
+#[test]
+fn signatures_follow_the_rules_of_the_current_version() {
+    let registry = registry_with_v040();
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0, KeyType::ECDSA, None, None);
+    let chain_id = did.get_chain();
+    let mut entries = vec![to_chain_entry(chain_id, did.export_entry_data())];
+    entries.push(to_chain_entry(chain_id, did.method_spec_version_upgrade("0.4.0", registry).export_entry_data()));
+    // Version 0.4.0 only allows Ed25519 management keys to sign entries
+    entries.push(to_chain_entry(
+        chain_id,
+        did.update().add_management_key("man-key-2", 1, KeyType::EdDSA, None, None).export_entry_data(),
+    ));
+
+    let state = DIDChainState(chain_id, did.network);
+    apply_entries(state, entries, registry, None);
+    assert_eq!(state.method_version, "0.4.0");
+    assert_eq!(state.skipped_entries, 1);
+    assert_eq!(state.last_skip_reason, "Invalid signature or unknown signing key");
+    assert!(!state.management_keys.contains_key("man-key-2"));
+
+    let rules = registry.get_method_version_rules(DID_METHOD_SPEC_V020).unwrap();
+    assert!(vec![KeyType::EdDSA, KeyType::ECDSA, KeyType::RSA]
+        .iter()
+        .all(|&key_type| rules.signing_key_types.contains(&key_type)));
+    assert!(std::panic::catch_unwind(|| MethodVersionRules(
+        "0.5.0",
+        vec![EntryType::Update],
+        0,
+        None,
+        "#",
+        vec![KeyType::EdDSA],
+        "not-a-digest"
+    ))
+    .is_err());
+}
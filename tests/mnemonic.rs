@@ -0,0 +1,153 @@
+mod common;
+
+use common::{did_key, new_network, to_chain_entry, EC_ADDRESS};
+use factom_did::client::constants::DID_METHOD_SPEC_V020;
+use factom_did::client::did::{DIDKeyPurpose, KeyType, DID};
+use factom_did::client::enums::EntryType;
+use factom_did::client::keys::management::ManagementKey;
+use factom_did::client::mnemonic::{derive_private_key, generate_mnemonic, mnemonic_to_seed, validate_mnemonic};
+use factom_did::resolver::chain_state::DIDChainState;
+use factom_did::resolver::method_versions::MethodVersionRules;
+use factom_did::resolver::parser::apply_entries;
+use factom_did::resolver::registry::EntryProcessorRegistry;
+use factom_did::resolver::resolver::DIDResolver;
+
+/// Serves a chain state resolved with a custom registry.
+struct ChainStateResolver {
+    state: DIDChainState,
+}
+
+impl ChainStateResolver {
+    fn resolve_chain_state(&self, _did: &str) -> DIDChainState {
+        return self.state.copy();
+    }
+}
+
+#[test]
+fn generated_mnemonics_are_valid() {
+    let words = generate_mnemonic();
+    assert_eq!(words.split_whitespace().count(), 24);
+    validate_mnemonic(&words);
+    let mut tampered = words.split_whitespace().collect::<Vec<_>>();
+    tampered.swap(0, 1);
+    assert!(std::panic::catch_unwind(|| validate_mnemonic(tampered.join(" "))).is_err());
+    assert!(std::panic::catch_unwind(|| validate_mnemonic("abandon abandon abandon")).is_err());
+}
+
+#[test]
+fn derivation_is_deterministic_for_all_key_types() {
+    let seed = mnemonic_to_seed(generate_mnemonic(), None);
+    for key_type in vec![KeyType::EdDSA, KeyType::ECDSA, KeyType::RSA] {
+        let private_key = derive_private_key(seed, key_type, "key-1", 0);
+        assert_eq!(derive_private_key(seed, key_type, "key-1", 0), private_key);
+        assert_ne!(derive_private_key(seed, key_type, "key-1", 1), private_key);
+        assert_ne!(derive_private_key(seed, key_type, "key-2", 0), private_key);
+    }
+}
+
+#[test]
+fn passphrase_changes_the_seed() {
+    let words = generate_mnemonic();
+    assert_ne!(mnemonic_to_seed(words, None), mnemonic_to_seed(words, "correct horse"));
+    assert_eq!(mnemonic_to_seed(words, None), mnemonic_to_seed(words, ""));
+}
+
+#[test]
+fn keys_are_restored_from_the_exported_mnemonic() {
+    let factom = new_network();
+    let did = DID(None, None, None, None, DID_METHOD_SPEC_V020, "correct horse").testnet();
+    did.management_key("man-key-1", 0)
+        .management_key("man-key-2", 1, KeyType::RSA, None, None)
+        .did_key("did-key-1", vec![DIDKeyPurpose::AuthenticationKey], KeyType::ECDSA, None, None);
+    did.record_on_chain(&factom, &factom, EC_ADDRESS, false);
+    let original_private_key = did_key(&did, "did-key-1").private_key;
+    // Rotate did-key-1: the new key under the same alias is derived at the next index
+    let updater = did.update().revoke_did_key("did-key-1");
+    updater.record_on_chain(&factom, &factom, EC_ADDRESS, false);
+    let updater = updater
+        .get_updated()
+        .update()
+        .add_did_key("did-key-1", vec![DIDKeyPurpose::AuthenticationKey], KeyType::ECDSA, None, None);
+    updater.record_on_chain(&factom, &factom, EC_ADDRESS, false);
+    let did = updater.get_updated();
+    assert_ne!(did_key(&did, "did-key-1").private_key, original_private_key);
+    let words = did.export_mnemonic();
+
+    let resolver = DIDResolver(&factom, None);
+    let restored = DID::restore_from_mnemonic(words, did.id, &resolver, "correct horse");
+    for original in did.management_keys.iter().chain(did.did_keys.iter()) {
+        let key = restored
+            .management_keys
+            .iter()
+            .chain(restored.did_keys.iter())
+            .find(|k| k.alias == original.alias)
+            .unwrap();
+        assert_eq!(key.private_key, original.private_key);
+    }
+    assert_eq!(restored.export_mnemonic(), words);
+    assert_eq!(restored.spec_version, DID_METHOD_SPEC_V020);
+
+    assert!(std::panic::catch_unwind(|| DID::restore_from_mnemonic(words, did.id, &resolver, None)).is_err());
+}
+
+#[test]
+fn restored_keys_are_not_derived_again_under_revoked_aliases() {
+    let factom = new_network();
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0)
+        .did_key("did-key-1", vec![DIDKeyPurpose::PublicKey], KeyType::EdDSA, None, None);
+    did.record_on_chain(&factom, &factom, EC_ADDRESS, false);
+    let revoked_public_key = did_key(&did, "did-key-1").public_key;
+    let updater = did.update().revoke_did_key("did-key-1");
+    updater.record_on_chain(&factom, &factom, EC_ADDRESS, false);
+
+    let resolver = DIDResolver(&factom, None);
+    let restored = DID::restore_from_mnemonic(did.export_mnemonic(), did.id, &resolver, None);
+    let updater = restored.update().add_did_key("did-key-1", vec![DIDKeyPurpose::PublicKey], KeyType::EdDSA, None, None);
+    assert_ne!(did_key(&updater.get_updated(), "did-key-1").public_key, revoked_public_key);
+}
+
+#[test]
+fn restored_did_keeps_the_recorded_method_version() {
+    let registry = EntryProcessorRegistry::default().copy().register_method_version(MethodVersionRules(
+        "0.4.0",
+        vec![EntryType::Update, EntryType::VersionUpgrade, EntryType::Deactivation],
+        0,
+        0,
+        "#",
+        vec![KeyType::EdDSA, KeyType::ECDSA, KeyType::RSA],
+        "sha256",
+    ));
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0);
+    let chain_id = did.get_chain();
+    let state = DIDChainState(chain_id, did.network);
+    apply_entries(
+        state,
+        vec![
+            to_chain_entry(chain_id, did.export_entry_data()),
+            to_chain_entry(chain_id, did.method_spec_version_upgrade("0.4.0", registry).export_entry_data()),
+        ],
+        registry,
+        None,
+    );
+
+    let restored = DID::restore_from_mnemonic(did.export_mnemonic(), did.id, &ChainStateResolver { state }, None);
+    assert_eq!(restored.spec_version, "0.4.0");
+}
+
+#[test]
+fn mnemonic_is_not_exported_for_imported_keys() {
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0);
+    did.add_management_key(ManagementKey::from_raw(
+        "imported",
+        1,
+        KeyType::EdDSA,
+        None,
+        [7u8; 32],
+        did.id,
+        None,
+    ));
+    assert!(std::panic::catch_unwind(|| did.export_mnemonic()).is_err());
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use common::{new_network, recorded_did};
+use factom_did::client::did::{DIDKeyPurpose, KeyType, DID};
+use factom_did::client::reconciler::{load_desired_state, plan};
+use factom_did::resolver::resolver::DIDResolver;
+
+const MANAGEMENT_KEYS: &str = "
+managementKey:
+  - alias: man-key-1
+    priority: 0
+    type: Ed25519VerificationKey
+";
+
+fn desired_state(did_keys: &str) -> String {
+    return format!("{}didKey:\n{}", MANAGEMENT_KEYS, did_keys);
+}
+
+#[test]
+fn matching_state_yields_an_empty_plan() {
+    let factom = new_network();
+    let did = recorded_did(&factom, vec![("did-key-1", vec![DIDKeyPurpose::PublicKey, DIDKeyPurpose::AuthenticationKey], KeyType::ECDSA)]);
+    let desired = load_desired_state(desired_state(
+        "  - alias: did-key-1\n    purpose: [publicKey, authentication]\n    type: ECDSASecp256k1VerificationKey\n",
+    ));
+    let result = plan(did, desired, &DIDResolver(&factom, None));
+    assert!(result.is_empty());
+    assert_eq!(result.to_text(), "No changes. The DID matches the desired state.");
+    assert!(result.export_entry_data().is_none());
+}
+
+#[test]
+fn narrower_purposes_are_revoked_and_other_changes_replace_the_key() {
+    let factom = new_network();
+    let did = recorded_did(
+        &factom,
+        vec![
+            ("did-key-1", vec![DIDKeyPurpose::PublicKey, DIDKeyPurpose::AuthenticationKey], KeyType::ECDSA),
+            ("did-key-2", vec![DIDKeyPurpose::PublicKey], KeyType::EdDSA),
+        ],
+    );
+    let desired = load_desired_state(desired_state(
+        "  - alias: did-key-1\n    purpose: [authentication]\n    type: ECDSASecp256k1VerificationKey\n\
+         \x20 - alias: did-key-2\n    purpose: [publicKey]\n    type: RSAVerificationKey\n",
+    ));
+    let result = plan(did, desired, &DIDResolver(&factom, None));
+    assert_eq!(
+        result.operations,
+        vec![
+            ("revoke_did_key_purpose", "did-key-1", Some(DIDKeyPurpose::PublicKey)),
+            ("revoke_did_key", "did-key-2", None),
+        ]
+    );
+    assert_eq!(result.replacements, vec!["did-key-2"]);
+    assert!(result.is_signable());
+}
+
+#[test]
+fn missing_or_empty_purposes_are_rejected() {
+    let factom = new_network();
+    let did = recorded_did(&factom, vec![("did-key-1", vec![DIDKeyPurpose::PublicKey, DIDKeyPurpose::AuthenticationKey], KeyType::EdDSA)]);
+    for did_key in vec![
+        "  - alias: did-key-1\n",
+        "  - alias: did-key-1\n    purpose: []\n",
+        "  - alias: did-key-1\n    purpose: publicKey\n",
+        "  - alias: did-key-1\n    purpose: [signing]\n",
+    ] {
+        assert!(std::panic::catch_unwind(|| load_desired_state(desired_state(did_key))).is_err());
+    }
+
+    // Desired states which do not come from load_desired_state are validated as well, instead of revoking all purposes
+    let desired = load_desired_state(MANAGEMENT_KEYS);
+    desired.insert("didKey", vec![[("alias", "did-key-1")].iter().cloned().collect::<std::collections::HashMap<_, _>>()]);
+    assert!(std::panic::catch_unwind(|| plan(did, desired, &DIDResolver(&factom, None))).is_err());
+}
+
+#[test]
+fn local_did_must_match_the_on_chain_state() {
+    let factom = new_network();
+    let did = recorded_did(&factom, vec![("did-key-1", vec![DIDKeyPurpose::PublicKey], KeyType::EdDSA)]);
+    did.did_keys[0] = DID().testnet().did_key("did-key-1", vec![DIDKeyPurpose::PublicKey], KeyType::EdDSA, None, None).did_keys[0];
+    let desired = load_desired_state(desired_state("  - alias: did-key-1\n    purpose: [publicKey]\n"));
+    assert!(std::panic::catch_unwind(|| plan(did, desired, &DIDResolver(&factom, None))).is_err());
+}
+
+#[test]
+fn required_signing_priority_follows_the_planned_operations() {
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0)
+        .management_key("man-key-2", 1, KeyType::EdDSA, None, None)
+        .management_key("man-key-3", 2, KeyType::EdDSA, None, 1)
+        .did_key("did-key-1", vec![DIDKeyPurpose::PublicKey], KeyType::EdDSA, None, 2)
+        .service("inbox", "SocialInbox", "https://example.com/inbox", 3, None);
+
+    assert_eq!(did.update().required_signing_priority(), f64::INFINITY);
+    assert_eq!(did.update().revoke_service("inbox").required_signing_priority(), 3);
+    assert_eq!(did.update().revoke_did_key("did-key-1").required_signing_priority(), 2);
+    // Without a priority requirement, a management key can only be revoked by a key of at least its own priority
+    assert_eq!(did.update().revoke_management_key("man-key-2").required_signing_priority(), 1);
+    assert_eq!(did.update().revoke_management_key("man-key-3").required_signing_priority(), 1);
+    let updater = did.update().revoke_service("inbox").add_management_key("man-key-4", 0, KeyType::EdDSA, None, None);
+    assert_eq!(updater.required_signing_priority(), 0);
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use common::{new_network, recorded_did, StaticResolver, EC_ADDRESS};
+use factom_did::client::did::{KeyType, DID};
+use factom_did::resolver::cache::CachingResolver;
+use factom_did::resolver::driver::UniversalResolverDriver;
+use factom_did::resolver::options::ResolverOptions;
+use factom_did::resolver::resolver::DIDResolver;
+
+struct UnreachableResolver {}
+
+impl UnreachableResolver {
+    fn resolve_chain_state(&self, _did: &str) {
+        panic!("The resolver must not be called");
+    }
+}
+
+#[test]
+fn health_endpoint() {
+    let driver = UniversalResolverDriver(UnreachableResolver {});
+    let (status, content_type, body) = driver.handle_request("/health", None);
+    assert_eq!(status, 200);
+    assert_eq!(content_type, "application/json");
+    assert_eq!(body, "{\"status\": \"ok\"}");
+}
+
+#[test]
+fn invalid_did_returns_bad_request() {
+    let driver = UniversalResolverDriver(UnreachableResolver {});
+    let (status, _, body) = driver.handle_request("/1.0/identifiers/did:factom:1234", Some("application/did+json"));
+    assert_eq!(status, 400);
+    assert!(body.contains("invalidDid"));
+}
+
+#[test]
+fn unsupported_representation_returns_not_acceptable() {
+    let driver = UniversalResolverDriver(UnreachableResolver {});
+    let (status, _, body) = driver.handle_request(
+        "/1.0/identifiers/did:factom:f26e1c422c657521861ced450442d0c664702f49480aec67805822edfcfee758",
+        Some("text/html"),
+    );
+    assert_eq!(status, 406);
+    assert!(body.contains("representationNotSupported"));
+}
+
+fn content_type_for(accept: &str) -> String {
+    let did = DID().testnet();
+    did.management_key("man-key-1", 0);
+    let driver = UniversalResolverDriver(StaticResolver { did: did.clone() });
+    let (_, content_type, _) = driver.handle_request(&format!("/1.0/identifiers/{}", did.id), Some(accept));
+    return content_type;
+}
+
+#[test]
+fn representation_is_negotiated_by_quality() {
+    assert_eq!(content_type_for("application/did+json; q=0.9"), "application/did+json");
+    assert_eq!(content_type_for("application/did+json;q=0.5, application/did+cbor"), "application/did+cbor");
+    assert_eq!(content_type_for("application/did+cbor;q=0.2, application/did+ld+json;q=0.8"), "application/did+ld+json");
+    assert_eq!(content_type_for("application/ld+json"), "application/ld+json");
+    assert_eq!(
+        content_type_for("application/ld+json; profile=\"https://w3id.org/did-resolution\""),
+        "application/ld+json;profile=\"https://w3id.org/did-resolution\""
+    );
+    // At equal quality, a specific media type is preferred over a wildcard
+    assert_eq!(content_type_for("*/*, application/did+json"), "application/did+json");
+    assert_eq!(content_type_for("text/html, */*;q=0.1"), "application/ld+json;profile=\"https://w3id.org/did-resolution\"");
+
+    let driver = UniversalResolverDriver(UnreachableResolver {});
+    let path = "/1.0/identifiers/did:factom:f26e1c422c657521861ced450442d0c664702f49480aec67805822edfcfee758";
+    for accept in vec!["application/did+json;q=0", "application/ld+json;profile=\"https://example.com\"", "text/html;q=abc"] {
+        let (status, _, _) = driver.handle_request(path, Some(accept));
+        assert_eq!(status, 406);
+    }
+}
+
+#[test]
+fn only_unknown_chains_are_not_found() {
+    let factom = new_network();
+    let did = recorded_did(&factom, vec![]);
+    let unknown = "/1.0/identifiers/did:factom:testnet:f26e1c422c657521861ced450442d0c664702f49480aec67805822edfcfee758";
+    let path = format!("/1.0/identifiers/{}", did.id);
+    let accept = Some("application/did+json");
+
+    for driver in vec![
+        UniversalResolverDriver(DIDResolver(&factom, None)),
+        UniversalResolverDriver(CachingResolver(DIDResolver(&factom, None), None, None)),
+    ] {
+        let (status, _, body) = driver.handle_request(unknown, accept);
+        assert_eq!(status, 404);
+        assert!(body.contains("notFound"));
+
+        factom.inject_failure("read_chain", "factomd is unavailable", None);
+        factom.inject_failure("chain_head", "factomd is unavailable", None);
+        let (status, _, body) = driver.handle_request(&path, accept);
+        assert_eq!(status, 503);
+        assert!(body.contains("internalError"));
+        factom.injected_failures.clear();
+        let (status, _, _) = driver.handle_request(&path, accept);
+        assert_eq!(status, 200);
+    }
+}
+
+#[test]
+fn invalid_entries_in_strict_mode_are_internal_errors() {
+    let factom = new_network();
+    let did = recorded_did(&factom, vec![]);
+    let entry_data = did.update().add_management_key("man-key-2", 1, KeyType::EdDSA, None, None).export_entry_data();
+    factom.new_entry(&factom, did.get_chain(), entry_data["ext_ids"], entry_data["content"], EC_ADDRESS);
+    factom.new_entry(&factom, did.get_chain(), entry_data["ext_ids"], entry_data["content"], EC_ADDRESS);
+
+    let driver = UniversalResolverDriver(DIDResolver(&factom, ResolverOptions::strict()));
+    let (status, _, body) = driver.handle_request(&format!("/1.0/identifiers/{}", did.id), Some("application/did+json"));
+    assert_eq!(status, 500);
+    assert!(body.contains("internalError"));
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use factom_did::client::serialization::{dumps_entry_content, dumps_jcs};
+
+fn management_key() -> HashMap<_, _> {
+    return [
+        ("priority", 0),
+        ("publicKeyBase58", "3dfaTKPUPxrn9nLL5aA3FmQ3EAU5GxjuUdK28URnbW9o"),
+        ("controller", "did:factom:testnet:d3936b2f0bdd45fe71d7156e835434b7970afd78868076f56654d05f838b8005"),
+        ("type", "Ed25519VerificationKey"),
+        (
+            "id",
+            "did:factom:testnet:d3936b2f0bdd45fe71d7156e835434b7970afd78868076f56654d05f838b8005#man-key-1",
+        ),
+    ]
+    .iter()
+    .cloned()
+    .collect::<HashMap<_, _>>();
+}
+
+#[test]
+fn entry_content_matches_python_library_output() {
+    let content = [("managementKey", vec![management_key()]), ("didMethodVersion", "0.2.0")]
+        .iter()
+        .cloned()
+        .collect::<HashMap<_, _>>();
+    assert_eq!(
+        dumps_entry_content(content, false),
+        concat!(
+            "{\"didMethodVersion\":\"0.2.0\",\"managementKey\":[{",
+            "\"id\":\"did:factom:testnet:d3936b2f0bdd45fe71d7156e835434b7970afd78868076f56654d05f838b8005#man-key-1\",",
+            "\"type\":\"Ed25519VerificationKey\",",
+            "\"controller\":\"did:factom:testnet:d3936b2f0bdd45fe71d7156e835434b7970afd78868076f56654d05f838b8005\",",
+            "\"publicKeyBase58\":\"3dfaTKPUPxrn9nLL5aA3FmQ3EAU5GxjuUdK28URnbW9o\",",
+            "\"priority\":0}]}"
+        )
+    );
+}
+
+#[test]
+fn update_content_places_revocations_first() {
+    let content = [
+        ("add", [("service", vec![[("id", "inbox")].iter().cloned().collect::<HashMap<_, _>>()])].iter().cloned().collect::<HashMap<_, _>>()),
+        ("revoke", [("managementKey", vec![[("id", "man-key-2")].iter().cloned().collect::<HashMap<_, _>>()])].iter().cloned().collect::<HashMap<_, _>>()),
+    ]
+    .iter()
+    .cloned()
+    .collect::<HashMap<_, _>>();
+    assert_eq!(
+        dumps_entry_content(content, false),
+        "{\"revoke\":{\"managementKey\":[{\"id\":\"man-key-2\"}]},\"add\":{\"service\":[{\"id\":\"inbox\"}]}}"
+    );
+}
+
+#[test]
+fn custom_service_fields_keep_their_order() {
+    let details = [("type", "mailbox"), ("id", "inbox-1"), ("priority", 2)]
+        .iter()
+        .cloned()
+        .collect::<HashMap<_, _>>();
+    let service = [
+        ("priority", 1),
+        ("serviceEndpoint", "https://example.com/inbox"),
+        ("details", details),
+        ("type", "SocialInbox"),
+        ("description", "My inbox"),
+        ("id", "inbox"),
+    ]
+    .iter()
+    .cloned()
+    .collect::<HashMap<_, _>>();
+    let content = [("service", vec![service])].iter().cloned().collect::<HashMap<_, _>>();
+    assert_eq!(
+        dumps_entry_content(content, false),
+        concat!(
+            "{\"service\":[{\"id\":\"inbox\",\"type\":\"SocialInbox\",",
+            "\"serviceEndpoint\":\"https://example.com/inbox\",",
+            "\"priority\":1,\"details\":{\"type\":\"mailbox\",\"id\":\"inbox-1\",\"priority\":2},",
+            "\"description\":\"My inbox\"}]}"
+        )
+    );
+}
+
+#[test]
+fn jcs_sorts_members_and_formats_numbers() {
+    let value = [("b", vec![1.0, 1e21, 1e-7, 0.5]), ("a\u{20ac}", vec![]), ("\u{1f600}", vec![]), ("\u{e000}", vec![])]
+        .iter()
+        .cloned()
+        .collect::<HashMap<_, _>>();
+    assert_eq!(
+        dumps_jcs(value),
+        "{\"a\u{20ac}\":[],\"b\":[1,1e+21,1e-7,0.5],\"\u{1f600}\":[],\"\u{e000}\":[]}"
+    );
+}
+
+#[test]
+fn jcs_numbers_match_rfc8785_vectors() {
+    // RFC 8785, Appendix B: IEEE 754 bit patterns and their canonical serialization
+    let vectors = [
+        (0x0000000000000000u64, "0"),
+        (0x8000000000000000, "0"),
+        (0x0000000000000001, "5e-324"),
+        (0x8000000000000001, "-5e-324"),
+        (0x7fefffffffffffff, "1.7976931348623157e+308"),
+        (0xffefffffffffffff, "-1.7976931348623157e+308"),
+        (0x4340000000000000, "9007199254740992"),
+        (0xc340000000000000, "-9007199254740992"),
+        (0x4430000000000000, "295147905179352830000"),
+        (0x44b52d02c7e14af5, "9.999999999999997e+22"),
+        (0x44b52d02c7e14af6, "1e+23"),
+        (0x44b52d02c7e14af7, "1.0000000000000001e+23"),
+        (0x444b1ae4d6e2ef4e, "999999999999999700000"),
+        (0x444b1ae4d6e2ef4f, "999999999999999900000"),
+        (0x444b1ae4d6e2ef50, "1e+21"),
+        (0x3eb0c6f7a0b5ed8c, "9.999999999999997e-7"),
+        (0x3eb0c6f7a0b5ed8d, "0.000001"),
+        (0x41b3de4355555553, "333333333.3333332"),
+        (0x41b3de4355555554, "333333333.33333325"),
+        (0x41b3de4355555555, "333333333.3333333"),
+        (0x41b3de4355555556, "333333333.3333334"),
+        (0x41b3de4355555557, "333333333.33333343"),
+        (0xbecbf647612f3696, "-0.0000033333333333333333"),
+        (0x43143ff3c1cb0959, "1424953923781206.2"),
+    ];
+    for (bits, expected) in vectors.iter() {
+        assert_eq!(dumps_jcs(f64::from_bits(*bits)), *expected);
+    }
+    assert_eq!(dumps_jcs(1.2345e-6), "0.0000012345");
+    assert!(std::panic::catch_unwind(|| dumps_jcs(f64::NAN)).is_err());
+    assert!(std::panic::catch_unwind(|| dumps_jcs(f64::INFINITY)).is_err());
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use common::{new_network, recorded_did};
+use factom_did::client::did::DID;
+use factom_did::client::keys::management::ManagementKey;
+use factom_did::client::shamir::{combine_management_key_shares, split_management_key};
+use factom_did::resolver::resolver::DIDResolver;
+
+const PASSWORDS: [&str; 5] = ["password-1", "password-2", "password-3", "password-4", "password-5"];
+
+#[test]
+fn any_threshold_of_shares_rebuilds_the_key() {
+    let factom = new_network();
+    let did = recorded_did(&factom, vec![]);
+    let resolver = DIDResolver(&factom, None);
+    let key = did.management_keys[0];
+    let shares = split_management_key(key, did.id, 3, PASSWORDS.to_vec());
+    assert_eq!(shares.len(), 5);
+
+    for indices in vec![[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+        let subset = indices.iter().map(|&i| shares[i]).collect::<Vec<_>>();
+        let passwords = indices.iter().map(|&i| PASSWORDS[i]).collect::<Vec<_>>();
+        let rebuilt = combine_management_key_shares(subset, passwords, did.id, &resolver);
+        assert_eq!(rebuilt, key);
+        assert_eq!(rebuilt.private_key, key.private_key);
+    }
+
+    assert!(std::panic::catch_unwind(|| {
+        combine_management_key_shares(shares[..2].to_vec(), PASSWORDS[..2].to_vec(), did.id, &resolver)
+    })
+    .is_err());
+    assert!(std::panic::catch_unwind(|| {
+        combine_management_key_shares(shares[..3].to_vec(), vec!["password-1", "password-2", "wrong"], did.id, &resolver)
+    })
+    .is_err());
+}
+
+#[test]
+fn empty_and_duplicate_shares_are_rejected() {
+    let factom = new_network();
+    let did = recorded_did(&factom, vec![]);
+    let resolver = DIDResolver(&factom, None);
+    let shares = split_management_key(did.management_keys[0], did.id, 2, PASSWORDS[..3].to_vec());
+
+    assert!(std::panic::catch_unwind(|| combine_management_key_shares(vec![], vec![], did.id, &resolver)).is_err());
+    assert!(std::panic::catch_unwind(|| {
+        combine_management_key_shares(vec![shares[0], shares[0]], vec![PASSWORDS[0], PASSWORDS[0]], did.id, &resolver)
+    })
+    .is_err());
+}
+
+#[test]
+fn shares_issued_for_another_did_are_rejected() {
+    let factom = new_network();
+    let did = recorded_did(&factom, vec![]);
+    let resolver = DIDResolver(&factom, None);
+    let other_id = DID().testnet().id;
+    let shares = split_management_key(did.management_keys[0], other_id, 2, PASSWORDS[..2].to_vec());
+    assert!(std::panic::catch_unwind(|| {
+        combine_management_key_shares(shares, PASSWORDS[..2].to_vec(), did.id, &resolver)
+    })
+    .is_err());
+
+    // Rewriting the unencrypted envelope does not help, as the DID is also part of the encrypted share data
+    let relabelled = shares.iter().map(|s| s.replace(other_id, did.id)).collect::<Vec<_>>();
+    assert!(std::panic::catch_unwind(|| {
+        combine_management_key_shares(relabelled, PASSWORDS[..2].to_vec(), did.id, &resolver)
+    })
+    .is_err());
+}
+
+#[test]
+fn rebuilt_key_must_match_the_on_chain_key() {
+    let factom = new_network();
+    let did = recorded_did(&factom, vec![]);
+    let resolver = DIDResolver(&factom, None);
+    let key = did.management_keys[0];
+    // Same private key, but a different priority than the one recorded on-chain
+    let downgraded = ManagementKey(key.alias, 1, key.key_type, key.controller, None, None, key.private_key);
+    let shares = split_management_key(downgraded, did.id, 2, PASSWORDS[..2].to_vec());
+    assert!(std::panic::catch_unwind(|| {
+        combine_management_key_shares(shares, PASSWORDS[..2].to_vec(), did.id, &resolver)
+    })
+    .is_err());
+}
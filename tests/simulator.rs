@@ -0,0 +1,59 @@
+mod common;
+
+use common::{new_network, recorded_did, EC_ADDRESS};
+use factom_did::client::did::{DIDKeyPurpose, KeyType, DID};
+use factom_did::client::keys::management::ManagementKey;
+use factom_did::client::simulator::simulate_entry;
+use factom_did::resolver::resolver::DIDResolver;
+
+#[test]
+fn accepted_entry_matches_the_recorded_one() {
+    let factom = new_network();
+    let did = recorded_did(&factom, vec![("did-key-1", vec![DIDKeyPurpose::PublicKey], KeyType::EdDSA)]);
+    let resolver = DIDResolver(&factom, None);
+    let updater = did.update().add_did_key("did-key-2", vec![DIDKeyPurpose::AuthenticationKey], KeyType::ECDSA, None, None);
+    let entry_data = updater.export_entry_data();
+
+    let result = simulate_entry(did.id, entry_data, resolver.read_entries(did.get_chain()));
+    assert!(result.accepted);
+    let changes = result.diff();
+    assert_eq!(changes.added_did_keys.iter().map(|k| k.alias).collect::<Vec<_>>(), vec!["did-key-2"]);
+    assert_eq!(changes.to_text(), "+ DID key did-key-2 (authentication)");
+
+    updater.record_on_chain(&factom, &factom, EC_ADDRESS, false);
+    let entries = resolver.read_entries(did.get_chain());
+    assert_eq!(entries[entries.len() - 1]["entryhash"], result.entry_hash);
+    let resolved = resolver.resolve_state(did.id, None);
+    assert!(factom_did::resolver::diff::diff(result.after, resolved).is_empty());
+}
+
+#[test]
+fn entry_signed_by_an_unknown_key_is_not_accepted() {
+    let factom = new_network();
+    let did = recorded_did(&factom, vec![]);
+    let resolver = DIDResolver(&factom, None);
+    let forged = DID(
+        did.id,
+        vec![ManagementKey("man-key-1", 0, KeyType::EdDSA, did.id, None, None, None)],
+        vec![],
+        vec![],
+        None,
+        None,
+    );
+    let entry_data = forged.update().add_management_key("man-key-2", 0, KeyType::EdDSA, None, None).export_entry_data();
+
+    let result = simulate_entry(did.id, entry_data, resolver.read_entries(did.get_chain()));
+    assert!(!result.accepted);
+    assert!(result.diff().is_empty());
+    assert_eq!(result.after.skipped_entries, result.before.skipped_entries + 1);
+}
+
+#[test]
+fn missing_entry_is_rejected() {
+    let factom = new_network();
+    let did = recorded_did(&factom, vec![]);
+    let entries = DIDResolver(&factom, None).read_entries(did.get_chain());
+    // An updater without any operation exports no entry
+    let entry_data = did.update().export_entry_data();
+    assert!(std::panic::catch_unwind(|| simulate_entry(did.id, entry_data, entries)).is_err());
+}
@@ -0,0 +1,202 @@
+mod common;
+
+use std::collections::HashMap;
+
+use common::{did_key, new_network, EC_ADDRESS};
+use factom_did::client::did::{DIDKeyPurpose, KeyType, DID};
+use factom_did::client::mock_factom::MockFactom;
+use factom_did::client::vc::{issue_credential, issue_presentation, verify_credential, verify_presentation, ProofFormat};
+use factom_did::resolver::resolver::DIDResolver;
+
+const CREDENTIALS_V2_CONTEXT: &str = "https://www.w3.org/ns/credentials/v2";
+
+fn recorded_did(factom: &MockFactom) -> DID {
+    return common::recorded_did(
+        factom,
+        vec![
+            ("issuer-key", vec![DIDKeyPurpose::PublicKey], KeyType::EdDSA),
+            ("holder-key", vec![DIDKeyPurpose::AuthenticationKey], KeyType::EdDSA),
+            ("ec-key", vec![DIDKeyPurpose::PublicKey], KeyType::ECDSA),
+        ],
+    );
+}
+
+fn credential() -> HashMap<_, _> {
+    return [
+        ("@context", vec![CREDENTIALS_V2_CONTEXT]),
+        ("type", vec!["VerifiableCredential", "ExampleDegreeCredential"]),
+        (
+            "credentialSubject",
+            [("id", "did:example:alice"), ("degree", "Bachelor of Science")]
+                .iter()
+                .cloned()
+                .collect::<HashMap<_, _>>(),
+        ),
+    ]
+    .iter()
+    .cloned()
+    .collect::<HashMap<_, _>>();
+}
+
+#[test]
+fn credentials_round_trip_in_all_proof_formats() {
+    let factom = new_network();
+    let did = recorded_did(&factom);
+    let resolver = DIDResolver(&factom, None);
+    for (alias, proof_format) in vec![
+        ("issuer-key", ProofFormat::Jwt),
+        ("ec-key", ProofFormat::Jwt),
+        ("issuer-key", ProofFormat::EddsaJcs2022),
+        ("issuer-key", ProofFormat::Ed25519Signature2020),
+    ] {
+        let secured = issue_credential(credential(), did_key(&did, alias), did.id, proof_format);
+        let verified = verify_credential(secured, &resolver, None, None);
+        assert_eq!(verified["issuer"], did.id);
+        assert_eq!(verified["credentialSubject"]["degree"], "Bachelor of Science");
+    }
+}
+
+#[test]
+fn tampered_credentials_are_rejected() {
+    let factom = new_network();
+    let did = recorded_did(&factom);
+    let resolver = DIDResolver(&factom, None);
+    for proof_format in vec![ProofFormat::EddsaJcs2022, ProofFormat::Ed25519Signature2020] {
+        let secured = issue_credential(credential(), did_key(&did, "issuer-key"), did.id, proof_format);
+        secured["credentialSubject"]["degree"] = "Doctor of Philosophy";
+        assert!(std::panic::catch_unwind(|| verify_credential(secured, &resolver, None, None)).is_err());
+    }
+}
+
+#[test]
+fn signing_key_must_be_suitable() {
+    let factom = new_network();
+    let did = recorded_did(&factom);
+    assert!(std::panic::catch_unwind(|| issue_credential(credential(), did_key(&did, "holder-key"), did.id, None)).is_err());
+    assert!(std::panic::catch_unwind(|| issue_credential(
+        credential(),
+        did_key(&did, "ec-key"),
+        did.id,
+        ProofFormat::EddsaJcs2022
+    ))
+    .is_err());
+    assert!(std::panic::catch_unwind(|| issue_credential(credential(), did.management_keys[0], did.id, None)).is_err());
+
+    let other_issuer = credential();
+    other_issuer.insert("issuer", "did:example:someone-else");
+    assert!(std::panic::catch_unwind(|| issue_credential(other_issuer, did_key(&did, "issuer-key"), did.id, None)).is_err());
+}
+
+#[test]
+fn key_must_be_active_at_issuance_time() {
+    let factom = new_network();
+    let did = recorded_did(&factom);
+    let resolver = DIDResolver(&factom, None);
+    let key = did_key(&did, "issuer-key");
+
+    // The DID is recorded at 2020-09-13T12:26:40Z and the key is revoked at 12:36:40Z
+    let issued_before_revocation = credential();
+    issued_before_revocation.insert("validFrom", "2020-09-13T12:30:00Z");
+    let issued_before_revocation = issue_credential(issued_before_revocation, key, did.id, None);
+    verify_credential(issued_before_revocation, &resolver, None, None);
+    for _ in 0..10 {
+        factom.advance_minute();
+    }
+    let updater = did.update().revoke_did_key("issuer-key");
+    updater.record_on_chain(&factom, &factom, EC_ADDRESS, false);
+    let issued_after_revocation = credential();
+    issued_after_revocation.insert("validFrom", "2020-09-13T12:40:00Z");
+    let issued_after_revocation = issue_credential(issued_after_revocation, key, did.id, None);
+
+    // Once the key is revoked, only a trusted timestamp at which it was active proves when the credential was signed
+    assert!(std::panic::catch_unwind(|| verify_credential(issued_before_revocation, &resolver, None, None)).is_err());
+    verify_credential(issued_before_revocation, &resolver, None, 1600000200);
+    assert!(std::panic::catch_unwind(|| verify_credential(issued_after_revocation, &resolver, None, None)).is_err());
+
+    let issued_before_creation = credential();
+    issued_before_creation.insert("validFrom", "2020-01-01T00:00:00Z");
+    let issued_before_creation = issue_credential(issued_before_creation, key, did.id, None);
+    assert!(std::panic::catch_unwind(|| verify_credential(issued_before_creation, &resolver, None, None)).is_err());
+}
+
+#[test]
+fn credentials_backdated_after_revocation_are_rejected() {
+    let factom = new_network();
+    let did = recorded_did(&factom);
+    let resolver = DIDResolver(&factom, None);
+    let key = did_key(&did, "issuer-key");
+    for _ in 0..10 {
+        factom.advance_minute();
+    }
+    did.update()
+        .revoke_did_key("issuer-key")
+        .record_on_chain(&factom, &factom, EC_ADDRESS, false);
+
+    // Signed with the revoked key after 12:36:40Z, but claiming to have been issued at 12:30:00Z
+    let backdated = credential();
+    backdated.insert("validFrom", "2020-09-13T12:30:00Z");
+    let backdated = issue_credential(backdated, key, did.id, ProofFormat::Jwt);
+    assert!(std::panic::catch_unwind(|| verify_credential(backdated, &resolver, None, None)).is_err());
+    // A trusted timestamp taken when the credential was actually seen is after the revocation
+    assert!(std::panic::catch_unwind(|| verify_credential(backdated, &resolver, None, 1600001000)).is_err());
+}
+
+#[test]
+fn validity_period_is_enforced() {
+    let factom = new_network();
+    let did = recorded_did(&factom);
+    let resolver = DIDResolver(&factom, None);
+    let expiring = credential();
+    expiring.insert("validUntil", "2030-01-01T00:00:00Z");
+    let secured = issue_credential(expiring, did_key(&did, "issuer-key"), did.id, ProofFormat::EddsaJcs2022);
+    verify_credential(secured, &resolver, None, None);
+    assert!(std::panic::catch_unwind(|| verify_credential(secured, &resolver, 1893456001, None)).is_err());
+
+    let not_valid_yet = credential();
+    not_valid_yet.insert("validFrom", "2099-01-01T00:00:00Z");
+    let secured = issue_credential(not_valid_yet, did_key(&did, "issuer-key"), did.id, None);
+    assert!(std::panic::catch_unwind(|| verify_credential(secured, &resolver, None, None)).is_err());
+}
+
+#[test]
+fn presentations_are_bound_to_challenge_and_holder_key() {
+    let factom = new_network();
+    let did = recorded_did(&factom);
+    let resolver = DIDResolver(&factom, None);
+    let jwt_credential = issue_credential(credential(), did_key(&did, "issuer-key"), did.id, None);
+    let embedded_credential = issue_credential(credential(), did_key(&did, "issuer-key"), did.id, ProofFormat::EddsaJcs2022);
+    let presentation = [
+        ("@context", vec![CREDENTIALS_V2_CONTEXT]),
+        ("type", vec!["VerifiablePresentation"]),
+        ("verifiableCredential", vec![jwt_credential, embedded_credential]),
+    ]
+    .iter()
+    .cloned()
+    .collect::<HashMap<_, _>>();
+
+    for proof_format in vec![ProofFormat::Jwt, ProofFormat::EddsaJcs2022] {
+        let secured = issue_presentation(
+            presentation,
+            did_key(&did, "holder-key"),
+            did.id,
+            proof_format,
+            "challenge-1",
+            "verifier.example.com",
+        );
+        let (verified, credentials) =
+            verify_presentation(secured, &resolver, "challenge-1", "verifier.example.com", None);
+        assert_eq!(verified["holder"], did.id);
+        assert_eq!(credentials.len(), 2);
+        assert!(std::panic::catch_unwind(|| verify_presentation(secured, &resolver, "challenge-2", None, None)).is_err());
+    }
+
+    assert!(std::panic::catch_unwind(|| issue_presentation(
+        presentation,
+        did_key(&did, "issuer-key"),
+        did.id,
+        None,
+        "challenge-1",
+        None
+    ))
+    .is_err());
+}